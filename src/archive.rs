@@ -0,0 +1,232 @@
+use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+
+use crate::user::*;
+use crate::client::*;
+use crate::goal::*;
+use crate::collateral::*;
+use crate::pronouns::*;
+use crate::note_day::*;
+use crate::note::*;
+use crate::migrations::schema_version;
+use crate::load_errors::*;
+use crate::utils::write_file_atomically;
+
+/// bumped whenever the layout of the archive file changes
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 6;
+
+/// the lists of records with IDs, as named in the archive file and in `next_ids`
+pub const ID_LISTS: [&str; 9] = [
+  "users",
+  "clients",
+  "goals",
+  "collaterals",
+  "general_collaterals",
+  "pronouns",
+  "note_days",
+  "note_templates",
+  "notes",
+];
+
+/// everything saved by the program, stored as a single JSON document
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ArchiveFile {
+  pub schema_version: u32,
+  pub users: Vec<User>,
+  pub clients: Vec<Client>,
+  pub goals: Vec<Goal>,
+  pub collaterals: Vec<Collateral>,
+  pub general_collaterals: Vec<Collateral>,
+  pub pronouns: Vec<Pronouns>,
+  pub note_days: Vec<NoteDay>,
+  pub note_templates: Vec<NoteTemplate>,
+  pub notes: Vec<Note>,
+  /// the ID each list's next new record gets, so IDs of deleted records are not reused
+  pub next_ids: BTreeMap<String, u32>,
+}
+
+impl ArchiveFile {
+  /// an empty archive with the default pronouns
+  pub fn new() -> ArchiveFile {
+    ArchiveFile {
+      schema_version: ARCHIVE_SCHEMA_VERSION,
+      users: vec![],
+      clients: vec![],
+      goals: vec![],
+      collaterals: vec![],
+      general_collaterals: vec![],
+      pronouns: Pronouns::defaults(),
+      note_days: vec![],
+      note_templates: vec![],
+      notes: vec![],
+      next_ids: BTreeMap::new(),
+    }
+  }
+  pub fn to_json(&self) -> Result<String, Error> {
+    serde_json::to_string_pretty(self).map_err(|e| Error::new(ErrorKind::InvalidData, e) )
+  }
+  /// parses an archive at the current schema version; older archives must be migrated first
+  pub fn from_json(s: &str) -> Result<ArchiveFile, Error> {
+    let value = Self::current_version_value(s)?;
+    serde_json::from_value(value).map_err(|e| Error::new(ErrorKind::InvalidData, e) )
+  }
+  /// like from_json, but records that cannot be read are left out and returned as errors instead of failing the whole file
+  pub fn from_json_with_errors(s: &str, filepath: &str) -> Result<(ArchiveFile, Vec<LoadError>), Error> {
+    let value = Self::current_version_value(s)?;
+    let mut errors: Vec<LoadError> = vec![];
+    let archive = ArchiveFile {
+      schema_version: ARCHIVE_SCHEMA_VERSION,
+      users: records(&value, "users", RecordKind::UserRecord, filepath, &mut errors)?,
+      clients: records(&value, "clients", RecordKind::ClientRecord, filepath, &mut errors)?,
+      goals: records(&value, "goals", RecordKind::GoalRecord, filepath, &mut errors)?,
+      collaterals: records(&value, "collaterals", RecordKind::CollateralRecord, filepath, &mut errors)?,
+      general_collaterals: records(&value, "general_collaterals", RecordKind::GeneralCollateralRecord, filepath, &mut errors)?,
+      pronouns: records(&value, "pronouns", RecordKind::PronounsRecord, filepath, &mut errors)?,
+      note_days: records(&value, "note_days", RecordKind::NoteDayRecord, filepath, &mut errors)?,
+      note_templates: records(&value, "note_templates", RecordKind::NoteTemplateRecord, filepath, &mut errors)?,
+      notes: records(&value, "notes", RecordKind::NoteRecord, filepath, &mut errors)?,
+      next_ids: match value.get("next_ids") {
+        Some(v) => serde_json::from_value(v.clone())
+          .map_err(|e| Error::new(ErrorKind::InvalidData, format!("Archive file has unreadable next IDs: {}", e)) )?,
+        None => return Err(Error::new(ErrorKind::InvalidData, "Archive file has no next IDs.")),
+      },
+    };
+    Ok((archive, errors))
+  }
+  fn current_version_value(s: &str) -> Result<Value, Error> {
+    let invalid = |e: String| Error::new(ErrorKind::InvalidData, e);
+    let value: Value = serde_json::from_str(s).map_err(|e| invalid(e.to_string()) )?;
+    let version = schema_version(&value).map_err(invalid)?;
+    if version > ARCHIVE_SCHEMA_VERSION {
+      return Err(invalid(format!(
+        "Archive uses schema version {}, but this version of the program only reads up to version {}.",
+        version,
+        ARCHIVE_SCHEMA_VERSION,
+      )));
+    }
+    if version < ARCHIVE_SCHEMA_VERSION {
+      return Err(invalid(format!(
+        "Archive uses schema version {} and needs to be upgraded to version {}. \
+        Run 'wrap_notes migrate --dry-run' to see the changes and 'wrap_notes migrate' to apply them.",
+        version,
+        ARCHIVE_SCHEMA_VERSION,
+      )));
+    }
+    Ok(value)
+  }
+  pub fn read(filepath: &str) -> Result<ArchiveFile, Error> {
+    Self::from_json(&Self::read_text(filepath)?)
+  }
+  pub fn read_with_errors(filepath: &str) -> Result<(ArchiveFile, Vec<LoadError>), Error> {
+    Self::from_json_with_errors(&Self::read_text(filepath)?, filepath)
+  }
+  /// whether the file is a plain-text archive, of any schema version
+  pub fn check_readable(filepath: &str) -> Result<(), Error> {
+    serde_json::from_str::<Value>(&Self::read_text(filepath)?)
+      .map(|_| ())
+      .map_err(|e| Error::new(ErrorKind::InvalidData, e) )
+  }
+  fn read_text(filepath: &str) -> Result<String, Error> {
+    fs::read_to_string(filepath).map_err(|e| match e.kind() {
+      // an encrypted archive is not valid UTF-8
+      ErrorKind::InvalidData => Error::new(ErrorKind::InvalidData, format!("{} is not a readable archive file.", filepath)),
+      _ => e,
+    })
+  }
+  pub fn write(&self, filepath: &str) -> Result<(), Error> {
+    write_file_atomically(filepath, self.to_json()?.as_bytes())
+  }
+}
+
+/// reads one record saved in the archive, naming the field that could not be read
+pub fn parse_json_record<T: DeserializeOwned>(raw: &str) -> Result<T, FieldError> {
+  let value: Value = serde_json::from_str(raw).map_err(|e| FieldError::new("record", e.to_string()) )?;
+  parse_json_value(value)
+}
+
+fn parse_json_value<T: DeserializeOwned>(value: Value) -> Result<T, FieldError> {
+  serde_path_to_error::deserialize(value).map_err(|e| {
+    let field = match e.path().to_string() {
+      p if p == "." => String::from("record"),
+      p => p,
+    };
+    FieldError::new(&field, e.inner().to_string())
+  })
+}
+
+fn records<T: DeserializeOwned>(
+  value: &Value,
+  key: &str,
+  kind: RecordKind,
+  filepath: &str,
+  errors: &mut Vec<LoadError>,
+) -> Result<Vec<T>, Error> {
+  let list = match value.get(key).and_then(|v| v.as_array() ) {
+    Some(list) => list,
+    None => return Err(Error::new(ErrorKind::InvalidData, format!("Archive file has no list of {}.", key))),
+  };
+  let mut records: Vec<T> = vec![];
+  for (i, record) in list.iter().enumerate() {
+    match parse_json_value(record.clone()) {
+      Ok(r) => records.push(r),
+      Err(e) => errors.push(LoadError::new(filepath, i + 1, kind, RecordFormat::JsonRecord, e, record.to_string())),
+    }
+  }
+  Ok(records)
+}
+
+impl Default for ArchiveFile {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::NaiveDate;
+
+  #[test]
+  fn note_text_round_trips() {
+    let content = String::from("Met with family | discussed plan\nSecond line with \"quotes\" and a \\ backslash.");
+    let mut note = Note::new(
+      1,
+      NaiveDate::from_ymd(2021, 3, 4),
+      NoteCategory::ICCNote(ICCNoteCategory::CareCoordination),
+      StructureType::Referral,
+      content.clone(),
+      1,
+      1,
+      vec![1, 2],
+    );
+    note.blanks.insert(0, (Blank::Pronoun1ForBlank(Some(2)), String::from("she | her"), vec![1, 2]));
+    let archive = ArchiveFile {
+      schema_version: ARCHIVE_SCHEMA_VERSION,
+      users: vec![],
+      clients: vec![],
+      goals: vec![Goal::new(1, 1, String::from("Goal with | pipe # and hash"))],
+      collaterals: vec![],
+      general_collaterals: vec![],
+      pronouns: vec![],
+      note_days: vec![],
+      note_templates: vec![],
+      notes: vec![note],
+      next_ids: BTreeMap::new(),
+    };
+    let read = ArchiveFile::from_json(&archive.to_json().unwrap()).unwrap();
+    assert_eq!(read.notes[0].content, content);
+    assert_eq!(read.notes[0].blanks, archive.notes[0].blanks);
+    assert_eq!(read.goals[0].goal, String::from("Goal with | pipe # and hash"));
+
+    let newer = archive.to_json().unwrap().replacen(
+      &format!("\"schema_version\": {}", ARCHIVE_SCHEMA_VERSION),
+      &format!("\"schema_version\": {}", ARCHIVE_SCHEMA_VERSION + 1),
+      1,
+    );
+    assert!(ArchiveFile::from_json(&newer).is_err());
+  }
+}
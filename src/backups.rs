@@ -0,0 +1,152 @@
+use chrono::{Duration, NaiveDateTime};
+use std::cmp::Reverse;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::utils::write_file_atomically;
+
+pub const DEFAULT_BACKUP_COUNT: usize = 10;
+/// the archive is saved after every action, so only keep a new backup once the newest is this old
+pub const BACKUP_INTERVAL_MINUTES: i64 = 10;
+pub const BACKUP_DIR: &str = "backups";
+
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%.3f";
+
+/// a copy of the archive saved in the backups folder as '<archive>.<timestamp>.bak'
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+  pub path: PathBuf,
+  pub created: NaiveDateTime,
+}
+
+impl Backup {
+  pub fn name(&self) -> String {
+    self.path.file_name().map(|n| n.to_string_lossy().to_string() ).unwrap_or_default()
+  }
+  pub fn fmt_created(&self) -> String {
+    self.created.format("%Y-%m-%d %H:%M:%S").to_string()
+  }
+}
+
+pub fn backup_dir(archive_filepath: &str) -> PathBuf {
+  match Path::new(archive_filepath).parent() {
+    Some(dir) => dir.join(BACKUP_DIR),
+    None => PathBuf::from(BACKUP_DIR),
+  }
+}
+
+fn archive_file_name(archive_filepath: &str) -> String {
+  Path::new(archive_filepath).file_name().map(|n| n.to_string_lossy().to_string() ).unwrap_or_default()
+}
+
+/// every backup of the archive, newest first
+pub fn list_backups(archive_filepath: &str) -> Result<Vec<Backup>, Error> {
+  let dir = backup_dir(archive_filepath);
+  if !dir.exists() {
+    return Ok(vec![]);
+  }
+  let prefix = format!("{}.", archive_file_name(archive_filepath));
+  let mut backups: Vec<Backup> = vec![];
+  for entry in fs::read_dir(&dir)? {
+    let path = entry?.path();
+    let name = match path.file_name() {
+      Some(n) => n.to_string_lossy().to_string(),
+      None => continue,
+    };
+    let timestamp = match name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".bak") ) {
+      Some(ts) => ts,
+      None => continue,
+    };
+    if let Ok(created) = NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT) {
+      backups.push(Backup { path, created });
+    }
+  }
+  backups.sort_by_key(|b| Reverse(b.created) );
+  Ok(backups)
+}
+
+/// copies the archive into the backups folder and removes the oldest backups beyond `keep`
+pub fn create_backup(archive_filepath: &str, keep: usize, now: NaiveDateTime) -> Result<Option<Backup>, Error> {
+  if keep == 0 || !Path::new(archive_filepath).exists() {
+    return Ok(None);
+  }
+  let dir = backup_dir(archive_filepath);
+  fs::create_dir_all(&dir)?;
+  let name = format!("{}.{}.bak", archive_file_name(archive_filepath), now.format(BACKUP_TIMESTAMP_FORMAT));
+  let backup = Backup { path: dir.join(name), created: now };
+  write_file_atomically(&backup.path, &fs::read(archive_filepath)?)?;
+  prune_backups(archive_filepath, keep)?;
+  Ok(Some(backup))
+}
+
+pub fn prune_backups(archive_filepath: &str, keep: usize) -> Result<(), Error> {
+  for old in list_backups(archive_filepath)?.into_iter().skip(keep) {
+    fs::remove_file(old.path)?;
+  }
+  Ok(())
+}
+
+/// backs up the archive before it is overwritten with `new_contents`, unless nothing changed
+/// or the newest backup is less than BACKUP_INTERVAL_MINUTES old
+pub fn backup_if_due(archive_filepath: &str, new_contents: &[u8], keep: usize, now: NaiveDateTime) -> Result<Option<Backup>, Error> {
+  match fs::read(archive_filepath) {
+    Ok(current) if current == new_contents => return Ok(None),
+    Ok(_) => (),
+    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+    Err(e) => return Err(e),
+  }
+  if let Some(newest) = list_backups(archive_filepath)?.first() {
+    if now - newest.created < Duration::minutes(BACKUP_INTERVAL_MINUTES) {
+      return Ok(None);
+    }
+  }
+  create_backup(archive_filepath, keep, now)
+}
+
+/// replaces the archive with a backup; the current archive is backed up first so the restore can be undone
+pub fn restore_backup(archive_filepath: &str, backup: &Backup, keep: usize, now: NaiveDateTime) -> Result<Option<Backup>, Error> {
+  let contents = fs::read(&backup.path)?;
+  let saved = create_backup(archive_filepath, keep.max(1), now)?;
+  write_file_atomically(archive_filepath, &contents)?;
+  Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::NaiveDate;
+
+  #[test]
+  fn rotates_and_restores_backups() {
+    let dir = std::env::temp_dir().join("wrap_notes_test_backups");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let archive_filepath = dir.join("archive.json").to_string_lossy().to_string();
+    let start = NaiveDate::from_ymd(2021, 3, 4).and_hms(9, 0, 0);
+
+    assert_eq!(backup_if_due(&archive_filepath, b"v1", 2, start).unwrap(), None);
+    write_file_atomically(&archive_filepath, b"v1").unwrap();
+    assert_eq!(backup_if_due(&archive_filepath, b"v1", 2, start).unwrap(), None);
+    for (i, version) in ["v2", "v3", "v4"].iter().enumerate() {
+      let now = start + Duration::minutes(BACKUP_INTERVAL_MINUTES * (i as i64 + 1));
+      assert!(backup_if_due(&archive_filepath, version.as_bytes(), 2, now).unwrap().is_some());
+      // a second save soon after does not replace the backup
+      assert_eq!(backup_if_due(&archive_filepath, b"v5", 2, now + Duration::minutes(1)).unwrap(), None);
+      write_file_atomically(&archive_filepath, version.as_bytes()).unwrap();
+    }
+
+    let backups = list_backups(&archive_filepath).unwrap();
+    assert_eq!(backups.len(), 2);
+    assert_eq!(fs::read(&backups[0].path).unwrap(), b"v3");
+    assert_eq!(fs::read(&backups[1].path).unwrap(), b"v2");
+
+    let now = start + Duration::hours(2);
+    restore_backup(&archive_filepath, &backups[1], 2, now).unwrap();
+    assert_eq!(fs::read(&archive_filepath).unwrap(), b"v2");
+    let backups = list_backups(&archive_filepath).unwrap();
+    assert_eq!(backups[0].created, now);
+    assert_eq!(fs::read(&backups[0].path).unwrap(), b"v4");
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
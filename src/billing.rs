@@ -0,0 +1,399 @@
+use chrono::{Duration, NaiveDate, NaiveTime};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::note::*;
+use crate::note_archive::*;
+
+use NoteCategory::{ICCNote, FPNote};
+use FPNoteCategory::{DescriptionOfIntervention, ResponseToIntervention};
+
+pub const DEFAULT_UNIT_MINUTES: u32 = 15;
+/// the longest time that can be entered for one contact
+pub const MAX_NOTE_MINUTES: u32 = 24 * 60;
+
+/// what a note is billed as: ICC contacts by category, FP contacts by intervention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BillingCode {
+  IccContact(ICCNoteCategory),
+  FpIntervention(FPIntervention),
+  /// FP sections that do not name an intervention, such as Functioning
+  FpNoIntervention,
+}
+
+use BillingCode::{IccContact, FpIntervention as FpInterventionCode, FpNoIntervention};
+
+impl BillingCode {
+  pub fn for_category(category: &NoteCategory) -> BillingCode {
+    match category {
+      ICCNote(c) => IccContact(*c),
+      FPNote(DescriptionOfIntervention(Some(i))) | FPNote(ResponseToIntervention(Some(i))) => FpInterventionCode(*i),
+      FPNote(_) => FpNoIntervention,
+    }
+  }
+  pub fn all() -> Vec<BillingCode> {
+    let mut codes: Vec<BillingCode> = ICCNoteCategory::iterator().map(IccContact).collect();
+    codes.extend(FPIntervention::iterator().map(FpInterventionCode));
+    codes.push(FpNoIntervention);
+    codes
+  }
+  /// the name used for a code in the config file ('daily_unit_cap.<key>') and in CSV files
+  pub fn key(&self) -> &'static str {
+    match self {
+      IccContact(ICCNoteCategory::FaceToFaceContactWithClient) => "FaceToFaceContactWithClient",
+      IccContact(ICCNoteCategory::TelephoneContactWithClient) => "TelephoneContactWithClient",
+      IccContact(ICCNoteCategory::CareCoordination) => "CareCoordination",
+      IccContact(ICCNoteCategory::Documentation) => "Documentation",
+      IccContact(ICCNoteCategory::CarePlanningTeam) => "CarePlanningTeam",
+      IccContact(ICCNoteCategory::TransportClient) => "TransportClient",
+      IccContact(ICCNoteCategory::MemberOutreachNoShow) => "MemberOutreachNoShow",
+      FpInterventionCode(FPIntervention::FaceToFaceContact) => "FaceToFaceContact",
+      FpInterventionCode(FPIntervention::CollateralContact) => "CollateralContact",
+      FpInterventionCode(FPIntervention::CrisisSupport) => "CrisisSupport",
+      FpInterventionCode(FPIntervention::TelephoneSupport) => "TelephoneSupport",
+      FpInterventionCode(FPIntervention::DirectTimeWithProviders) => "DirectTimeWithProviders",
+      FpInterventionCode(FPIntervention::EducatingCoachingModelingAndGuiding) => "EducatingCoachingModelingAndGuiding",
+      FpInterventionCode(FPIntervention::EngageParentCaregiverInAddressingGoals) => "EngageParentCaregiverInAddressingGoals",
+      FpInterventionCode(FPIntervention::TeachAdvocacyGuideLinkageToResources) => "TeachAdvocacyGuideLinkageToResources",
+      FpInterventionCode(FPIntervention::TeachNetworkingInCommunityAndWithProviders) => "TeachNetworkingInCommunityAndWithProviders",
+      FpInterventionCode(FPIntervention::ProviderOutreachToPerson) => "ProviderOutreachToPerson",
+      FpInterventionCode(FPIntervention::MemberTransportationByStaff) => "MemberTransportationByStaff",
+      FpInterventionCode(FPIntervention::NoShowLateCancellation) => "NoShowLateCancellation",
+      FpInterventionCode(FPIntervention::FPInterventionDocumentation) => "FPInterventionDocumentation",
+      FpInterventionCode(FPIntervention::Other) => "FPInterventionOther",
+      FpNoIntervention => "FPNoIntervention",
+    }
+  }
+}
+
+impl fmt::Display for BillingCode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      IccContact(c) => write!(f, "ICC - {}", c),
+      FpInterventionCode(i) => write!(f, "FP - {}", i),
+      FpNoIntervention => write!(f, "FP - No intervention"),
+    }
+  }
+}
+
+/// how minutes become units and how many units a client may have in a day
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillingSettings {
+  pub unit_minutes: u32,
+  /// units per client per day across all codes
+  pub daily_cap: Option<u32>,
+  /// units per client per day for one code, keyed by BillingCode::key
+  pub code_caps: BTreeMap<String, u32>,
+}
+
+impl Default for BillingSettings {
+  fn default() -> Self {
+    BillingSettings { unit_minutes: DEFAULT_UNIT_MINUTES, daily_cap: None, code_caps: BTreeMap::new() }
+  }
+}
+
+impl BillingSettings {
+  /// each contact is billed on its own, rounding to the nearest unit, so 8 to 22 minutes is one 15 minute unit
+  pub fn units(&self, minutes: u32) -> u32 {
+    if self.unit_minutes == 0 {
+      return 0;
+    }
+    minutes.saturating_add(self.unit_minutes / 2) / self.unit_minutes
+  }
+}
+
+/// one client's notes for one code on one day
+#[derive(Debug, Clone, Copy, Default)]
+struct DayTotal {
+  notes: usize,
+  minutes: u32,
+  units: u32,
+}
+
+/// a client's total for one code over the month
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillingLine {
+  pub client_id: u32,
+  pub client_name: String,
+  pub code: BillingCode,
+  pub notes: usize,
+  pub minutes: u32,
+  pub units: u32,
+  /// days where this code, or the client's total, went over a cap
+  pub dates_over_cap: Vec<NaiveDate>,
+}
+
+/// a day where a client had more units than a cap allows; `code` is None for the daily total
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapExceeded {
+  pub client_name: String,
+  pub date: NaiveDate,
+  pub code: Option<BillingCode>,
+  pub units: u32,
+  pub cap: u32,
+}
+
+impl CapExceeded {
+  pub fn description(&self) -> String {
+    let what = match self.code {
+      Some(code) => code.to_string(),
+      None => String::from("all contacts"),
+    };
+    format!(
+      "{} on {}: {} units of {}, over the cap of {}.",
+      self.client_name,
+      self.date.format("%Y-%m-%d"),
+      self.units,
+      what,
+      self.cap,
+    )
+  }
+}
+
+/// units per client per code for the notes dated `first` to `last`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillingReport {
+  pub first: NaiveDate,
+  pub last: NaiveDate,
+  pub lines: Vec<BillingLine>,
+  pub over_caps: Vec<CapExceeded>,
+  /// IDs of notes in range with no duration, which are not counted
+  pub untimed_note_ids: Vec<u32>,
+}
+
+impl BillingReport {
+  pub fn new(
+    a: &NoteArchive,
+    first: NaiveDate,
+    last: NaiveDate,
+    user_id: Option<u32>,
+    client_id: Option<u32>,
+    settings: &BillingSettings,
+  ) -> BillingReport {
+    let mut totals: BTreeMap<(u32, BillingCode), BTreeMap<NaiveDate, DayTotal>> = BTreeMap::new();
+    let mut untimed_note_ids: Vec<u32> = vec![];
+    for n in &a.notes {
+      if n.date < first || n.date > last {
+        continue;
+      }
+      if user_id.is_some_and(|id| n.foreign_key["user_id"] != id ) || client_id.is_some_and(|id| n.foreign_key["client_id"] != id ) {
+        continue;
+      }
+      let minutes = match n.duration_minutes {
+        Some(m) => m,
+        None => {
+          untimed_note_ids.push(n.id);
+          continue;
+        },
+      };
+      let day = totals
+        .entry((n.foreign_key["client_id"], BillingCode::for_category(&n.category)))
+        .or_default()
+        .entry(n.date)
+        .or_default();
+      day.notes += 1;
+      day.minutes += minutes;
+      day.units += settings.units(minutes);
+    }
+    untimed_note_ids.sort_unstable();
+
+    let client_name = |id: u32| match a.get_client_by_id(id) {
+      Some(c) => c.full_name(),
+      None => format!("Client {}", id),
+    };
+    let mut over_caps: Vec<CapExceeded> = vec![];
+    let mut daily_totals: BTreeMap<(u32, NaiveDate), u32> = BTreeMap::new();
+    for ((c_id, code), days) in &totals {
+      for (date, day) in days {
+        *daily_totals.entry((*c_id, *date)).or_default() += day.units;
+        if let Some(cap) = settings.code_caps.get(code.key()) {
+          if day.units > *cap {
+            over_caps.push(CapExceeded { client_name: client_name(*c_id), date: *date, code: Some(*code), units: day.units, cap: *cap });
+          }
+        }
+      }
+    }
+    if let Some(cap) = settings.daily_cap {
+      for ((c_id, date), units) in &daily_totals {
+        if *units > cap {
+          over_caps.push(CapExceeded { client_name: client_name(*c_id), date: *date, code: None, units: *units, cap });
+        }
+      }
+    }
+    over_caps.sort_by(|x, y| (&x.client_name, x.date).cmp(&(&y.client_name, y.date)) );
+
+    let mut lines: Vec<BillingLine> = totals.iter().map(|((c_id, code), days)| {
+      let over_total = |date: &NaiveDate| settings.daily_cap.is_some_and(|cap| daily_totals[&(*c_id, *date)] > cap );
+      let over_code = |units: u32| settings.code_caps.get(code.key()).is_some_and(|cap| units > *cap );
+      BillingLine {
+        client_id: *c_id,
+        client_name: client_name(*c_id),
+        code: *code,
+        notes: days.values().map(|d| d.notes ).sum(),
+        minutes: days.values().map(|d| d.minutes ).sum(),
+        units: days.values().map(|d| d.units ).sum(),
+        dates_over_cap: days.iter()
+          .filter(|(date, d)| over_code(d.units) || over_total(date) )
+          .map(|(date, _)| *date )
+          .collect(),
+      }
+    }).collect();
+    lines.sort_by(|x, y| (&x.client_name, x.client_id, x.code).cmp(&(&y.client_name, y.client_id, y.code)) );
+
+    BillingReport { first, last, lines, over_caps, untimed_note_ids }
+  }
+  pub fn total_units(&self) -> u32 {
+    self.lines.iter().map(|l| l.units ).sum()
+  }
+  pub fn to_display_string(&self) -> String {
+    let mut output = format!(
+      "Billing units from {} to {}\n\n",
+      self.first.format("%Y-%m-%d"),
+      self.last.format("%Y-%m-%d"),
+    );
+    if self.lines.is_empty() {
+      output.push_str("No timed notes in this period.\n");
+    }
+    let mut current_client: Option<u32> = None;
+    for line in &self.lines {
+      if current_client != Some(line.client_id) {
+        let client_units: u32 = self.lines.iter().filter(|l| l.client_id == line.client_id ).map(|l| l.units ).sum();
+        output.push_str(&format!("{} ({} units)\n", line.client_name, client_units));
+        current_client = Some(line.client_id);
+      }
+      output.push_str(&format!(
+        "  {: <55} {: >4} notes {: >6} min {: >4} units{}\n",
+        line.code.to_string(),
+        line.notes,
+        line.minutes,
+        line.units,
+        if line.dates_over_cap.is_empty() { "" } else { "  *over cap" },
+      ));
+    }
+    output.push_str(&format!("\nTotal: {} units\n", self.total_units()));
+    for over in &self.over_caps {
+      output.push_str(&format!("Over cap: {}\n", over.description()));
+    }
+    if !self.untimed_note_ids.is_empty() {
+      output.push_str(&format!(
+        "{} notes have no time entered and are not counted (IDs {}).\n",
+        self.untimed_note_ids.len(),
+        self.untimed_note_ids.iter().map(|id| id.to_string() ).collect::<Vec<String>>().join(", "),
+      ));
+    }
+    output
+  }
+  /// one row per client and code, with the days over a cap listed in the last column
+  pub fn to_csv(&self) -> String {
+    let mut output = String::from("client_id,client_name,from,to,code,description,notes,minutes,units,dates_over_cap\n");
+    for line in &self.lines {
+      let fields = [
+        line.client_id.to_string(),
+        line.client_name.clone(),
+        self.first.format("%Y-%m-%d").to_string(),
+        self.last.format("%Y-%m-%d").to_string(),
+        line.code.key().to_string(),
+        line.code.to_string(),
+        line.notes.to_string(),
+        line.minutes.to_string(),
+        line.units.to_string(),
+        line.dates_over_cap.iter().map(|d| d.format("%Y-%m-%d").to_string() ).collect::<Vec<String>>().join(";"),
+      ];
+      output.push_str(&fields.iter().map(|f| csv_field(f) ).collect::<Vec<String>>().join(","));
+      output.push('\n');
+    }
+    output
+  }
+}
+
+/// quotes a CSV field if it contains a comma, quote or line break
+pub fn csv_field(s: &str) -> String {
+  if s.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+/// reads a contact's time as minutes ('45') or as a start and end time ('9:00-9:45' or '9:00am-10:15am');
+/// an end time earlier than the start is taken to be the next day, so '23:30-0:30' is an hour
+pub fn parse_note_time(s: &str) -> Result<(Option<NaiveTime>, u32), String> {
+  let s = s.trim();
+  if let Ok(minutes) = s.parse::<u32>() {
+    if minutes > MAX_NOTE_MINUTES {
+      return Err(format!("A contact can be at most {} minutes long, not {}.", MAX_NOTE_MINUTES, minutes));
+    }
+    return Ok((None, minutes));
+  }
+  let (start, end) = s.split_once('-')
+    .ok_or(format!("Invalid time (expected minutes, or a start and end time such as 9:00-9:45): {}", s))?;
+  let start = parse_clock_time(start)?;
+  let end = parse_clock_time(end)?;
+  if end == start {
+    return Err(format!("End time {} is the same as the start time.", end.format("%-I:%M %p")));
+  }
+  let duration = if end < start { end - start + Duration::days(1) } else { end - start };
+  Ok((Some(start), duration.num_minutes() as u32))
+}
+
+fn parse_clock_time(s: &str) -> Result<NaiveTime, String> {
+  let compact: String = s.chars().filter(|c| !c.is_whitespace() ).collect::<String>().to_ascii_uppercase();
+  NaiveTime::parse_from_str(&compact, "%I:%M%p")
+    .or_else(|_| NaiveTime::parse_from_str(&compact, "%H:%M") )
+    .map_err(|_| format!("Invalid time of day: {}", s.trim()) )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn totals_units_and_flags_caps() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_billing_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    let day = NaiveDate::from_ymd(2021, 3, 4);
+    a.notes.clear();
+    for (id, category, minutes) in [
+      (1, ICCNote(ICCNoteCategory::CareCoordination), Some(20)),
+      (2, ICCNote(ICCNoteCategory::CareCoordination), Some(7)),
+      (3, FPNote(DescriptionOfIntervention(Some(FPIntervention::CrisisSupport))), Some(60)),
+      (4, ICCNote(ICCNoteCategory::Documentation), None),
+    ] {
+      let mut n = Note::new(id, day, category, StructureType::Referral, String::new(), 1, 1, vec![]);
+      n.set_time(None, minutes);
+      a.notes.push(n);
+    }
+    let settings = BillingSettings {
+      unit_minutes: 15,
+      daily_cap: Some(4),
+      code_caps: [(String::from("CareCoordination"), 1)].iter().cloned().collect(),
+    };
+    let report = BillingReport::new(&a, day, day, None, None, &settings);
+    assert_eq!(report.untimed_note_ids, vec![4]);
+    assert_eq!(report.lines.len(), 2);
+    let care = report.lines.iter().find(|l| l.code == IccContact(ICCNoteCategory::CareCoordination) ).unwrap();
+    assert_eq!((care.notes, care.minutes, care.units), (2, 27, 1));
+    assert_eq!(report.total_units(), 5);
+    assert_eq!(report.over_caps.len(), 1);
+    assert_eq!(report.over_caps[0].code, None);
+    assert!(report.to_csv().lines().nth(1).unwrap().ends_with(",1,2021-03-04"));
+    assert_eq!(csv_field("Lee, Ann \"A\""), String::from("\"Lee, Ann \"\"A\"\"\""));
+  }
+
+  #[test]
+  fn parses_minutes_and_time_ranges() {
+    assert_eq!(parse_note_time("45"), Ok((None, 45)));
+    assert_eq!(parse_note_time("9:00 - 9:45"), Ok((Some(NaiveTime::from_hms(9, 0, 0)), 45)));
+    assert_eq!(parse_note_time("11:30am-1:00 PM"), Ok((Some(NaiveTime::from_hms(11, 30, 0)), 90)));
+    assert_eq!(parse_note_time("23:30-0:30"), Ok((Some(NaiveTime::from_hms(23, 30, 0)), 60)));
+    assert!(parse_note_time("9:00-9:00").is_err());
+    assert_eq!(parse_note_time("1440"), Ok((None, 1440)));
+    assert!(parse_note_time("4294967295").is_err());
+    assert_eq!(BillingSettings::default().units(u32::MAX), u32::MAX / 15);
+    assert!(parse_note_time("soon").is_err());
+  }
+}
@@ -1,5 +1,6 @@
-use std::{fmt, io, thread, time};
+use std::{fmt, thread, time};
 use ansi_term::Colour::{Black, Red, Blue};
+use crate::idle_lock::read_input_line;
 
 // print errors
 pub fn print_err(s: String) {
@@ -97,7 +98,7 @@ impl InternalDocumentFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -108,7 +109,7 @@ impl InternalDocumentFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -240,7 +241,7 @@ impl ExternalDocumentFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -251,7 +252,7 @@ impl ExternalDocumentFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -357,7 +358,7 @@ impl InternalMeetingFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -368,7 +369,7 @@ impl InternalMeetingFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -478,7 +479,7 @@ impl ExternalMeetingFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -489,7 +490,7 @@ impl ExternalMeetingFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -655,7 +656,7 @@ impl AppearanceFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -666,7 +667,7 @@ impl AppearanceFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -857,7 +858,7 @@ impl SupportedParentFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -868,7 +869,7 @@ impl SupportedParentFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -1043,7 +1044,7 @@ impl ParentingSkillFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -1054,7 +1055,7 @@ impl ParentingSkillFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -1235,7 +1236,7 @@ impl CarePlanningTopicFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -1246,7 +1247,7 @@ impl CarePlanningTopicFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -1501,7 +1502,7 @@ impl YouthTopicFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -1512,7 +1513,7 @@ impl YouthTopicFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -1733,7 +1734,7 @@ impl ContactMethodFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -1744,7 +1745,7 @@ impl ContactMethodFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -1861,7 +1862,7 @@ impl ContactPurposeFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -1872,7 +1873,7 @@ impl ContactPurposeFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -2004,7 +2005,7 @@ impl FulfilledContactPurposeFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -2015,7 +2016,7 @@ impl FulfilledContactPurposeFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -2138,7 +2139,7 @@ impl ServiceFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -2149,7 +2150,7 @@ impl ServiceFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -2254,7 +2255,7 @@ impl MeetingMethodFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -2265,7 +2266,7 @@ impl MeetingMethodFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -2356,7 +2357,7 @@ impl SignatureMethodFillIn {
       println_inst!("Enter text for the selected blank.");
       'get_string: loop {
         let mut input = String::new();
-        let input_result = io::stdin().read_line(&mut input);
+        let input_result = read_input_line(&mut input);
         match input_result {
           Err(_) => {
             println_err!("Invalid entry.");
@@ -2367,7 +2368,7 @@ impl SignatureMethodFillIn {
             break loop {
               println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
               let mut confirm_input = String::new();
-              let confirm_input_result = io::stdin().read_line(&mut confirm_input);
+              let confirm_input_result = read_input_line(&mut confirm_input);
               match confirm_input_result {
                 Err(_) => {
                   println_err!("Invalid entry.");
@@ -0,0 +1,1146 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::note_archive::*;
+use crate::note_day::*;
+use crate::config::*;
+use crate::migrations::*;
+use crate::backups::*;
+use crate::export::*;
+use crate::ehr_entry::*;
+use crate::pdf::*;
+use crate::billing::*;
+use crate::travel::*;
+use crate::dashboard::*;
+use crate::compliance::*;
+use crate::search::*;
+use crate::note_filter::*;
+use crate::vocabulary::*;
+use crate::template_library::*;
+use crate::goal_report::*;
+use crate::goal::*;
+use crate::note::{Note, NoteTemplate};
+use crate::client::*;
+use crate::utils::write_file_atomically;
+
+pub const CLI_USAGE: &str = "\
+Usage: wrap_notes [--data-dir PATH | --profile NAME] [COMMAND] [OPTIONS]
+
+Run without a command to start the interactive program.
+The data directory can also be set with WRAP_NOTES_DATA_DIR or WRAP_NOTES_PROFILE,
+or in the config file (see 'profile list').
+
+Commands:
+  user list
+  client list [--user ID] [--status active|inactive]
+  client add --first NAME --last NAME --dob YYYY-MM-DD [--pronouns ID] [--user ID] [--enrolled YYYY-MM-DD]
+  client discharge --client ID [--date YYYY-MM-DD] --reason TEXT
+  client reactivate --client ID [--referred YYYY-MM-DD] [--enrolled YYYY-MM-DD]
+  goal list --client ID
+  goal objective --goal ID --text TEXT [--baseline TEXT] [--target YYYY-MM-DD]
+  goal progress --goal ID --rating RATING [--date YYYY-MM-DD] [--comment TEXT]
+  goal status --goal ID --status active|met|discontinued
+  goal report --client ID [--since YYYY-MM-DD] [--until YYYY-MM-DD] [--csv PATH]
+  note-day list [--user ID] [--client ID] [--since YYYY-MM-DD] [--until YYYY-MM-DD]
+  note-day add --user ID --client ID --date YYYY-MM-DD [--date YYYY-MM-DD ...]
+  dashboard --user ID
+  deadlines --user ID [--client ID] [--csv PATH]
+  search --query TEXT [--user ID] [--client ID] [--since YYYY-MM-DD] [--until YYYY-MM-DD] [--category NAME]
+  note list [--user ID] [--client ID] [--note-day ID] [--since YYYY-MM-DD] [--until YYYY-MM-DD]
+            [--collateral ID] [--structure NAME] [--category NAME] [--unfilled yes|no]
+  note print (--note-day ID | --note ID)
+  note time --note ID --time (MINUTES | HH:MM-HH:MM)
+  note travel --note ID --miles N [--minutes N] [--from PLACE] [--to PLACE]
+  billing --month YYYY-MM [--user ID] [--client ID] [--csv PATH]
+  mileage --user ID [--date YYYY-MM-DD] [--csv PATH]
+  ehr --note-day ID [--dir PATH | --copy]
+  export (--note-day ID | --client ID [--since YYYY-MM-DD] [--until YYYY-MM-DD]) [--format md|html|txt|all] [--dir PATH]
+  pdf (--note-day ID | --client ID --month YYYY-MM) [--output PATH]
+  template list [--user ID]
+  template check [--user ID]
+  template export --user ID [--template ID ...] [--output PATH]
+  template import --user ID --file PATH [--dry-run]
+  vocabulary list [--blank NAME]
+  vocabulary export [--output PATH]
+  migrate [--dry-run]
+  restore [--backup N]
+  profile list
+  profile add --name NAME --dir PATH
+  profile default --name NAME
+  help
+";
+
+/// options that take no value
+const CLI_FLAGS: &[&str] = &["dry-run", "copy"];
+
+/// positional words, '--option value' pairs and '--flag' switches from the command line
+pub struct CliArgs {
+  pub positional: Vec<String>,
+  pub options: HashMap<String, Vec<String>>,
+  pub flags: Vec<String>,
+}
+
+impl CliArgs {
+  pub fn parse(args: &[String]) -> Result<CliArgs, String> {
+    let mut positional: Vec<String> = vec![];
+    let mut options: HashMap<String, Vec<String>> = HashMap::new();
+    let mut flags: Vec<String> = vec![];
+    let mut i = 0;
+    while i < args.len() {
+      let arg = &args[i];
+      if let Some(name) = arg.strip_prefix("--") {
+        if CLI_FLAGS.contains(&name) {
+          flags.push(name.to_string());
+          i += 1;
+          continue;
+        }
+        match args.get(i+1) {
+          Some(value) if !value.starts_with("--") => {
+            options.entry(name.to_string()).or_default().push(value.clone());
+            i += 2;
+          },
+          _ => return Err(format!("Missing value for option '--{}'.", name)),
+        }
+      } else {
+        positional.push(arg.clone());
+        i += 1;
+      }
+    }
+    Ok(CliArgs { positional, options, flags })
+  }
+  pub fn has_flag(&self, name: &str) -> bool {
+    self.flags.iter().any(|f| f == name )
+  }
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self.options.get(name).and_then(|v| v.last() ).map(|s| &s[..] )
+  }
+  pub fn get_all(&self, name: &str) -> Vec<&str> {
+    match self.options.get(name) {
+      Some(v) => v.iter().map(|s| &s[..] ).collect(),
+      None => vec![],
+    }
+  }
+  pub fn require(&self, name: &str) -> Result<&str, String> {
+    self.get(name).ok_or_else(|| format!("Missing required option '--{}'.", name) )
+  }
+  pub fn get_id(&self, name: &str) -> Result<Option<u32>, String> {
+    match self.get(name) {
+      Some(s) => s.parse().map(Some).map_err(|_| format!("Invalid ID for '--{}': {}", name, s) ),
+      None => Ok(None),
+    }
+  }
+  pub fn require_id(&self, name: &str) -> Result<u32, String> {
+    self.get_id(name)?.ok_or_else(|| format!("Missing required option '--{}'.", name) )
+  }
+  pub fn get_date(&self, name: &str) -> Result<Option<NaiveDate>, String> {
+    match self.get(name) {
+      Some(s) => parse_cli_date(s).map(Some),
+      None => Ok(None),
+    }
+  }
+}
+
+pub fn parse_cli_date(s: &str) -> Result<NaiveDate, String> {
+  NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", s) )
+}
+
+/// runs a single non-interactive command against the archive and returns its output
+pub fn run_command(a: &mut NoteArchive, args: &[String]) -> Result<String, String> {
+  let cli_args = CliArgs::parse(args)?;
+  let words: Vec<&str> = cli_args.positional.iter().map(|s| &s[..] ).collect();
+  match &words[..] {
+    ["help"] | [] => Ok(String::from(CLI_USAGE)),
+    ["user", "list"] => Ok(cli_user_list(a)),
+    ["client", "list"] => cli_client_list(a, &cli_args),
+    ["client", "add"] => check_no_load_errors(a).and_then(|_| cli_client_add(a, &cli_args) ),
+    ["client", "discharge"] => check_no_load_errors(a).and_then(|_| cli_client_discharge(a, &cli_args) ),
+    ["client", "reactivate"] => check_no_load_errors(a).and_then(|_| cli_client_reactivate(a, &cli_args) ),
+    ["goal", "list"] => cli_goal_list(a, &cli_args),
+    ["goal", "objective"] => check_no_load_errors(a).and_then(|_| cli_goal_objective(a, &cli_args) ),
+    ["goal", "progress"] => check_no_load_errors(a).and_then(|_| cli_goal_progress(a, &cli_args) ),
+    ["goal", "status"] => check_no_load_errors(a).and_then(|_| cli_goal_status(a, &cli_args) ),
+    ["goal", "report"] => cli_goal_report(a, &cli_args),
+    ["note-day", "list"] => cli_note_day_list(a, &cli_args),
+    ["note-day", "add"] => check_no_load_errors(a).and_then(|_| cli_note_day_add(a, &cli_args) ),
+    ["dashboard"] => cli_dashboard(a, &cli_args),
+    ["deadlines"] => cli_deadlines(a, &cli_args),
+    ["search"] => cli_search(a, &cli_args),
+    ["note", "list"] => cli_note_list(a, &cli_args),
+    ["note", "print"] => cli_note_print(a, &cli_args),
+    ["note", "time"] => check_no_load_errors(a).and_then(|_| cli_note_time(a, &cli_args) ),
+    ["note", "travel"] => check_no_load_errors(a).and_then(|_| cli_note_travel(a, &cli_args) ),
+    ["billing"] => cli_billing(a, &cli_args),
+    ["mileage"] => cli_mileage(a, &cli_args),
+    ["template", "list"] => cli_template_list(a, &cli_args),
+    ["template", "check"] => cli_template_check(a, &cli_args),
+    ["template", "export"] => cli_template_export(a, &cli_args),
+    ["template", "import"] => cli_template_import(a, &cli_args),
+    ["vocabulary", "list"] => cli_vocabulary_list(a, &cli_args),
+    ["vocabulary", "export"] => cli_vocabulary_export(a, &cli_args),
+    ["export"] => cli_export(a, &cli_args),
+    ["ehr"] => cli_ehr(a, &cli_args),
+    ["pdf"] => cli_pdf(a, &cli_args),
+    _ => Err(format!("Unknown command: '{}'.\n\n{}", words.join(" "), CLI_USAGE)),
+  }
+}
+
+/// handles commands that edit the config file rather than an archive, returning None for any other command
+pub fn run_profile_command(args: &[String]) -> Option<Result<String, String>> {
+  match args.first() {
+    Some(word) if word == "profile" => (),
+    _ => return None,
+  }
+  let result = CliArgs::parse(args).and_then(|cli_args| {
+    let mut config = Config::load()?;
+    let words: Vec<&str> = cli_args.positional.iter().map(|s| &s[..] ).collect();
+    match &words[..] {
+      ["profile", "list"] => {
+        let mut output = match config_file_path() {
+          Some(fp) => format!("Config file: {}\n", fp.display()),
+          None => String::from("Config file: (no config directory found)\n"),
+        };
+        for (name, dir) in &config.profiles {
+          let marker = if config.default_profile.as_ref() == Some(name) { "*" } else { " " };
+          output.push_str(&format!("{} {}\t{}\n", marker, name, dir.display()));
+        }
+        Ok(output)
+      },
+      ["profile", "add"] => {
+        let name = cli_args.require("name")?.trim().to_string();
+        if name.is_empty() || name.contains(char::is_whitespace) || name.contains('=') {
+          return Err(format!("Invalid profile name: '{}'.", name));
+        }
+        let dir = std::path::PathBuf::from(cli_args.require("dir")?);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e) )?;
+        config.profiles.insert(name.clone(), dir);
+        config.save().map_err(|e| format!("Failed to save config: {}", e) )?;
+        Ok(format!("Added profile '{}'.\n", name))
+      },
+      ["profile", "default"] => {
+        let name = cli_args.require("name")?.to_string();
+        config.profile_dir(&name)?;
+        config.default_profile = Some(name.clone());
+        config.save().map_err(|e| format!("Failed to save config: {}", e) )?;
+        Ok(format!("Default profile is now '{}'.\n", name))
+      },
+      _ => Err(format!("Unknown command: '{}'.\n\n{}", words.join(" "), CLI_USAGE)),
+    }
+  });
+  Some(result)
+}
+
+/// handles 'migrate', which has to run before the archive can be opened, returning None for any other command
+pub fn run_migrate_command(args: &[String], filepaths: &HashMap<String, String>) -> Option<Result<String, String>> {
+  match args.first() {
+    Some(word) if word == "migrate" => (),
+    _ => return None,
+  }
+  let result = CliArgs::parse(args).and_then(|cli_args| {
+    if cli_args.positional.len() > 1 {
+      return Err(format!("Unknown command: '{}'.\n\n{}", cli_args.positional.join(" "), CLI_USAGE));
+    }
+    let dry_run = cli_args.has_flag("dry-run");
+    let report = migrate_archive_file(&filepaths["archive_filepath"], dry_run)
+      .map_err(|e| format!("Failed to migrate {}: {}", filepaths["archive_filepath"], e) )?;
+    let mut output = report.to_display_string();
+    if report.is_needed() {
+      if dry_run {
+        output.push_str("Dry run: no files were changed.\n");
+      } else {
+        output.push_str(&format!(
+          "Archive upgraded. The original was saved as {}.schema-v{}.\n",
+          filepaths["archive_filepath"],
+          report.from_version,
+        ));
+      }
+    }
+    Ok(output)
+  });
+  Some(result)
+}
+
+/// lists backups of the archive, or with '--backup N', replaces the archive with one of them
+pub fn run_restore_command(args: &[String], filepaths: &HashMap<String, String>, keep: usize) -> Option<Result<String, String>> {
+  match args.first() {
+    Some(word) if word == "restore" => (),
+    _ => return None,
+  }
+  let archive_filepath = &filepaths["archive_filepath"];
+  let result = CliArgs::parse(args).and_then(|cli_args| {
+    if cli_args.positional.len() > 1 {
+      return Err(format!("Unknown command: '{}'.\n\n{}", cli_args.positional.join(" "), CLI_USAGE));
+    }
+    let backups = list_backups(archive_filepath).map_err(|e| format!("Failed to read backups: {}", e) )?;
+    if backups.is_empty() {
+      return Ok(format!("No backups of {} in {}.\n", archive_filepath, backup_dir(archive_filepath).display()));
+    }
+    let n = match cli_args.get("backup") {
+      Some(s) => match s.parse::<usize>() {
+        Ok(n) if n > 0 && n <= backups.len() => n,
+        _ => return Err(format!("Invalid backup number: {} (choose 1 to {}).", s, backups.len())),
+      },
+      None => {
+        let mut output = String::new();
+        for (i, b) in backups.iter().enumerate() {
+          output.push_str(&format!("{}\t{}\t{}\n", i + 1, b.fmt_created(), b.name()));
+        }
+        output.push_str("Run 'wrap_notes restore --backup N' to restore one.\n");
+        return Ok(output);
+      },
+    };
+    let backup = &backups[n - 1];
+    let saved = restore_backup(archive_filepath, backup, keep, chrono::Local::now().naive_local())
+      .map_err(|e| format!("Failed to restore {}: {}", backup.name(), e) )?;
+    let mut output = format!("Restored the archive from {} ({}).\n", backup.name(), backup.fmt_created());
+    if let Some(s) = saved {
+      output.push_str(&format!("The previous archive was saved as {}.\n", s.name()));
+    }
+    Ok(output)
+  });
+  Some(result)
+}
+
+fn check_user_id(a: &NoteArchive, id: Option<u32>) -> Result<(), String> {
+  match id {
+    Some(u_id) if !a.users.iter().any(|u| u.id == u_id ) => Err(format!("No user with ID {}.", u_id)),
+    _ => Ok(()),
+  }
+}
+
+fn check_client_id(a: &NoteArchive, id: Option<u32>) -> Result<(), String> {
+  match id {
+    Some(c_id) if a.get_client_by_id(c_id).is_none() => Err(format!("No client with ID {}.", c_id)),
+    _ => Ok(()),
+  }
+}
+
+fn cli_user_list(a: &NoteArchive) -> String {
+  let mut output = String::new();
+  for u in &a.users {
+    output.push_str(&format!("{}\t{}\t{}\n", u.id, u.role, u.full_name()));
+  }
+  output
+}
+
+fn cli_client_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.get_id("user")?;
+  check_user_id(a, user_id)?;
+  let active = match cli_args.get("status") {
+    None => None,
+    Some("active") => Some(true),
+    Some("inactive") => Some(false),
+    Some(other) => return Err(format!("Status must be active or inactive, not '{}'.", other)),
+  };
+  let mut output = String::new();
+  for c in &a.clients {
+    if let Some(u_id) = user_id {
+      let user = a.users.iter().find(|u| u.id == u_id ).unwrap();
+      if !user.foreign_keys["client_ids"].contains(&c.id) {
+        continue;
+      }
+    }
+    if active.is_some_and(|active| c.is_active() != active ) {
+      continue;
+    }
+    output.push_str(&format!("{}\t{}\t{}\t{}\n", c.id, c.full_name(), c.fmt_dob(), c.fmt_status()));
+  }
+  Ok(output)
+}
+
+/// saving would drop records that could not be read, so changes wait until they are repaired
+fn check_no_load_errors(a: &NoteArchive) -> Result<(), String> {
+  match a.load_errors.len() {
+    0 => Ok(()),
+    n => Err(format!("{} records could not be read. Run the program without arguments to repair them before making changes.", n)),
+  }
+}
+
+fn cli_client_add(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let first_name = cli_args.require("first")?.trim().to_string();
+  let last_name = cli_args.require("last")?.trim().to_string();
+  let dob = parse_cli_date(cli_args.require("dob")?)?;
+  let pronouns = cli_args.get_id("pronouns")?.unwrap_or(3);
+  if a.get_pronouns_by_id(pronouns).is_none() {
+    return Err(format!("No pronouns with ID {}.", pronouns));
+  }
+  let user_id = cli_args.get_id("user")?;
+  check_user_id(a, user_id)?;
+
+  let enrolled = cli_args.get_date("enrolled")?.unwrap_or_else(|| chrono::Local::now().naive_local().date() );
+  let client = match a.generate_unique_new_client(first_name, last_name, dob, pronouns, enrolled) {
+    Ok(c) => c,
+    Err(e) => {
+      let msg = e.iter().map(|(k, v)| format!("{} ({})", k, v) ).collect::<Vec<String>>().join(", ");
+      return Err(format!("Failed to create client: {}.", msg));
+    }
+  };
+  let id = client.id;
+  if let Some(u_id) = user_id {
+    let user = a.users.iter_mut().find(|u| u.id == u_id ).unwrap();
+    user.foreign_keys.get_mut("client_ids").unwrap().push(id);
+  }
+  a.save_client(client);
+  Ok(format!("{}\n", id))
+}
+
+fn cli_client_discharge(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let c_id = cli_args.require_id("client")?;
+  let date = cli_args.get_date("date")?.unwrap_or_else(|| chrono::Local::now().naive_local().date() );
+  let reason = DischargeReason::parse(cli_args.require("reason")?);
+  let c = a.clients.iter_mut().find(|c| c.id == c_id ).ok_or(format!("No client with ID {}.", c_id))?;
+  c.discharge(date, reason)?;
+  let output = format!("{}\t{}\n", c.id, c.fmt_status());
+  a.write_to_files();
+  Ok(output)
+}
+
+/// opens a new enrollment for a discharged client; earlier notes stay with the earlier episode
+fn cli_client_reactivate(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let c_id = cli_args.require_id("client")?;
+  let referred = cli_args.get_date("referred")?;
+  let enrolled = cli_args.get_date("enrolled")?.unwrap_or_else(|| chrono::Local::now().naive_local().date() );
+  let c = a.clients.iter_mut().find(|c| c.id == c_id ).ok_or(format!("No client with ID {}.", c_id))?;
+  c.reactivate(referred, enrolled)?;
+  let output = format!("{}\t{}\n", c.id, c.fmt_status());
+  a.write_to_files();
+  Ok(output)
+}
+
+fn cli_note_days<'a>(a: &'a NoteArchive, cli_args: &CliArgs) -> Result<Vec<&'a NoteDay>, String> {
+  let user_id = cli_args.get_id("user")?;
+  let client_id = cli_args.get_id("client")?;
+  check_user_id(a, user_id)?;
+  check_client_id(a, client_id)?;
+  let since = cli_args.get_date("since")?;
+  let until = cli_args.get_date("until")?;
+  let mut nds: Vec<&NoteDay> = a.note_days.iter()
+    .filter(|nd| user_id.is_none_or(|id| nd.foreign_key["user_id"] == id ) )
+    .filter(|nd| client_id.is_none_or(|id| nd.foreign_key["client_id"] == id ) )
+    .filter(|nd| since.is_none_or(|d| nd.date >= d ) )
+    .filter(|nd| until.is_none_or(|d| nd.date <= d ) )
+    .collect();
+  nds.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.id.cmp(&b.id) ) );
+  Ok(nds)
+}
+
+fn cli_note_day_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let mut output = String::new();
+  for nd in cli_note_days(a, cli_args)? {
+    let client_name = match a.get_client_by_id(nd.foreign_key["client_id"]) {
+      Some(c) => c.full_name(),
+      None => String::new(),
+    };
+    output.push_str(&format!(
+      "{}\t{}\t{}\t{}\t{}\n",
+      nd.id,
+      nd.fmt_date(),
+      nd.foreign_key["user_id"],
+      client_name,
+      nd.foreign_keys["note_ids"].len(),
+    ));
+  }
+  Ok(output)
+}
+
+fn cli_goal_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let client_id = cli_args.require_id("client")?;
+  check_client_id(a, Some(client_id))?;
+  let mut output = String::new();
+  for g in a.goals.iter().filter(|g| g.client_id == client_id ) {
+    let latest = match g.latest_progress() {
+      Some(p) => format!("{} ({})", p.rating, p.date.format("%Y-%m-%d")),
+      None => String::new(),
+    };
+    output.push_str(&format!("{}\t{}\t{}\t{}\n", g.id, g.status, g.goal, latest));
+  }
+  Ok(output)
+}
+
+fn cli_goal_mut<'a>(a: &'a mut NoteArchive, cli_args: &CliArgs) -> Result<&'a mut Goal, String> {
+  let goal_id = cli_args.require_id("goal")?;
+  a.get_goal_by_id_mut(goal_id).ok_or(format!("No goal with ID {}.", goal_id))
+}
+
+fn cli_goal_objective(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let description = cli_args.require("text")?.trim().to_string();
+  let baseline = cli_args.get("baseline").map(|b| b.trim().to_string() );
+  let target_date = cli_args.get_date("target")?;
+  let g = cli_goal_mut(a, cli_args)?;
+  let objective = Objective::new(description, baseline, target_date);
+  let output = format!("Goal {}: added objective '{}'\n", g.id, objective);
+  g.objectives.push(objective);
+  a.write_to_files();
+  Ok(output)
+}
+
+/// records a rating, linked to the client's note day on that date if there is one
+fn cli_goal_progress(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let rating = ProgressRating::parse(cli_args.require("rating")?)?;
+  let date = cli_args.get_date("date")?.unwrap_or_else(|| chrono::Local::now().naive_local().date() );
+  let comment = cli_args.get("comment").unwrap_or("").trim().to_string();
+  let client_id = cli_goal_mut(a, cli_args)?.client_id;
+  let note_day_id = a.note_days.iter()
+    .find(|nd| nd.date == date && nd.foreign_key["client_id"] == client_id )
+    .map(|nd| nd.id );
+  let g = cli_goal_mut(a, cli_args)?;
+  g.add_progress(ProgressEntry::new(date, rating, comment, note_day_id));
+  let output = format!("Goal {}: {}\n", g.id, g.render(GoalRendering::LatestProgress));
+  a.write_to_files();
+  Ok(output)
+}
+
+fn cli_goal_status(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let status = GoalStatus::parse(cli_args.require("status")?)?;
+  let g = cli_goal_mut(a, cli_args)?;
+  g.status = status;
+  let output = format!("Goal {} is {}.\n", g.id, status);
+  a.write_to_files();
+  Ok(output)
+}
+
+fn cli_goal_report(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let client_id = cli_args.require_id("client")?;
+  check_client_id(a, Some(client_id))?;
+  let report = GoalProgressReport::new(a, client_id, cli_args.get_date("since")?, cli_args.get_date("until")?);
+  match cli_args.get("csv") {
+    Some(fp) => {
+      write_file_atomically(fp, report.to_csv().as_bytes()).map_err(|e| format!("Failed to write {}: {}", fp, e) )?;
+      Ok(format!("{}\n", fp))
+    },
+    None => Ok(report.to_display_string()),
+  }
+}
+
+fn cli_note_day_add(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  let client_id = cli_args.require_id("client")?;
+  check_user_id(a, Some(user_id))?;
+  check_client_id(a, Some(client_id))?;
+  let dates = cli_args.get_all("date");
+  if dates.is_empty() {
+    return Err(String::from("Missing required option '--date'."));
+  }
+  let mut parsed_dates: Vec<NaiveDate> = vec![];
+  for d in dates {
+    parsed_dates.push(parse_cli_date(d)?);
+  }
+  let mut output = String::new();
+  for date in parsed_dates {
+    let nd = a.generate_unique_new_note_day(date, user_id, client_id)?;
+    output.push_str(&format!("{}\t{}\n", nd.id, nd.fmt_date()));
+    a.save_note_day(nd);
+  }
+  Ok(output)
+}
+
+fn cli_note_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let notes: Vec<&Note> = match cli_args.get_id("note-day")? {
+    Some(nd_id) => match a.get_note_day_by_id(nd_id) {
+      Some(nd) => a.note_day_notes(nd.clone()),
+      None => return Err(format!("No note day with ID {}.", nd_id)),
+    },
+    None => a.filter_notes(&cli_note_filter(a, cli_args)?),
+  };
+  let mut output = String::new();
+  for n in notes {
+    let (content, _) = n.generate_display_content_string_with_blanks(None, None, None, None, None);
+    output.push_str(&format!(
+      "{}\t{}\t{}\t{}\t{}\t{}\n",
+      n.id,
+      a.get_note_day_by_note_id(n.id).map(|nd| nd.id.to_string() ).unwrap_or_default(),
+      n.fmt_date(),
+      n.category,
+      n.structure,
+      content.replace('\n', " "),
+    ));
+  }
+  Ok(output)
+}
+
+/// the note filter options of 'note list', written as the conditions NoteFilter::parse reads
+fn cli_note_filter(a: &NoteArchive, cli_args: &CliArgs) -> Result<NoteFilter, String> {
+  let mut conditions: Vec<String> = vec![];
+  for name in ["user", "client", "collateral", "structure", "category", "since", "until", "unfilled"] {
+    if let Some(value) = cli_args.get(name) {
+      conditions.push(format!("{}:{}", name, value));
+    }
+  }
+  let filter = NoteFilter::parse(&conditions.join(" "), chrono::Local::now().naive_local().date())?;
+  check_user_id(a, filter.user_id)?;
+  check_client_id(a, filter.client_id)?;
+  if let Some(co_id) = filter.collateral_id {
+    if !a.collaterals.iter().any(|co| co.id == co_id ) {
+      return Err(format!("No collateral with ID {}.", co_id));
+    }
+  }
+  Ok(filter)
+}
+
+/// plain text of a note day with category headings, matching the interactive printout
+pub fn note_day_plain_text(a: &NoteArchive, nd: &NoteDay) -> String {
+  let name = match a.get_client_by_id(nd.foreign_key["client_id"]) {
+    Some(c) => c.full_name(),
+    None => String::new(),
+  };
+  let mut output = format!("{} notes for {}\n\n", nd.heading_date(), name);
+  for (k, v) in &a.get_note_day_notes_by_category(nd.clone()) {
+    if !v.is_empty() {
+      output.push_str(&format!("{}\n", k));
+      for n in v {
+        let (content, _) = n.generate_display_content_string_with_blanks(None, None, None, None, None);
+        output.push_str(&format!("{}\n\n", content));
+      }
+    }
+  }
+  output
+}
+
+fn cli_note_print(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  match (cli_args.get_id("note-day")?, cli_args.get_id("note")?) {
+    (Some(nd_id), None) => match a.get_note_day_by_id(nd_id) {
+      Some(nd) => Ok(note_day_plain_text(a, nd)),
+      None => Err(format!("No note day with ID {}.", nd_id)),
+    },
+    (None, Some(n_id)) => match a.get_note_option_by_id(n_id) {
+      Some(n) => {
+        let (content, _) = n.generate_display_content_string_with_blanks(None, None, None, None, None);
+        Ok(format!("{}\n", content))
+      },
+      None => Err(format!("No note with ID {}.", n_id)),
+    },
+    _ => Err(String::from("Specify exactly one of '--note-day' or '--note'.")),
+  }
+}
+
+/// the user's clients, most urgent first
+fn cli_dashboard(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  let u = a.users.iter().find(|u| u.id == user_id ).ok_or(format!("No user with ID {}.", user_id))?;
+  let today = chrono::Local::now().naive_local().date();
+  let mut output = String::new();
+  for row in caseload_rows(a, u, today, Config::load()?.contact_due_days()) {
+    output.push_str(&format!(
+      "{}\t{}\t{}\t{}\t{}\t{}\n",
+      row.client_id,
+      row.client_name,
+      row.last_note_day.map(|d| d.format("%Y-%m-%d").to_string() ).unwrap_or_default(),
+      row.last_contact.clone().unwrap_or_default(),
+      row.notes_with_unfilled_blanks,
+      row.fmt_due(today),
+    ));
+  }
+  Ok(output)
+}
+
+/// overdue and upcoming deadlines for the user's clients, or for one of them
+fn cli_deadlines(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  let u = a.users.iter().find(|u| u.id == user_id ).ok_or(format!("No user with ID {}.", user_id))?;
+  let client_ids = match cli_args.get_id("client")? {
+    Some(c_id) if u.foreign_keys["client_ids"].contains(&c_id) => vec![c_id],
+    Some(c_id) => return Err(format!("Client {} is not on the caseload of user {}.", c_id, user_id)),
+    None => u.foreign_keys["client_ids"].clone(),
+  };
+  let today = chrono::Local::now().naive_local().date();
+  let alerts = compliance_alerts(a, &client_ids, &Config::load()?.compliance_rules(), today);
+  match cli_args.get("csv") {
+    Some(fp) => {
+      write_file_atomically(fp, compliance_alerts_csv(&alerts).as_bytes()).map_err(|e| format!("Failed to write {}: {}", fp, e) )?;
+      Ok(format!("{}\n", fp))
+    },
+    None => Ok(alerts.iter().map(|alert| format!(
+      "{}\t{}\t{}\t{}\t{}\n",
+      alert.client_id,
+      alert.client_name,
+      alert.rule,
+      alert.due.format("%Y-%m-%d"),
+      alert.status,
+    )).collect()),
+  }
+}
+
+/// matching notes, templates and collaterals with the matches in square brackets;
+/// options are combined with any 'client:' style filters in the query
+fn cli_search(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let mut query = SearchQuery::parse(cli_args.require("query")?)?;
+  let user_id = cli_args.get_id("user")?;
+  check_user_id(a, user_id)?;
+  if let Some(c_id) = cli_args.get_id("client")? {
+    query.filter.client_id = Some(c_id);
+  }
+  if let Some(since) = cli_args.get_date("since")? {
+    query.filter.since = Some(since);
+  }
+  if let Some(until) = cli_args.get_date("until")? {
+    query.filter.until = Some(until);
+  }
+  if let Some(name) = cli_args.get("category") {
+    query.filter.category = Some(NoteMatch::parse(name)?);
+  }
+  let index = SearchIndex::new(a, user_id);
+  Ok(index.search(&query).iter().map(|r| format!(
+    "{}\t{}\t{}\n",
+    r.document.record,
+    r.document.title,
+    r.snippet.to_marked_string().replace(['\n', '\t'], " "),
+  )).collect())
+}
+
+fn cli_note_time(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let n_id = cli_args.require_id("note")?;
+  let (start_time, minutes) = parse_note_time(cli_args.require("time")?)?;
+  let n = a.get_note_option_by_id(n_id).ok_or(format!("No note with ID {}.", n_id))?;
+  if let Some(start) = start_time {
+    if let Some(other) = find_overlapping_note(a, n, start, minutes) {
+      return Err(format!("That time overlaps note {} ({}) for another client.", other.id, other.fmt_time()));
+    }
+  }
+  let n = a.notes.iter_mut().find(|n| n.id == n_id ).unwrap();
+  n.set_time(start_time, Some(minutes));
+  let output = format!("{}\t{}\n", n.id, n.fmt_time());
+  a.write_to_files();
+  Ok(output)
+}
+
+fn cli_note_travel(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let n_id = cli_args.require_id("note")?;
+  let travel = Travel {
+    start_location: cli_args.get("from").map(|s| s.to_string() ),
+    end_location: cli_args.get("to").map(|s| s.to_string() ),
+    miles: parse_miles(cli_args.require("miles")?)?,
+    minutes: match cli_args.get("minutes") {
+      Some(m) => m.parse().map_err(|_| format!("Invalid number of minutes: {}", m) )?,
+      None => 0,
+    },
+  };
+  let n = a.notes.iter_mut().find(|n| n.id == n_id ).ok_or(format!("No note with ID {}.", n_id))?;
+  let output = format!("{}\t{}\n", n.id, travel.fmt_travel());
+  n.travel = Some(travel);
+  a.write_to_files();
+  Ok(output)
+}
+
+/// a user's travel for the pay period that includes '--date' (by default today)
+fn cli_mileage(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  check_user_id(a, Some(user_id))?;
+  let date = cli_args.get_date("date")?.unwrap_or_else(|| chrono::Local::now().naive_local().date() );
+  let (first, last) = Config::load()?.pay_period().containing(date);
+  let log = MileageLog::new(a, user_id, first, last);
+  match cli_args.get("csv") {
+    Some(fp) => {
+      write_file_atomically(fp, log.to_csv().as_bytes()).map_err(|e| format!("Failed to write {}: {}", fp, e) )?;
+      for overlap in &log.overlaps {
+        eprintln!("Warning: overlapping times: {}", overlap.description());
+      }
+      Ok(format!("{}\n", fp))
+    },
+    None => Ok(log.to_display_string()),
+  }
+}
+
+/// units per client for a month, printed as a table or saved as CSV
+fn cli_billing(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let (first, last) = parse_month(cli_args.require("month")?)?;
+  let user_id = cli_args.get_id("user")?;
+  let client_id = cli_args.get_id("client")?;
+  check_user_id(a, user_id)?;
+  check_client_id(a, client_id)?;
+  let settings = Config::load()?.billing_settings();
+  let report = BillingReport::new(a, first, last, user_id, client_id, &settings);
+  match cli_args.get("csv") {
+    Some(fp) => {
+      write_file_atomically(fp, report.to_csv().as_bytes()).map_err(|e| format!("Failed to write {}: {}", fp, e) )?;
+      for over in &report.over_caps {
+        eprintln!("Warning: {}", over.description());
+      }
+      Ok(format!("{}\n", fp))
+    },
+    None => Ok(report.to_display_string()),
+  }
+}
+
+fn cli_export(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let note_days: Vec<&NoteDay> = match (cli_args.get_id("note-day")?, cli_args.get_id("client")?) {
+    (Some(nd_id), None) => match a.get_note_day_by_id(nd_id) {
+      Some(nd) => vec![nd],
+      None => return Err(format!("No note day with ID {}.", nd_id)),
+    },
+    (None, Some(_)) => cli_note_days(a, cli_args)?,
+    _ => return Err(String::from("Specify exactly one of '--note-day' or '--client'.")),
+  };
+  if note_days.is_empty() {
+    return Err(String::from("No note days in that range."));
+  }
+  let formats = ExportFormat::parse_list(cli_args.get("format").unwrap_or("all"))?;
+  let dir = match cli_args.get("dir") {
+    Some(d) => std::path::PathBuf::from(d),
+    None => export_dir(&a.filepaths["archive_filepath"]),
+  };
+  let written = ExportDocument::new(a, &note_days).write_files(&dir, &formats)
+    .map_err(|e| format!("Failed to write export to {}: {}", dir.display(), e) )?;
+  Ok(written.iter().map(|fp| format!("{}\n", fp.display()) ).collect())
+}
+
+/// saves a PDF of one note day or of a client's month, by default into the exports folder
+fn cli_pdf(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let (file_name, bytes) = match (cli_args.get_id("note-day")?, cli_args.get_id("client")?) {
+    (Some(nd_id), None) => match a.get_note_day_by_id(nd_id) {
+      Some(nd) => note_day_pdf(a, nd),
+      None => return Err(format!("No note day with ID {}.", nd_id)),
+    },
+    (None, Some(c_id)) => {
+      let c = a.get_client_by_id(c_id).ok_or(format!("No client with ID {}.", c_id))?;
+      let (first, last) = parse_month(cli_args.require("month")?)?;
+      client_month_pdf(a, c, first, last)
+        .ok_or(format!("No note days for {} in {}.", c.full_name(), first.format("%B %Y")))?
+    },
+    _ => return Err(String::from("Specify exactly one of '--note-day' or '--client'.")),
+  };
+  let fp = match cli_args.get("output") {
+    Some(o) => std::path::PathBuf::from(o),
+    None => export_dir(&a.filepaths["archive_filepath"]).join(file_name),
+  };
+  write_pdf(&fp, &bytes).map_err(|e| format!("Failed to write {}: {}", fp.display(), e) )?;
+  Ok(format!("{}\n", fp.display()))
+}
+
+/// prints one paragraph per EHR text box, or saves each to a file, or copies them one at a time
+fn cli_ehr(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let nd_id = cli_args.require_id("note-day")?;
+  let nd = a.get_note_day_by_id(nd_id).ok_or(format!("No note day with ID {}.", nd_id))?;
+  let config = Config::load()?;
+  let paragraphs = ehr_paragraphs(a, nd, &config.char_limits);
+  if paragraphs.is_empty() {
+    return Err(format!("Note day {} has no notes.", nd_id));
+  }
+  for warning in paragraphs.iter().filter_map(|p| p.limit_warning() ) {
+    eprintln!("Warning: {}", warning);
+  }
+  if let Some(dir) = cli_args.get("dir") {
+    let written = write_ehr_files(&paragraphs, std::path::Path::new(dir), &ehr_file_stem(a, nd))
+      .map_err(|e| format!("Failed to write files to {}: {}", dir, e) )?;
+    return Ok(written.iter().map(|fp| format!("{}\n", fp.display()) ).collect());
+  }
+  if cli_args.has_flag("copy") {
+    let command = config.clipboard_command
+      .ok_or("No clipboard command is set. Add 'clipboard_command = COMMAND' to the config file.")?;
+    for (i, p) in paragraphs.iter().enumerate() {
+      pipe_to_command(&command, &p.text).map_err(|e| format!("Failed to copy {}: {}", p.heading(), e) )?;
+      eprint!("Copied {} ({} characters).", p.heading(), p.char_count());
+      if i + 1 < paragraphs.len() {
+        eprintln!(" Press Enter to copy {}.", paragraphs[i + 1].heading());
+        let mut s = String::new();
+        std::io::stdin().read_line(&mut s).map_err(|e| e.to_string() )?;
+      } else {
+        eprintln!();
+      }
+    }
+    return Ok(String::new());
+  }
+  let mut output = String::new();
+  for p in &paragraphs {
+    let count = match p.char_limit {
+      Some(limit) => format!("{}/{} characters", p.char_count(), limit),
+      None => format!("{} characters", p.char_count()),
+    };
+    output.push_str(&format!("== {} ({}) ==\n{}\n\n", p.heading(), count, p.text));
+  }
+  Ok(output)
+}
+
+fn cli_note_templates<'a>(a: &'a NoteArchive, cli_args: &CliArgs) -> Result<Vec<&'a NoteTemplate>, String> {
+  let user_id = cli_args.get_id("user")?;
+  check_user_id(a, user_id)?;
+  Ok(a.note_templates.iter().filter(|nt| match user_id {
+    Some(u_id) => nt.foreign_keys.get("user_ids").is_some_and(|ids| ids.contains(&u_id) ),
+    None => true,
+  }).collect())
+}
+
+fn cli_template_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let mut output = String::new();
+  for nt in cli_note_templates(a, cli_args)? {
+    output.push_str(&format!("{}\t{}\t{}\n", nt.id, nt.structure, nt.preview()));
+  }
+  Ok(output)
+}
+
+/// one line per problem found by linting each template
+fn cli_template_check(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let mut output = String::new();
+  for nt in cli_note_templates(a, cli_args)? {
+    for issue in nt.lint() {
+      output.push_str(&format!("{}\t{}\n", nt.id, issue));
+    }
+  }
+  Ok(output)
+}
+
+/// writes a user's templates, or the chosen ones, to a library file for importing elsewhere
+fn cli_template_export(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  check_user_id(a, Some(user_id))?;
+  let mut templates = a.user_note_templates(user_id);
+  let chosen_ids = cli_args.get_all("template");
+  if !chosen_ids.is_empty() {
+    let mut chosen: Vec<&NoteTemplate> = vec![];
+    for s in chosen_ids {
+      let nt_id: u32 = s.parse().map_err(|_| format!("Invalid ID for '--template': {}", s) )?;
+      match templates.iter().find(|nt| nt.id == nt_id ) {
+        Some(nt) => chosen.push(nt),
+        None => return Err(format!("User {} has no template with ID {}.", user_id, nt_id)),
+      }
+    }
+    templates = chosen;
+  }
+  if templates.is_empty() {
+    return Err(format!("User {} has no templates.", user_id));
+  }
+  let fp = match cli_args.get("output") {
+    Some(o) => std::path::PathBuf::from(o),
+    None => export_dir(&a.filepaths["archive_filepath"]).join(format!("note_templates_user_{}.json", user_id)),
+  };
+  TemplateLibrary::new(&templates).save(&fp)?;
+  Ok(format!("{} templates written to {}\n", templates.len(), fp.display()))
+}
+
+/// previews what a library file would add for a user, then saves the new templates unless it is a dry run
+fn cli_template_import(a: &mut NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let user_id = cli_args.require_id("user")?;
+  check_user_id(a, Some(user_id))?;
+  let library = TemplateLibrary::load(cli_args.require("file")?)?;
+  let plan = a.plan_template_import(&library, user_id);
+  let mut output: String = plan.iter().map(|entry| format!("{}\n", entry.preview()) ).collect();
+  let to_import = plan.iter().filter(|entry| entry.will_import() ).count();
+  if cli_args.has_flag("dry-run") {
+    output.push_str(&format!("Dry run: {} of {} templates would be imported.\n", to_import, plan.len()));
+    return Ok(output);
+  }
+  check_no_load_errors(a)?;
+  let ids = a.import_templates(&plan, user_id);
+  let id_list: Vec<String> = ids.iter().map(|id| id.to_string() ).collect();
+  output.push_str(&format!("Imported {} of {} templates: {}\n", ids.len(), plan.len(), id_list.join(", ")));
+  Ok(output)
+}
+
+fn cli_vocabulary_list(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let vocabularies = a.vocabularies()?;
+  let blanks = match cli_args.get("blank") {
+    Some(name) => match fill_in_blanks().into_iter().find(|b| fill_in_blank_key(b).eq_ignore_ascii_case(name) ) {
+      Some(b) => vec![b],
+      None => return Err(format!("'{}' is not a fill-in blank.", name)),
+    },
+    None => fill_in_blanks(),
+  };
+  let mut output = String::new();
+  for b in blanks {
+    for (i, option) in vocabularies.options(&b).iter().enumerate() {
+      let custom = if option.custom { "\tcustom" } else { "" };
+      output.push_str(&format!("{}\t{}\t{}{}\n", fill_in_blank_key(&b), i, option.text, custom));
+    }
+  }
+  Ok(output)
+}
+
+/// writes the lists in use to a file admins can edit; by default the data directory's vocabulary file, if there isn't one yet
+fn cli_vocabulary_export(a: &NoteArchive, cli_args: &CliArgs) -> Result<String, String> {
+  let vocabularies = a.vocabularies()?;
+  let filepath = match (cli_args.get("output"), a.filepaths.get("vocabulary_filepath")) {
+    (Some(o), _) => o.to_string(),
+    (None, Some(fp)) if std::path::Path::new(fp).exists() => return Err(format!("{} already exists; use '--output' to write a copy elsewhere.", fp)),
+    (None, Some(fp)) => fp.clone(),
+    (None, None) => return Err(String::from("No vocabulary file path is set; use '--output'.")),
+  };
+  write_file_atomically(&filepath, vocabularies.to_file_string().as_bytes()).map_err(|e| format!("Failed to write {}: {}", filepath, e) )?;
+  Ok(format!("{}\n", filepath))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::note::*;
+  use std::fs;
+
+  fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(|w| w.to_string() ).collect()
+  }
+
+  /// a test archive saved to its own file, with client 3 (Ann Lee) added for user 2
+  fn test_archive(name: &str) -> (NoteArchive, HashMap<String, String>) {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), format!("test_cli_{}_archive.json", name),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    let added = run_command(&mut a, &args("client add --first Ann --last Lee --dob 2010-05-06 --user 2")).unwrap();
+    assert_eq!(added, "3\n");
+    (a, filepaths)
+  }
+
+  fn remove_test_archive(filepaths: HashMap<String, String>) {
+    for (_, v) in filepaths {
+      fs::remove_file(v).unwrap();
+    }
+  }
+
+  /// a note for client 3 on 9/1/2026
+  fn push_test_note(a: &mut NoteArchive) {
+    let category = NoteCategory::ICCNote(ICCNoteCategory::CareCoordination);
+    a.notes.push(Note::new(1, NaiveDate::from_ymd(2026, 9, 1), category, StructureType::Referral, String::new(), 2, 3, vec![]));
+  }
+
+  #[test]
+  fn runs_client_commands() {
+    let (mut a, filepaths) = test_archive("client");
+    assert!(run_command(&mut a, &args("client list --user 2")).unwrap().contains("Ann Lee"));
+    assert_eq!(NoteArchive::open(filepaths.clone()).unwrap().clients.len(), 3);
+    let discharged = run_command(&mut a, &args("client discharge --client 3 --date 2026-12-01 --reason 1")).unwrap();
+    assert_eq!(discharged, "3\tDischarged 2026-12-01 (goals met)\n");
+    let reloaded = NoteArchive::open(filepaths.clone()).unwrap();
+    assert_eq!(reloaded.get_client_by_id(3).unwrap().current_episode().unwrap().discharge_date, Some(NaiveDate::from_ymd(2026, 12, 1)));
+    assert!(run_command(&mut a, &args("client list --user 2 --status inactive")).unwrap().contains("Ann Lee"));
+    assert!(run_command(&mut a, &args("client reactivate --client 3 --enrolled 2026-11-01")).is_err());
+    run_command(&mut a, &args("client reactivate --client 3 --referred 2026-12-10 --enrolled 2026-12-15")).unwrap();
+    assert!(run_command(&mut a, &args("client list --status inactive")).unwrap().is_empty());
+    assert_eq!(a.get_client_by_id(3).unwrap().episodes.len(), 2);
+    assert!(NoteArchive::open(filepaths.clone()).unwrap().get_client_by_id(3).unwrap().is_active());
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_note_day_commands() {
+    let (mut a, filepaths) = test_archive("note_day");
+    let note_days = a.note_days.len();
+    let days = run_command(&mut a, &args("note-day add --user 2 --client 3 --date 2026-09-01 --date 2026-09-02")).unwrap();
+    assert_eq!(days.lines().count(), 2);
+    assert!(run_command(&mut a, &args("note-day add --user 2 --client 3 --date 2026-09-01")).is_err());
+    let listed = run_command(&mut a, &args("note-day list --client 3 --since 2026-09-02")).unwrap();
+    assert_eq!(listed.lines().count(), 1);
+    assert!(listed.contains("2026-09-02"));
+    assert_eq!(NoteArchive::open(filepaths.clone()).unwrap().note_days.len(), note_days + 2);
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_export_and_pdf_commands() {
+    let (mut a, filepaths) = test_archive("export");
+    run_command(&mut a, &args("note-day add --user 2 --client 3 --date 2026-09-01 --date 2026-09-02")).unwrap();
+    let export_dir = std::env::temp_dir().join("wrap_notes_test_cli_export");
+    let _ = fs::remove_dir_all(&export_dir);
+    let exported = run_command(&mut a, &args(&format!("export --client 3 --format all --dir {}", export_dir.display()))).unwrap();
+    assert_eq!(exported.lines().count(), 3);
+    assert!(exported.contains("lee_ann_2026-09-01_to_2026-09-02.html"));
+    assert!(fs::read_to_string(export_dir.join("lee_ann_2026-09-01_to_2026-09-02.md")).unwrap().contains("# Tuesday 9/1 notes for Ann Lee"));
+    assert!(run_command(&mut a, &args("export --client 3 --since 2027-01-01")).is_err());
+    assert!(run_command(&mut a, &args("export --note-day 1 --format pdf")).is_err());
+    let pdf_fp = export_dir.join("september.pdf");
+    let saved = run_command(&mut a, &args(&format!("pdf --client 3 --month 2026-09 --output {}", pdf_fp.display()))).unwrap();
+    assert!(saved.contains("september.pdf"));
+    assert!(fs::read(&pdf_fp).unwrap().starts_with(b"%PDF-"));
+    assert!(run_command(&mut a, &args("pdf --client 3 --month 2026-10")).is_err());
+    assert!(run_command(&mut a, &args("pdf --client 3")).is_err());
+    fs::remove_dir_all(&export_dir).unwrap();
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_note_time_and_billing_commands() {
+    let (mut a, filepaths) = test_archive("note_time");
+    push_test_note(&mut a);
+    assert!(run_command(&mut a, &args("note time --note 1 --time 9:00-9:45")).unwrap().contains("(45 minutes)"));
+    assert_eq!(NoteArchive::open(filepaths.clone()).unwrap().get_note_option_by_id(1).unwrap().duration_minutes, Some(45));
+    let billing = run_command(&mut a, &args("billing --month 2026-09 --client 3")).unwrap();
+    assert!(billing.contains("Ann Lee (3 units)"));
+    assert!(run_command(&mut a, &args("billing --month 2026-09 --client 99")).is_err());
+    assert!(run_command(&mut a, &args("note time --note 1 --time 9:45-9:45")).is_err());
+    assert!(run_command(&mut a, &args("note time --note 1 --time 23:30-0:30")).unwrap().contains("(60 minutes)"));
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_travel_commands() {
+    let (mut a, filepaths) = test_archive("travel");
+    push_test_note(&mut a);
+    let travel = run_command(&mut a, &args("note travel --note 1 --miles 12.5 --minutes 25 --from Office")).unwrap();
+    assert!(travel.contains("from Office, 12.5 miles, 25 minutes"));
+    let reloaded = NoteArchive::open(filepaths.clone()).unwrap();
+    assert_eq!(reloaded.get_note_option_by_id(1).unwrap().travel.as_ref().unwrap().start_location, Some(String::from("Office")));
+    let mileage = run_command(&mut a, &args("mileage --user 2 --date 2026-09-01")).unwrap();
+    assert!(mileage.contains("Total: 12.5 miles, 25 minutes of travel"));
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_search_and_note_list_commands() {
+    let (mut a, filepaths) = test_archive("search");
+    push_test_note(&mut a);
+    a.notes[0].content = String::from("Called the school counselor about enrollment.");
+    let found = run_command(&mut a, &args("search --query counselor --client 3 --category CareCoordination")).unwrap();
+    assert!(found.starts_with("note 1\t"));
+    assert!(found.contains("[counselor]"));
+    assert!(run_command(&mut a, &args("search --query counselor --since 2026-10-01")).unwrap().is_empty());
+    let filtered = run_command(&mut a, &args("note list --client 3 --structure Referral --category CareCoordination")).unwrap();
+    assert!(filtered.starts_with("1\t"));
+    assert!(run_command(&mut a, &args("note list --unfilled yes")).unwrap().is_empty());
+    assert!(run_command(&mut a, &args("note list --structure CareCoordination")).is_err());
+    assert!(run_command(&mut a, &args("note list --client 99")).is_err());
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_template_commands() {
+    let (mut a, filepaths) = test_archive("template");
+    a.note_templates.push(NoteTemplate::new(90, StructureType::Intake, true, String::from("Met  with (---gg---)."), vec![2]));
+    let checked = run_command(&mut a, &args("template check --user 2")).unwrap();
+    assert!(checked.contains("90\tError at sentence 1, column 11: Unknown blank '(---gg---)'."));
+    assert!(checked.contains("90\tWarning at sentence 1, column 4: Double space."));
+    let library_fp = std::env::temp_dir().join("wrap_notes_test_cli_templates.json");
+    a.note_templates.push(NoteTemplate::new(91, StructureType::Intake, true, String::from("Completed intake with (---c---)."), vec![2]));
+    let exported = run_command(&mut a, &args(&format!("template export --user 2 --template 90 --template 91 --output {}", library_fp.display()))).unwrap();
+    assert!(exported.starts_with("2 templates written"));
+    assert!(run_command(&mut a, &args("template export --user 2 --template 999")).is_err());
+    let preview = run_command(&mut a, &args(&format!("template import --user 1 --file {} --dry-run", library_fp.display()))).unwrap();
+    assert!(preview.contains("Skipped: Intake template has 1 error\n"));
+    assert!(preview.contains("Dry run: 1 of 2 templates would be imported."));
+    let imported = run_command(&mut a, &args(&format!("template import --user 1 --file {}", library_fp.display()))).unwrap();
+    assert!(imported.contains("Imported 1 of 2 templates"));
+    let reimported = run_command(&mut a, &args(&format!("template import --user 1 --file {}", library_fp.display()))).unwrap();
+    assert!(reimported.contains("Imported 0 of 2 templates"));
+    fs::remove_file(&library_fp).unwrap();
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_goal_commands() {
+    let (mut a, filepaths) = test_archive("goal");
+    a.goals.push(Goal::new(50, 3, String::from("attend school regularly")));
+    assert!(run_command(&mut a, &args("goal objective --goal 50 --text attend_4_days --target 2026-12-01")).unwrap().contains("attend_4_days by 12/1/2026"));
+    let rated = run_command(&mut a, &args("goal progress --goal 50 --rating 3 --date 2026-09-01 --comment 3_days")).unwrap();
+    assert_eq!(rated, "Goal 50: attend school regularly: some progress as of 9/1/2026 (3_days)\n");
+    assert!(run_command(&mut a, &args("goal progress --goal 50 --rating great")).is_err());
+    assert!(run_command(&mut a, &args("goal status --goal 50 --status met")).unwrap().contains("is met"));
+    assert_eq!(run_command(&mut a, &args("goal list --client 3")).unwrap(), "50\tmet\tattend school regularly\tsome progress (2026-09-01)\n");
+    let goal_report = run_command(&mut a, &args("goal report --client 3 --since 2026-09-01")).unwrap();
+    assert!(goal_report.contains("  2026-09-01  some progress; 3_days\n"));
+    assert!(run_command(&mut a, &args("goal status --goal 999 --status met")).is_err());
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn runs_vocabulary_commands() {
+    let (mut a, filepaths) = test_archive("vocabulary");
+    let services = run_command(&mut a, &args("vocabulary list --blank service")).unwrap();
+    assert!(services.starts_with("Service\t0\t"));
+    assert!(run_command(&mut a, &args("vocabulary list --blank Lunch")).is_err());
+    assert!(run_command(&mut a, &args("vocabulary export")).is_err());
+    remove_test_archive(filepaths);
+  }
+
+  #[test]
+  fn rejects_unknown_commands() {
+    let (mut a, filepaths) = test_archive("unknown");
+    assert!(run_command(&mut a, &args("note print")).is_err());
+    assert!(run_command(&mut a, &args("bogus")).is_err());
+    remove_test_archive(filepaths);
+  }
+}
@@ -1,9 +1,10 @@
 use std::fmt;
 use chrono::{NaiveDate, Datelike};
 use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Client {
   pub id: u32,
   pub first_name: String,
@@ -11,6 +12,8 @@ pub struct Client {
   pub dob: NaiveDate,
   pub pronouns: u32,
   pub foreign_keys: HashMap<String, Vec<u32>>,
+  /// each period of services, oldest first; a client with none predates enrollment tracking and counts as active
+  pub episodes: Vec<Enrollment>,
 }
 
 impl PartialEq for Client {
@@ -41,6 +44,7 @@ impl Client {
       dob,
       pronouns,
       foreign_keys,
+      episodes: vec![],
     }
   }
   pub fn full_name(&self) -> String {
@@ -53,6 +57,47 @@ impl Client {
   pub fn full_name_with_label(&self) -> String {
     format!("{} (youth)", self.full_name())
   }
+  pub fn current_episode(&self) -> Option<&Enrollment> {
+    self.episodes.last()
+  }
+  pub fn is_active(&self) -> bool {
+    self.current_episode().is_none_or(|e| e.discharge_date.is_none() )
+  }
+  /// such as 'Enrolled 2021-03-01' or 'Discharged 2021-06-30 (goals met)'
+  pub fn fmt_status(&self) -> String {
+    match self.current_episode() {
+      Some(e) => e.fmt_status(),
+      None => String::from("Active"),
+    }
+  }
+  pub fn discharge(&mut self, date: NaiveDate, reason: DischargeReason) -> Result<(), String> {
+    let episode = match self.episodes.last_mut() {
+      Some(e) if e.discharge_date.is_none() => e,
+      Some(_) => return Err(format!("{} is already discharged.", self.full_name())),
+      None => return Err(format!("{} has no enrollment to discharge from.", self.full_name())),
+    };
+    if date < episode.enrollment_date {
+      return Err(format!("Discharge date is before the enrollment date ({}).", episode.enrollment_date.format("%Y-%m-%d")));
+    }
+    episode.discharge_date = Some(date);
+    episode.discharge_reason = Some(reason);
+    Ok(())
+  }
+  /// opens a new episode; notes from earlier episodes are left as they are
+  pub fn reactivate(&mut self, referral_date: Option<NaiveDate>, enrollment_date: NaiveDate) -> Result<(), String> {
+    if self.is_active() {
+      return Err(format!("{} is already active.", self.full_name()));
+    }
+    let last_discharge = self.current_episode().and_then(|e| e.discharge_date ).unwrap();
+    if enrollment_date < last_discharge {
+      return Err(format!("Enrollment date is before the last discharge ({}).", last_discharge.format("%Y-%m-%d")));
+    }
+    if referral_date.is_some_and(|r| r > enrollment_date ) {
+      return Err(String::from("Referral date is after the enrollment date."));
+    }
+    self.episodes.push(Enrollment::new(referral_date, enrollment_date));
+    Ok(())
+  }
   pub fn fmt_dob(&self) -> String {
     self.dob.format("%Y-%m-%d").to_string()
   }
@@ -83,6 +128,78 @@ impl Client {
   }
 }
 
+/// one period of services, from referral and enrollment to discharge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Enrollment {
+  pub referral_date: Option<NaiveDate>,
+  pub enrollment_date: NaiveDate,
+  pub discharge_date: Option<NaiveDate>,
+  pub discharge_reason: Option<DischargeReason>,
+}
+
+impl Enrollment {
+  pub fn new(referral_date: Option<NaiveDate>, enrollment_date: NaiveDate) -> Enrollment {
+    Enrollment { referral_date, enrollment_date, discharge_date: None, discharge_reason: None }
+  }
+  /// whether a date falls within the episode, counting from referral if there was one
+  pub fn contains(&self, date: NaiveDate) -> bool {
+    date >= self.referral_date.unwrap_or(self.enrollment_date) && self.discharge_date.is_none_or(|d| date <= d )
+  }
+  pub fn fmt_status(&self) -> String {
+    match (self.discharge_date, &self.discharge_reason) {
+      (Some(d), Some(reason)) => format!("Discharged {} ({})", d.format("%Y-%m-%d"), reason),
+      (Some(d), None) => format!("Discharged {}", d.format("%Y-%m-%d")),
+      (None, _) => format!("Enrolled {}", self.enrollment_date.format("%Y-%m-%d")),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DischargeReason {
+  GoalsMet,
+  FamilyDeclined,
+  LostContact,
+  MovedAway,
+  HigherLevelOfCare,
+  AgedOut,
+  Other(String),
+}
+
+impl DischargeReason {
+  /// every reason except Other, in menu order
+  pub fn listed() -> Vec<DischargeReason> {
+    use DischargeReason::*;
+    vec![GoalsMet, FamilyDeclined, LostContact, MovedAway, HigherLevelOfCare, AgedOut]
+  }
+  /// a listed reason by its number in the menu or by name, or else Other with the text as given
+  pub fn parse(s: &str) -> DischargeReason {
+    let s = s.trim();
+    let listed = DischargeReason::listed();
+    if let Some(r) = s.parse::<usize>().ok().and_then(|i| i.checked_sub(1) ).and_then(|i| listed.get(i) ) {
+      return r.clone();
+    }
+    let squashed: String = s.chars().filter(|c| c.is_alphanumeric() ).collect();
+    listed.into_iter()
+      .find(|r| format!("{:?}", r).eq_ignore_ascii_case(&squashed) || r.to_string().eq_ignore_ascii_case(s) )
+      .unwrap_or(DischargeReason::Other(s.to_string()))
+  }
+}
+
+impl fmt::Display for DischargeReason {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use DischargeReason::*;
+    match self {
+      GoalsMet => write!(f, "goals met"),
+      FamilyDeclined => write!(f, "family declined services"),
+      LostContact => write!(f, "lost contact"),
+      MovedAway => write!(f, "moved out of the area"),
+      HigherLevelOfCare => write!(f, "higher level of care"),
+      AgedOut => write!(f, "aged out"),
+      Other(reason) => write!(f, "{}", reason),
+    }
+  }
+}
+
 impl fmt::Display for Client {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let first_name = self.first_name.replace(" | ", " / ");
@@ -129,4 +246,25 @@ mod tests {
     assert_eq!(c2.pronouns, 1);
     assert_eq!(c2.foreign_keys["collateral_ids"], test_vec);
   }
+
+  #[test]
+  fn discharges_and_reactivates() {
+    let d = |m, day| NaiveDate::from_ymd(2021, m, day);
+    let mut c = Client::new(1, String::from("Bob"), String::from("Smith"), NaiveDate::from_ymd(2010, 1, 1), 2, vec![]);
+    assert!(c.is_active());
+    assert!(c.discharge(d(3, 1), DischargeReason::GoalsMet).is_err());
+    c.episodes.push(Enrollment::new(Some(d(1, 4)), d(1, 11)));
+    assert!(c.discharge(d(1, 5), DischargeReason::GoalsMet).is_err());
+    c.discharge(d(6, 30), DischargeReason::parse("moved away")).unwrap();
+    assert!(!c.is_active());
+    assert_eq!(c.fmt_status(), String::from("Discharged 2021-06-30 (moved out of the area)"));
+    assert!(c.discharge(d(7, 1), DischargeReason::GoalsMet).is_err());
+    assert!(c.reactivate(None, d(6, 1)).is_err());
+    c.reactivate(Some(d(9, 1)), d(9, 8)).unwrap();
+    assert!(c.is_active());
+    assert_eq!(c.episodes.len(), 2);
+    assert!(c.episodes[0].contains(d(1, 4)) && !c.episodes[0].contains(d(7, 1)));
+    assert_eq!(DischargeReason::parse("1"), DischargeReason::GoalsMet);
+    assert_eq!(DischargeReason::parse("Transferred"), DischargeReason::Other(String::from("Transferred")));
+  }
 }
\ No newline at end of file
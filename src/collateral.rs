@@ -1,7 +1,8 @@
 use std::fmt;
 use chrono::{NaiveDate, Datelike};
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collateral {
   pub id: u32,
   pub first_name: String,
@@ -27,7 +28,7 @@ impl PartialEq for Collateral {
   }
 }
 
-#[derive(Debug, Clone, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, PartialOrd, Eq, Ord, Serialize, Deserialize)]
 pub enum SupportType {
   Natural,
   Formal,
@@ -204,6 +205,7 @@ mod tests {
       false,
       false,
       false,
+      NaiveDate::from_ymd(2020, 1, 1),
     );
     assert_eq!(c1.id, 1);
     assert_eq!(c1.first_name, String::from("Bob"));
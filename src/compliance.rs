@@ -0,0 +1,329 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+
+use crate::billing::*;
+use crate::note::*;
+use crate::note_archive::*;
+use crate::pdf::month_bounds;
+
+/// deadlines this close are shown as upcoming
+pub const UPCOMING_DAYS: i64 = 14;
+/// alerts listed under the caseload on the main menu; the rest are in the deadlines report
+pub const MAX_MENU_ALERTS: usize = 8;
+/// missed contact minimums are only reported for periods that ended this recently
+pub const COMPLIANCE_LOOKBACK_DAYS: i64 = 90;
+
+/// which notes a rule counts: by note structure ('Intake', 'CarePlan') or by contact type
+/// ('FaceToFaceContactWithClient', 'CrisisSupport'), using the names from BillingCode::key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoteMatch {
+  Structure(StructureType),
+  Contact(BillingCode),
+}
+
+impl NoteMatch {
+  pub fn parse(s: &str) -> Result<NoteMatch, String> {
+    if let Some(st) = StructureType::iterator().find(|st| format!("{:?}", st).eq_ignore_ascii_case(s) ) {
+      return Ok(NoteMatch::Structure(st));
+    }
+    match BillingCode::all().into_iter().find(|c| c.key().eq_ignore_ascii_case(s) ) {
+      Some(code) => Ok(NoteMatch::Contact(code)),
+      None => Err(format!("'{}' is not a note structure or contact type.", s)),
+    }
+  }
+  pub fn matches(&self, n: &Note) -> bool {
+    match self {
+      NoteMatch::Structure(st) => n.structure == *st,
+      NoteMatch::Contact(code) => BillingCode::for_category(&n.category) == *code,
+    }
+  }
+  /// the name used in rules, which parse reads back
+  pub fn key(&self) -> String {
+    match self {
+      NoteMatch::Structure(st) => format!("{:?}", st),
+      NoteMatch::Contact(code) => code.key().to_string(),
+    }
+  }
+}
+
+impl fmt::Display for NoteMatch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NoteMatch::Structure(st) => write!(f, "{}", st),
+      NoteMatch::Contact(code) => write!(f, "{}", code),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContactPeriod {
+  Week,
+  Month,
+}
+
+impl ContactPeriod {
+  /// weeks run Monday to Sunday
+  pub fn containing(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match self {
+      ContactPeriod::Week => {
+        let first = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        (first, first + Duration::days(6))
+      },
+      ContactPeriod::Month => month_bounds(date.year(), date.month()).unwrap(),
+    }
+  }
+}
+
+impl fmt::Display for ContactPeriod {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ContactPeriod::Week => write!(f, "week"),
+      ContactPeriod::Month => write!(f, "month"),
+    }
+  }
+}
+
+/// a required timeline, written in the config file as one of
+/// 'CarePlan within 30 days of Intake', 'CarePlan every 90 days' or '1 FaceToFaceContactWithClient per week'
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComplianceRule {
+  WithinDaysOf { required: NoteMatch, days: u32, anchor: NoteMatch },
+  EveryDays { required: NoteMatch, days: u32 },
+  MinimumPer { count: u32, required: NoteMatch, period: ContactPeriod },
+}
+
+use ComplianceRule::{WithinDaysOf, EveryDays, MinimumPer};
+
+impl ComplianceRule {
+  pub fn parse(s: &str) -> Result<ComplianceRule, String> {
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let lower: Vec<String> = words.iter().map(|w| w.to_ascii_lowercase() ).collect();
+    let lower: Vec<&str> = lower.iter().map(|w| &w[..] ).collect();
+    let number = |w: &str| w.parse::<u32>().map_err(|_| format!("'{}' is not a whole number in rule '{}'.", w, s) );
+    match &lower[..] {
+      [_, "within", _, "days", "of", _] => Ok(WithinDaysOf {
+        required: NoteMatch::parse(words[0])?,
+        days: number(words[2])?,
+        anchor: NoteMatch::parse(words[5])?,
+      }),
+      [_, "every", _, "days"] => Ok(EveryDays {
+        required: NoteMatch::parse(words[0])?,
+        days: number(words[2])?,
+      }),
+      [_, _, "per", period] => Ok(MinimumPer {
+        count: number(words[0])?,
+        required: NoteMatch::parse(words[1])?,
+        period: match *period {
+          "week" => ContactPeriod::Week,
+          "month" => ContactPeriod::Month,
+          _ => return Err(format!("Rule '{}' must be per week or per month.", s)),
+        },
+      }),
+      _ => Err(format!(
+        "Rule '{}' must read 'X within N days of Y', 'X every N days' or 'N X per week' (or month).",
+        s,
+      )),
+    }
+  }
+  /// the rule in plain words, for alerts and reports
+  pub fn description(&self) -> String {
+    match self {
+      WithinDaysOf { required, days, anchor } => format!("{} within {} days of {}", required, days, anchor),
+      EveryDays { required, days } => format!("{} every {} days", required, days),
+      MinimumPer { count, required, period } => format!("at least {} {} per {}", count, required, period),
+    }
+  }
+  /// the deadlines a client is past or close to under this rule; `notes` are all of the client's notes
+  pub fn evaluate(&self, notes: &[&Note], today: NaiveDate) -> Vec<(NaiveDate, DeadlineStatus)> {
+    let done: Vec<&Note> = notes.iter().copied().filter(|n| n.date <= today ).collect();
+    let earliest = |m: &NoteMatch| done.iter().filter(|n| m.matches(n) ).map(|n| n.date ).min();
+    let latest = |m: &NoteMatch| done.iter().filter(|n| m.matches(n) ).map(|n| n.date ).max();
+    match self {
+      WithinDaysOf { required, days, anchor } => {
+        let anchor_date = match earliest(anchor) {
+          Some(d) => d,
+          None => return vec![],
+        };
+        if done.iter().any(|n| required.matches(n) && n.date >= anchor_date ) {
+          return vec![];
+        }
+        deadline_status(anchor_date + Duration::days(*days as i64), today).into_iter().collect()
+      },
+      EveryDays { required, days } => match latest(required) {
+        Some(last) => deadline_status(last + Duration::days(*days as i64), today).into_iter().collect(),
+        None => vec![],
+      },
+      MinimumPer { count, required, period } => {
+        let start = match done.iter().map(|n| n.date ).min() {
+          Some(d) => d.max(today - Duration::days(COMPLIANCE_LOOKBACK_DAYS)),
+          None => return vec![],
+        };
+        let mut deadlines: Vec<(NaiveDate, DeadlineStatus)> = vec![];
+        let (mut first, mut last) = period.containing(start);
+        while first <= today {
+          let contacts = done.iter().filter(|n| required.matches(n) && n.date >= first && n.date <= last ).count();
+          if contacts < *count as usize {
+            deadlines.extend(deadline_status(last, today));
+          }
+          let next = period.containing(last + Duration::days(1));
+          first = next.0;
+          last = next.1;
+        }
+        deadlines
+      },
+    }
+  }
+}
+
+impl fmt::Display for ComplianceRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      WithinDaysOf { required, days, anchor } => write!(f, "{} within {} days of {}", required.key(), days, anchor.key()),
+      EveryDays { required, days } => write!(f, "{} every {} days", required.key(), days),
+      MinimumPer { count, required, period } => write!(f, "{} {} per {}", count, required.key(), period),
+    }
+  }
+}
+
+/// wraparound timelines used when the config file has no 'rule.' settings
+pub fn default_compliance_rules() -> Vec<(String, ComplianceRule)> {
+  [
+    ("assessment", "Assessment within 30 days of Intake"),
+    ("initial_care_plan", "CarePlan within 45 days of Intake"),
+    ("care_plan_review", "CarePlan every 90 days"),
+    ("weekly_contact", "1 FaceToFaceContactWithClient per week"),
+  ].iter().map(|(name, rule)| (name.to_string(), ComplianceRule::parse(rule).unwrap()) ).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DeadlineStatus {
+  Overdue,
+  Upcoming,
+}
+
+impl fmt::Display for DeadlineStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      DeadlineStatus::Overdue => write!(f, "overdue"),
+      DeadlineStatus::Upcoming => write!(f, "upcoming"),
+    }
+  }
+}
+
+fn deadline_status(due: NaiveDate, today: NaiveDate) -> Option<(NaiveDate, DeadlineStatus)> {
+  if due < today {
+    Some((due, DeadlineStatus::Overdue))
+  } else if (due - today).num_days() <= UPCOMING_DAYS {
+    Some((due, DeadlineStatus::Upcoming))
+  } else {
+    None
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComplianceAlert {
+  pub client_id: u32,
+  pub client_name: String,
+  pub rule_name: String,
+  pub rule: String,
+  pub due: NaiveDate,
+  pub status: DeadlineStatus,
+}
+
+impl ComplianceAlert {
+  pub fn description(&self, today: NaiveDate) -> String {
+    let when = match (self.due - today).num_days() {
+      0 => String::from("due today"),
+      days if days > 0 => format!("due in {} days", days),
+      days => format!("{} days overdue", -days),
+    };
+    format!("{}: {} ({}, {})", self.client_name, self.rule, self.due.format("%Y-%m-%d"), when)
+  }
+}
+
+/// every overdue or upcoming deadline for the active clients, overdue first, then by due date;
+/// only notes from a client's current enrollment count, so a reactivated client starts over
+pub fn compliance_alerts(a: &NoteArchive, client_ids: &[u32], rules: &[(String, ComplianceRule)], today: NaiveDate) -> Vec<ComplianceAlert> {
+  let mut alerts: Vec<ComplianceAlert> = vec![];
+  for c in client_ids.iter().filter_map(|id| a.get_client_by_id(*id) ).filter(|c| c.is_active() ) {
+    let notes: Vec<&Note> = a.notes.iter()
+      .filter(|n| n.foreign_key["client_id"] == c.id )
+      .filter(|n| c.current_episode().is_none_or(|e| e.contains(n.date) ) )
+      .collect();
+    for (name, rule) in rules {
+      for (due, status) in rule.evaluate(&notes, today) {
+        alerts.push(ComplianceAlert {
+          client_id: c.id,
+          client_name: c.full_name(),
+          rule_name: name.clone(),
+          rule: rule.description(),
+          due,
+          status,
+        });
+      }
+    }
+  }
+  alerts.sort_by(|x, y| (x.status, x.due, &x.client_name).cmp(&(y.status, y.due, &y.client_name)) );
+  alerts
+}
+
+pub fn compliance_alerts_csv(alerts: &[ComplianceAlert]) -> String {
+  let mut output = String::from("client_id,client_name,rule_name,rule,due,status\n");
+  for alert in alerts {
+    let fields = [
+      alert.client_id.to_string(),
+      alert.client_name.clone(),
+      alert.rule_name.clone(),
+      alert.rule.clone(),
+      alert.due.format("%Y-%m-%d").to_string(),
+      alert.status.to_string(),
+    ];
+    output.push_str(&fields.iter().map(|f| csv_field(f) ).collect::<Vec<String>>().join(","));
+    output.push('\n');
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_rules() {
+    for spec in ["CarePlan within 30 days of Intake", "CarePlan every 90 days", "2 CrisisSupport per month"] {
+      assert_eq!(ComplianceRule::parse(spec).unwrap().to_string(), spec);
+    }
+    assert_eq!(
+      ComplianceRule::parse("1 facetofacecontactwithclient PER WEEK").unwrap().description(),
+      String::from("at least 1 ICC - Face to face contact with client per week"),
+    );
+    assert!(ComplianceRule::parse("CarePlan within soon days of Intake").is_err());
+    assert!(ComplianceRule::parse("Lunch every 7 days").is_err());
+    assert!(ComplianceRule::parse("1 Intake per year").is_err());
+  }
+
+  #[test]
+  fn finds_overdue_and_upcoming_deadlines() {
+    let d = |m, day| NaiveDate::from_ymd(2021, m, day);
+    let note = |date, structure, category| Note::new(1, date, category, structure, String::new(), 1, 1, vec![]);
+    let face_to_face = NoteCategory::ICCNote(ICCNoteCategory::FaceToFaceContactWithClient);
+    let intake = note(d(3, 1), StructureType::Intake, face_to_face);
+    let visit = note(d(3, 10), StructureType::HomeVisit, face_to_face);
+    let notes = vec![&intake, &visit];
+    let today = d(3, 24);
+
+    let care_plan = ComplianceRule::parse("CarePlan within 30 days of Intake").unwrap();
+    assert_eq!(care_plan.evaluate(&notes, today), vec![(d(3, 31), DeadlineStatus::Upcoming)]);
+    assert_eq!(care_plan.evaluate(&notes, d(4, 1)), vec![(d(3, 31), DeadlineStatus::Overdue)]);
+    let plan = note(d(3, 20), StructureType::CarePlan, face_to_face);
+    assert_eq!(care_plan.evaluate(&[&intake, &plan], today), vec![]);
+
+    // weeks starting March 1, 8, 15 and 22: the weeks of the 15th and 22nd have no visits
+    let weekly = ComplianceRule::parse("1 FaceToFaceContactWithClient per week").unwrap();
+    assert_eq!(weekly.evaluate(&notes, today), vec![
+      (d(3, 21), DeadlineStatus::Overdue),
+      (d(3, 28), DeadlineStatus::Upcoming),
+    ]);
+    assert_eq!(ComplianceRule::parse("Intake every 90 days").unwrap().evaluate(&notes, today), vec![]);
+  }
+}
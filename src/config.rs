@@ -0,0 +1,437 @@
+use chrono::NaiveDate;
+use std::collections::{HashMap, BTreeMap};
+use std::env;
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+
+use crate::ehr_entry::*;
+use crate::billing::*;
+use crate::travel::*;
+use crate::dashboard::*;
+use crate::compliance::*;
+
+pub const USR_FL: &str = "users.txt";
+pub const CLT_FL: &str = "clients.txt";
+pub const G_FL: &str = "goals.txt";
+pub const COL_FL: &str = "collaterals.txt";
+pub const GCOL_FL: &str = "general_collaterals.txt";
+pub const PRN_FL: &str = "pronouns.txt";
+pub const ND_FL: &str = "note_days.txt";
+pub const NT_FL: &str = "note_templates.txt";
+pub const N_FL: &str = "note.txt";
+pub const ARCHIVE_FL: &str = "archive.json";
+pub const VOCAB_FL: &str = "vocabularies.txt";
+
+pub const CONFIG_FL: &str = "config.txt";
+pub const DATA_DIR_ENV: &str = "WRAP_NOTES_DATA_DIR";
+pub const PROFILE_ENV: &str = "WRAP_NOTES_PROFILE";
+
+/// settings saved in the per-user config file, one 'key = value' pair per line
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Config {
+  pub data_dir: Option<PathBuf>,
+  pub default_profile: Option<String>,
+  pub profiles: BTreeMap<String, PathBuf>,
+  pub idle_lock_minutes: Option<u64>,
+  pub backup_count: Option<usize>,
+  /// the command the EHR paragraphs are piped to, such as 'pbcopy' or 'xclip -selection clipboard'
+  pub clipboard_command: Option<String>,
+  /// character limits for EHR text boxes, keyed by section (see ehr_section_key)
+  pub char_limits: BTreeMap<String, usize>,
+  /// minutes in one billing unit, DEFAULT_UNIT_MINUTES if not set
+  pub billing_unit_minutes: Option<u32>,
+  /// units per client per day across all contacts
+  pub daily_unit_cap: Option<u32>,
+  /// units per client per day for one billing code, keyed by BillingCode::key
+  pub daily_unit_caps: BTreeMap<String, u32>,
+  /// the first day of any pay period; without it, pay periods are the 1st to 15th and 16th to the end of the month
+  pub pay_period_start: Option<NaiveDate>,
+  /// days in a pay period counted from pay_period_start, 14 if not set
+  pub pay_period_days: Option<u32>,
+  /// days after a client's last note day until the dashboard shows their next contact as due
+  pub contact_due_days: Option<u32>,
+  /// deadline rules keyed by name, such as 'rule.care_plan = CarePlan within 30 days of Intake'
+  pub compliance_rules: BTreeMap<String, ComplianceRule>,
+}
+
+/// data directory and profile chosen on the command line or in the environment
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlobalOptions {
+  pub data_dir: Option<String>,
+  pub profile: Option<String>,
+}
+
+impl GlobalOptions {
+  pub fn from_env() -> GlobalOptions {
+    GlobalOptions {
+      data_dir: env::var(DATA_DIR_ENV).ok().filter(|s| !s.trim().is_empty() ),
+      profile: env::var(PROFILE_ENV).ok().filter(|s| !s.trim().is_empty() ),
+    }
+  }
+  /// removes '--data-dir' and '--profile' from the arguments, returning them with the remaining arguments
+  pub fn split_from_args(args: &[String]) -> Result<(GlobalOptions, Vec<String>), String> {
+    let mut options = GlobalOptions::default();
+    let mut rest: Vec<String> = vec![];
+    let mut i = 0;
+    while i < args.len() {
+      match &args[i][..] {
+        "--data-dir" | "--profile" => {
+          let value = match args.get(i+1) {
+            Some(v) => v.clone(),
+            None => return Err(format!("Missing value for option '{}'.", args[i])),
+          };
+          if args[i] == "--data-dir" {
+            options.data_dir = Some(value);
+          } else {
+            options.profile = Some(value);
+          }
+          i += 2;
+        },
+        _ => {
+          rest.push(args[i].clone());
+          i += 1;
+        },
+      }
+    }
+    Ok((options, rest))
+  }
+}
+
+impl Config {
+  pub fn parse(s: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+    for (i, line) in s.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let (key, value) = match line.find('=') {
+        Some(idx) => (line[..idx].trim(), line[idx+1..].trim()),
+        None => return Err(format!("Line {} of config file is not a 'key = value' pair.", i+1)),
+      };
+      match key {
+        "data_dir" => config.data_dir = Some(PathBuf::from(value)),
+        "default_profile" => config.default_profile = Some(value.to_string()),
+        "idle_lock_minutes" => match value.parse::<u64>() {
+          Ok(minutes) => config.idle_lock_minutes = Some(minutes),
+          Err(_) => return Err(format!("Line {} of config file: 'idle_lock_minutes' must be a whole number.", i+1)),
+        },
+        "backup_count" => match value.parse::<usize>() {
+          Ok(count) => config.backup_count = Some(count),
+          Err(_) => return Err(format!("Line {} of config file: 'backup_count' must be a whole number.", i+1)),
+        },
+        "clipboard_command" => config.clipboard_command = Some(value.to_string()),
+        "billing_unit_minutes" => config.billing_unit_minutes = Some(parse_whole_number(key, value, i)?),
+        "daily_unit_cap" => config.daily_unit_cap = Some(parse_whole_number(key, value, i)?),
+        "pay_period_start" => match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+          Ok(date) => config.pay_period_start = Some(date),
+          Err(_) => return Err(format!("Line {} of config file: 'pay_period_start' must be a date (YYYY-MM-DD).", i+1)),
+        },
+        "pay_period_days" => config.pay_period_days = Some(parse_whole_number(key, value, i)?),
+        "contact_due_days" => config.contact_due_days = Some(parse_whole_number(key, value, i)?),
+        _ => match (
+          key.strip_prefix("profile."),
+          key.strip_prefix("char_limit."),
+          key.strip_prefix("daily_unit_cap."),
+          key.strip_prefix("rule."),
+        ) {
+          (Some(name), _, _, _) if !name.is_empty() => {
+            config.profiles.insert(name.to_string(), PathBuf::from(value));
+          },
+          (_, Some(section), _, _) if ehr_sections().iter().any(|s| ehr_section_key(s) == section ) => {
+            config.char_limits.insert(section.to_string(), parse_whole_number(key, value, i)?);
+          },
+          (_, _, Some(code), _) if BillingCode::all().iter().any(|c| c.key() == code ) => {
+            config.daily_unit_caps.insert(code.to_string(), parse_whole_number(key, value, i)?);
+          },
+          (_, _, _, Some(name)) if !name.is_empty() => match ComplianceRule::parse(value) {
+            Ok(rule) => {
+              config.compliance_rules.insert(name.to_string(), rule);
+            },
+            Err(e) => return Err(format!("Line {} of config file: {}", i+1, e)),
+          },
+          _ => return Err(format!("Unknown setting '{}' on line {} of config file.", key, i+1)),
+        },
+      }
+    }
+    Ok(config)
+  }
+  pub fn to_file_string(&self) -> String {
+    let mut lines = String::from("# wrap_notes configuration\n");
+    if let Some(dir) = &self.data_dir {
+      lines.push_str(&format!("data_dir = {}\n", dir.display()));
+    }
+    if let Some(profile) = &self.default_profile {
+      lines.push_str(&format!("default_profile = {}\n", profile));
+    }
+    if let Some(minutes) = self.idle_lock_minutes {
+      lines.push_str(&format!("idle_lock_minutes = {}\n", minutes));
+    }
+    if let Some(count) = self.backup_count {
+      lines.push_str(&format!("backup_count = {}\n", count));
+    }
+    if let Some(command) = &self.clipboard_command {
+      lines.push_str(&format!("clipboard_command = {}\n", command));
+    }
+    for (section, limit) in &self.char_limits {
+      lines.push_str(&format!("char_limit.{} = {}\n", section, limit));
+    }
+    if let Some(minutes) = self.billing_unit_minutes {
+      lines.push_str(&format!("billing_unit_minutes = {}\n", minutes));
+    }
+    if let Some(cap) = self.daily_unit_cap {
+      lines.push_str(&format!("daily_unit_cap = {}\n", cap));
+    }
+    for (code, cap) in &self.daily_unit_caps {
+      lines.push_str(&format!("daily_unit_cap.{} = {}\n", code, cap));
+    }
+    if let Some(start) = self.pay_period_start {
+      lines.push_str(&format!("pay_period_start = {}\n", start.format("%Y-%m-%d")));
+    }
+    if let Some(days) = self.pay_period_days {
+      lines.push_str(&format!("pay_period_days = {}\n", days));
+    }
+    if let Some(days) = self.contact_due_days {
+      lines.push_str(&format!("contact_due_days = {}\n", days));
+    }
+    for (name, rule) in &self.compliance_rules {
+      lines.push_str(&format!("rule.{} = {}\n", name, rule));
+    }
+    for (name, dir) in &self.profiles {
+      lines.push_str(&format!("profile.{} = {}\n", name, dir.display()));
+    }
+    lines
+  }
+  /// reads the config file if there is one; a missing file is an empty config
+  pub fn load() -> Result<Config, String> {
+    match config_file_path() {
+      Some(fp) if fp.exists() => {
+        let s = fs::read_to_string(&fp).map_err(|e| format!("Failed to read {}: {}", fp.display(), e) )?;
+        Config::parse(&s)
+      },
+      _ => Ok(Config::default()),
+    }
+  }
+  pub fn save(&self) -> Result<(), Error> {
+    let fp = match config_file_path() {
+      Some(fp) => fp,
+      None => return Err(Error::other("Unable to locate a config directory (HOME is not set).")),
+    };
+    if let Some(dir) = fp.parent() {
+      fs::create_dir_all(dir)?;
+    }
+    fs::write(fp, self.to_file_string())
+  }
+  pub fn billing_settings(&self) -> BillingSettings {
+    BillingSettings {
+      unit_minutes: self.billing_unit_minutes.unwrap_or(DEFAULT_UNIT_MINUTES),
+      daily_cap: self.daily_unit_cap,
+      code_caps: self.daily_unit_caps.clone(),
+    }
+  }
+  pub fn contact_due_days(&self) -> u32 {
+    self.contact_due_days.unwrap_or(DEFAULT_CONTACT_DUE_DAYS)
+  }
+  /// the configured deadline rules, or the default ones if none are set
+  pub fn compliance_rules(&self) -> Vec<(String, ComplianceRule)> {
+    if self.compliance_rules.is_empty() {
+      default_compliance_rules()
+    } else {
+      self.compliance_rules.iter().map(|(name, rule)| (name.clone(), rule.clone()) ).collect()
+    }
+  }
+  pub fn pay_period(&self) -> PayPeriod {
+    match self.pay_period_start {
+      Some(start) => PayPeriod::FixedDays(start, self.pay_period_days.unwrap_or(14)),
+      None => PayPeriod::SemiMonthly,
+    }
+  }
+  pub fn profile_dir(&self, name: &str) -> Result<PathBuf, String> {
+    match self.profiles.get(name) {
+      Some(dir) => Ok(dir.clone()),
+      None => Err(format!("No profile named '{}'. Add one with 'wrap_notes profile add --name {} --dir PATH'.", name, name)),
+    }
+  }
+  /// picks the data directory: flags, then environment, then the config file, then the default location
+  pub fn resolve_data_dir(&self, flags: &GlobalOptions, env_options: &GlobalOptions) -> Result<PathBuf, String> {
+    if let Some(dir) = &flags.data_dir {
+      return Ok(PathBuf::from(dir));
+    }
+    if let Some(profile) = &flags.profile {
+      return self.profile_dir(profile);
+    }
+    if let Some(dir) = &env_options.data_dir {
+      return Ok(PathBuf::from(dir));
+    }
+    if let Some(profile) = &env_options.profile {
+      return self.profile_dir(profile);
+    }
+    if let Some(profile) = &self.default_profile {
+      return self.profile_dir(profile);
+    }
+    if let Some(dir) = &self.data_dir {
+      return Ok(dir.clone());
+    }
+    Ok(default_data_dir())
+  }
+}
+
+fn parse_whole_number<T: std::str::FromStr>(key: &str, value: &str, line_index: usize) -> Result<T, String> {
+  value.parse::<T>().map_err(|_| format!("Line {} of config file: '{}' must be a whole number.", line_index+1, key) )
+}
+
+fn home_dir() -> Option<PathBuf> {
+  env::var_os("HOME").or_else(|| env::var_os("USERPROFILE") ).map(PathBuf::from)
+}
+
+/// $XDG_CONFIG_HOME/wrap_notes/config.txt, falling back to ~/.config
+pub fn config_file_path() -> Option<PathBuf> {
+  let base = match env::var_os("XDG_CONFIG_HOME") {
+    Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+    _ => home_dir().map(|h| h.join(".config") ),
+  };
+  base.map(|b| b.join("wrap_notes").join(CONFIG_FL) )
+}
+
+/// archives created before the data directory setting live in the current directory, so keep using it if it has one;
+/// otherwise use $XDG_DATA_HOME/wrap_notes (or ~/.local/share/wrap_notes)
+pub fn default_data_dir() -> PathBuf {
+  if Path::new(ARCHIVE_FL).exists() || Path::new(USR_FL).exists() {
+    return PathBuf::from(".");
+  }
+  let base = match env::var_os("XDG_DATA_HOME") {
+    Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+    _ => home_dir().map(|h| h.join(".local").join("share") ),
+  };
+  match base {
+    Some(b) => b.join("wrap_notes"),
+    None => PathBuf::from("."),
+  }
+}
+
+pub fn filepaths_for_dir(dir: &Path) -> HashMap<String, String> {
+  let fp = |name: &str| dir.join(name).to_string_lossy().to_string();
+  [
+    (String::from("archive_filepath"), fp(ARCHIVE_FL),),
+    (String::from("user_filepath"), fp(USR_FL),),
+    (String::from("client_filepath"), fp(CLT_FL),),
+    (String::from("goal_filepath"), fp(G_FL),),
+    (String::from("collateral_filepath"), fp(COL_FL),),
+    (String::from("general_collateral_filepath"), fp(GCOL_FL),),
+    (String::from("pronouns_filepath"), fp(PRN_FL),),
+    (String::from("note_day_filepath"), fp(ND_FL),),
+    (String::from("note_template_filepath"), fp(NT_FL),),
+    (String::from("note_filepath"), fp(N_FL),),
+    (String::from("vocabulary_filepath"), fp(VOCAB_FL),),
+  ].iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolves_data_dir_in_order() {
+    let config = Config::parse("\
+      # comment\n\
+      data_dir = /srv/notes\n\
+      default_profile = live\n\
+      profile.live = /srv/live\n\
+      profile.training = /srv/training\n\
+    ").unwrap();
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+
+    let none = GlobalOptions::default();
+    let flag_dir = GlobalOptions { data_dir: Some(String::from("/tmp/flag")), profile: Some(String::from("training")) };
+    let flag_profile = GlobalOptions { data_dir: None, profile: Some(String::from("training")) };
+    let env_dir = GlobalOptions { data_dir: Some(String::from("/tmp/env")), profile: None };
+
+    assert_eq!(config.resolve_data_dir(&flag_dir, &env_dir).unwrap(), PathBuf::from("/tmp/flag"));
+    assert_eq!(config.resolve_data_dir(&flag_profile, &env_dir).unwrap(), PathBuf::from("/srv/training"));
+    assert_eq!(config.resolve_data_dir(&none, &env_dir).unwrap(), PathBuf::from("/tmp/env"));
+    assert_eq!(config.resolve_data_dir(&none, &none).unwrap(), PathBuf::from("/srv/live"));
+
+    let missing = GlobalOptions { data_dir: None, profile: Some(String::from("nope")) };
+    assert!(config.resolve_data_dir(&missing, &none).is_err());
+    assert!(Config::parse("colour = blue").is_err());
+  }
+
+  #[test]
+  fn reads_idle_lock_minutes() {
+    let config = Config::parse("idle_lock_minutes = 10").unwrap();
+    assert_eq!(config.idle_lock_minutes, Some(10));
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert!(Config::parse("idle_lock_minutes = soon").is_err());
+  }
+
+  #[test]
+  fn reads_backup_count() {
+    let config = Config::parse("backup_count = 5").unwrap();
+    assert_eq!(config.backup_count, Some(5));
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert!(Config::parse("backup_count = -1").is_err());
+  }
+
+  #[test]
+  fn reads_clipboard_command_and_char_limits() {
+    let config = Config::parse("\
+      clipboard_command = xclip -selection clipboard\n\
+      char_limit.CareCoordination = 2000\n\
+    ").unwrap();
+    assert_eq!(config.clipboard_command, Some(String::from("xclip -selection clipboard")));
+    assert_eq!(config.char_limits.get("CareCoordination"), Some(&2000));
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert!(Config::parse("char_limit.Lunch = 100").is_err());
+  }
+
+  #[test]
+  fn reads_billing_settings() {
+    let config = Config::parse("\
+      billing_unit_minutes = 15\n\
+      daily_unit_cap = 32\n\
+      daily_unit_cap.CrisisSupport = 8\n\
+    ").unwrap();
+    assert_eq!(config.billing_settings().unit_minutes, 15);
+    assert_eq!(config.billing_settings().daily_cap, Some(32));
+    assert_eq!(config.daily_unit_caps.get("CrisisSupport"), Some(&8));
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert!(Config::parse("daily_unit_cap.Lunch = 4").is_err());
+  }
+
+  #[test]
+  fn reads_pay_period() {
+    let config = Config::parse("pay_period_start = 2021-01-04").unwrap();
+    assert_eq!(config.pay_period(), PayPeriod::FixedDays(NaiveDate::from_ymd(2021, 1, 4), 14));
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert_eq!(Config::default().pay_period(), PayPeriod::SemiMonthly);
+    assert!(Config::parse("pay_period_start = Monday").is_err());
+  }
+
+  #[test]
+  fn reads_contact_due_days() {
+    let config = Config::parse("contact_due_days = 14").unwrap();
+    assert_eq!(config.contact_due_days(), 14);
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert_eq!(Config::default().contact_due_days(), DEFAULT_CONTACT_DUE_DAYS);
+  }
+
+  #[test]
+  fn reads_compliance_rules() {
+    let config = Config::parse("rule.care_plan = CarePlan within 30 days of Intake").unwrap();
+    assert_eq!(config.compliance_rules().len(), 1);
+    assert_eq!(Config::parse(&config.to_file_string()).unwrap(), config);
+    assert_eq!(Config::default().compliance_rules(), default_compliance_rules());
+    assert!(Config::parse("rule.lunch = Lunch every 7 days").is_err());
+  }
+
+  #[test]
+  fn splits_global_options_from_args() {
+    let args: Vec<String> = vec!["--profile", "training", "note", "list", "--client", "3"].iter().map(|s| s.to_string() ).collect();
+    let (options, rest) = GlobalOptions::split_from_args(&args).unwrap();
+    assert_eq!(options.profile, Some(String::from("training")));
+    assert_eq!(rest, vec!["note", "list", "--client", "3"]);
+    let fps = filepaths_for_dir(Path::new("/srv/live"));
+    assert_eq!(fps["user_filepath"], String::from("/srv/live/users.txt"));
+  }
+}
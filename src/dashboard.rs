@@ -0,0 +1,130 @@
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+
+use crate::note::*;
+use crate::note_archive::*;
+use crate::user::*;
+
+use NoteCategory::{ICCNote, FPNote};
+use FPNoteCategory::{DescriptionOfIntervention, ResponseToIntervention};
+
+/// a client is due for contact this many days after the last note day, unless the config says otherwise
+pub const DEFAULT_CONTACT_DUE_DAYS: u32 = 7;
+
+/// one client on the current user's caseload
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseloadRow {
+  pub client_id: u32,
+  pub client_name: String,
+  pub last_note_day: Option<NaiveDate>,
+  pub last_contact: Option<String>,
+  pub notes_with_unfilled_blanks: usize,
+  /// when the next contact is due; None if the client has never had a note day
+  pub next_contact_due: Option<NaiveDate>,
+}
+
+impl CaseloadRow {
+  pub fn days_since_last_note_day(&self, today: NaiveDate) -> Option<i64> {
+    self.last_note_day.map(|d| (today - d).num_days() )
+  }
+  pub fn is_overdue(&self, today: NaiveDate) -> bool {
+    self.next_contact_due.is_none_or(|due| due < today )
+  }
+  pub fn fmt_days_since(&self, today: NaiveDate) -> String {
+    match self.days_since_last_note_day(today) {
+      Some(0) => String::from("today"),
+      Some(1) => String::from("1 day"),
+      Some(days) => format!("{} days", days),
+      None => String::from("never"),
+    }
+  }
+  pub fn fmt_due(&self, today: NaiveDate) -> String {
+    let due = match self.next_contact_due {
+      Some(due) => due,
+      None => return String::from("no contact yet"),
+    };
+    match (due - today).num_days() {
+      0 => String::from("due today"),
+      1 => String::from("due tomorrow"),
+      days if days > 1 => format!("due in {} days", days),
+      -1 => String::from("1 day overdue"),
+      days => format!("{} days overdue", -days),
+    }
+  }
+}
+
+/// the short name of a contact type, without the ICC/FP prefix
+pub fn contact_type(category: &NoteCategory) -> String {
+  match category {
+    ICCNote(c) => c.to_string(),
+    FPNote(DescriptionOfIntervention(Some(i))) | FPNote(ResponseToIntervention(Some(i))) => i.to_string(),
+    FPNote(c) => c.to_string(),
+  }
+}
+
+/// the user's active clients, most urgent first: never contacted, then most overdue, then soonest due;
+/// ties go to the client with more notes left unfinished
+pub fn caseload_rows(a: &NoteArchive, u: &User, today: NaiveDate, contact_due_days: u32) -> Vec<CaseloadRow> {
+  let mut rows: Vec<CaseloadRow> = u.foreign_keys["client_ids"].iter()
+    .filter_map(|c_id| a.get_client_by_id(*c_id) )
+    .filter(|c| c.is_active() )
+    .map(|c| {
+      let is_theirs = |fk: &HashMap<String, u32>| fk["user_id"] == u.id && fk["client_id"] == c.id;
+      let last_note_day = a.note_days.iter()
+        .filter(|nd| is_theirs(&nd.foreign_key) && nd.date <= today )
+        .map(|nd| nd.date )
+        .max();
+      let client_notes: Vec<&Note> = a.notes.iter().filter(|n| is_theirs(&n.foreign_key) ).collect();
+      let last_contact = client_notes.iter()
+        .filter(|n| n.date <= today )
+        .max_by_key(|n| (n.date, n.id) )
+        .map(|n| contact_type(&n.category) );
+      CaseloadRow {
+        client_id: c.id,
+        client_name: c.full_name(),
+        last_note_day,
+        last_contact,
+        notes_with_unfilled_blanks: client_notes.iter().filter(|n| n.has_unfilled_blanks() ).count(),
+        next_contact_due: last_note_day.map(|d| d + Duration::days(contact_due_days as i64) ),
+      }
+    })
+    .collect();
+  rows.sort_by(|x, y| {
+    x.next_contact_due.cmp(&y.next_contact_due)
+      .then_with(|| y.notes_with_unfilled_blanks.cmp(&x.notes_with_unfilled_blanks) )
+      .then_with(|| x.client_name.cmp(&y.client_name) )
+  });
+  rows
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::note_day::*;
+
+  #[test]
+  fn sorts_caseload_by_urgency() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_dashboard_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    let today = NaiveDate::from_ymd(2021, 3, 10);
+    let mut u = a.users[0].clone();
+    u.foreign_keys.insert(String::from("client_ids"), vec![1, 2, 99]);
+    a.note_days.clear();
+    a.notes.clear();
+    for (id, client_id, date) in [(1, 1, NaiveDate::from_ymd(2021, 3, 9)), (2, 2, NaiveDate::from_ymd(2021, 3, 1))] {
+      a.note_days.push(NoteDay::new(id, date, u.id, client_id, vec![]));
+      a.notes.push(Note::new(id, date, ICCNote(ICCNoteCategory::CareCoordination), StructureType::Referral, String::new(), u.id, client_id, vec![]));
+    }
+
+    let rows = caseload_rows(&a, &u, today, 7);
+    assert_eq!(rows.iter().map(|r| r.client_id ).collect::<Vec<u32>>(), vec![2, 1]);
+    assert!(rows[0].is_overdue(today));
+    assert_eq!(rows[0].fmt_due(today), String::from("2 days overdue"));
+    assert_eq!(rows[1].fmt_due(today), String::from("due in 6 days"));
+    assert_eq!(rows[1].fmt_days_since(today), String::from("1 day"));
+    assert_eq!(rows[1].last_contact, Some(String::from("Care coordination")));
+  }
+}
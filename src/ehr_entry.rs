@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::note::*;
+use crate::note_archive::*;
+use crate::note_day::*;
+use crate::export::*;
+use crate::utils::write_file_atomically;
+
+use NoteCategory::{ICCNote, FPNote};
+use ICCNoteCategory::{
+  FaceToFaceContactWithClient,
+  TelephoneContactWithClient,
+  CareCoordination,
+  Documentation,
+  CarePlanningTeam,
+  TransportClient,
+  MemberOutreachNoShow,
+};
+use FPNoteCategory::{
+  DescriptionOfIntervention,
+  ResponseToIntervention,
+  Functioning,
+  PlanAdditionalInformation,
+};
+
+/// the EHR text boxes in the order they appear on the form: one per ICC category,
+/// and one per FP section whatever the intervention
+pub fn ehr_sections() -> Vec<NoteCategory> {
+  let mut sections: Vec<NoteCategory> = ICCNoteCategory::iterator().map(ICCNote).collect();
+  sections.extend([
+    FPNote(Functioning),
+    FPNote(DescriptionOfIntervention(None)),
+    FPNote(ResponseToIntervention(None)),
+    FPNote(PlanAdditionalInformation),
+  ]);
+  sections
+}
+
+/// the text box a note goes in
+pub fn ehr_section(category: &NoteCategory) -> NoteCategory {
+  match category {
+    FPNote(DescriptionOfIntervention(_)) => FPNote(DescriptionOfIntervention(None)),
+    FPNote(ResponseToIntervention(_)) => FPNote(ResponseToIntervention(None)),
+    other => *other,
+  }
+}
+
+/// the name used for a section in the config file ('char_limit.<key>') and in file names
+pub fn ehr_section_key(section: &NoteCategory) -> &'static str {
+  match section {
+    ICCNote(FaceToFaceContactWithClient) => "FaceToFaceContactWithClient",
+    ICCNote(TelephoneContactWithClient) => "TelephoneContactWithClient",
+    ICCNote(CareCoordination) => "CareCoordination",
+    ICCNote(Documentation) => "Documentation",
+    ICCNote(CarePlanningTeam) => "CarePlanningTeam",
+    ICCNote(TransportClient) => "TransportClient",
+    ICCNote(MemberOutreachNoShow) => "MemberOutreachNoShow",
+    FPNote(Functioning) => "Functioning",
+    FPNote(DescriptionOfIntervention(_)) => "DescriptionOfIntervention",
+    FPNote(ResponseToIntervention(_)) => "ResponseToIntervention",
+    FPNote(PlanAdditionalInformation) => "PlanAdditionalInformation",
+  }
+}
+
+/// all of a note day's notes for one text box, joined into a single paragraph
+#[derive(Debug, Clone, PartialEq)]
+pub struct EhrParagraph {
+  pub section: NoteCategory,
+  pub text: String,
+  pub char_limit: Option<usize>,
+}
+
+impl EhrParagraph {
+  pub fn heading(&self) -> String {
+    match self.section {
+      ICCNote(c) => c.to_string(),
+      FPNote(c) => c.to_string(),
+    }
+  }
+  pub fn char_count(&self) -> usize {
+    self.text.chars().count()
+  }
+  /// how many characters the paragraph is over its limit, if it has one
+  pub fn chars_over_limit(&self) -> Option<usize> {
+    match self.char_limit {
+      Some(limit) if self.char_count() > limit => Some(self.char_count() - limit),
+      _ => None,
+    }
+  }
+  pub fn limit_warning(&self) -> Option<String> {
+    self.chars_over_limit().map(|over| format!(
+      "{}: {} characters, {} over the limit of {}.",
+      self.heading(),
+      self.char_count(),
+      over,
+      self.char_limit.unwrap(),
+    ))
+  }
+}
+
+/// collapses runs of whitespace and removes spaces before punctuation
+pub fn normalize_spacing(s: &str) -> String {
+  let collapsed = s.split_whitespace().collect::<Vec<&str>>().join(" ");
+  let mut output = String::with_capacity(collapsed.len());
+  for c in collapsed.chars() {
+    if matches!(c, '.' | ',' | ';' | ':' | '!' | '?') && output.ends_with(' ') {
+      output.pop();
+    }
+    output.push(c);
+  }
+  output
+}
+
+/// one paragraph per text box with notes, in form order; `char_limits` is keyed by ehr_section_key
+pub fn ehr_paragraphs(a: &NoteArchive, nd: &NoteDay, char_limits: &BTreeMap<String, usize>) -> Vec<EhrParagraph> {
+  let mut texts: BTreeMap<NoteCategory, Vec<String>> = BTreeMap::new();
+  for (category, notes) in a.get_note_day_notes_by_category(nd.clone()) {
+    for n in notes {
+      let (content, _) = n.generate_display_content_string_with_blanks(None, None, None, None, None);
+      let content = normalize_spacing(&content);
+      if !content.is_empty() {
+        texts.entry(ehr_section(&category)).or_default().push(content);
+      }
+    }
+  }
+  ehr_sections().into_iter()
+    .filter_map(|section| texts.remove(&section).map(|t| (section, t) ) )
+    .map(|(section, t)| EhrParagraph {
+      section,
+      text: t.join(" "),
+      char_limit: char_limits.get(ehr_section_key(&section)).copied(),
+    })
+    .collect()
+}
+
+/// writes each paragraph to its own numbered file, such as 'lee_ann_2021-03-04_01_CareCoordination.txt'
+pub fn write_ehr_files(paragraphs: &[EhrParagraph], dir: &Path, stem: &str) -> Result<Vec<PathBuf>, Error> {
+  std::fs::create_dir_all(dir)?;
+  let mut written: Vec<PathBuf> = vec![];
+  for (i, p) in paragraphs.iter().enumerate() {
+    let fp = dir.join(format!("{}_{:02}_{}.txt", stem, i + 1, ehr_section_key(&p.section)));
+    write_file_atomically(&fp, p.text.as_bytes())?;
+    written.push(fp);
+  }
+  Ok(written)
+}
+
+pub fn ehr_file_stem(a: &NoteArchive, nd: &NoteDay) -> String {
+  ExportDocument::new(a, &[nd]).file_stem()
+}
+
+/// runs the clipboard command (for example 'pbcopy' or 'xclip -selection clipboard') with the text on its input
+pub fn pipe_to_command(command: &str, text: &str) -> Result<(), Error> {
+  let mut words = command.split_whitespace();
+  let program = match words.next() {
+    Some(p) => p,
+    None => return Err(Error::new(ErrorKind::InvalidInput, "No clipboard command is set.")),
+  };
+  let mut child = Command::new(program)
+    .args(words)
+    .stdin(Stdio::piped())
+    .spawn()?;
+  if let Some(mut stdin) = child.stdin.take() {
+    stdin.write_all(text.as_bytes())?;
+  }
+  let status = child.wait()?;
+  if status.success() {
+    Ok(())
+  } else {
+    Err(Error::other(format!("'{}' exited with {}.", command, status)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn groups_fp_interventions_and_normalizes_spacing() {
+    assert_eq!(
+      ehr_section(&FPNote(DescriptionOfIntervention(Some(FPIntervention::CrisisSupport)))),
+      FPNote(DescriptionOfIntervention(None)),
+    );
+    assert_eq!(ehr_section_key(&FPNote(ResponseToIntervention(None))), "ResponseToIntervention");
+    assert_eq!(ehr_sections().len(), 11);
+    assert_eq!(normalize_spacing("  Met  with\nfamily , then left .  "), String::from("Met with family, then left."));
+
+    let p = EhrParagraph { section: ICCNote(CareCoordination), text: String::from("12345"), char_limit: Some(3) };
+    assert_eq!(p.chars_over_limit(), Some(2));
+    assert_eq!(p.limit_warning().unwrap(), String::from("Care coordination: 5 characters, 2 over the limit of 3."));
+    assert_eq!(EhrParagraph { char_limit: None, ..p }.limit_warning(), None);
+  }
+}
@@ -0,0 +1,310 @@
+use chrono::NaiveDate;
+use std::fmt;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+
+use crate::note_archive::*;
+use crate::note_day::*;
+use crate::utils::write_file_atomically;
+
+/// plain text exports are wrapped to this many columns
+pub const EXPORT_WRAP_WIDTH: usize = 80;
+pub const EXPORT_DIR: &str = "exports";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+  Markdown,
+  Html,
+  PlainText,
+}
+
+use ExportFormat::{Markdown, Html, PlainText};
+
+impl ExportFormat {
+  pub fn all() -> Vec<ExportFormat> {
+    vec![Markdown, Html, PlainText]
+  }
+  pub fn extension(&self) -> &'static str {
+    match self {
+      Markdown => "md",
+      Html => "html",
+      PlainText => "txt",
+    }
+  }
+  /// 'md', 'html' or 'txt', or 'all' for every format
+  pub fn parse_list(s: &str) -> Result<Vec<ExportFormat>, String> {
+    match &s.to_ascii_lowercase()[..] {
+      "md" | "markdown" => Ok(vec![Markdown]),
+      "html" => Ok(vec![Html]),
+      "txt" | "text" => Ok(vec![PlainText]),
+      "all" => Ok(Self::all()),
+      _ => Err(format!("Unknown export format '{}' (expected md, html, txt or all).", s)),
+    }
+  }
+}
+
+impl fmt::Display for ExportFormat {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let s = match self {
+      Markdown => "Markdown",
+      Html => "HTML",
+      PlainText => "plain text",
+    };
+    write!(f, "{}", s)
+  }
+}
+
+/// everything written out for one note day, with blanks already filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteDayExport {
+  pub date: NaiveDate,
+  pub heading_date: String,
+  pub client_name: String,
+  pub user_name_and_title: String,
+  pub categories: Vec<(String, Vec<String>)>,
+}
+
+impl NoteDayExport {
+  pub fn new(a: &NoteArchive, nd: &NoteDay) -> NoteDayExport {
+    let client_name = match a.get_client_by_id(nd.foreign_key["client_id"]) {
+      Some(c) => c.full_name(),
+      None => String::new(),
+    };
+    let user_name_and_title = match a.users.iter().find(|u| u.id == nd.foreign_key["user_id"] ) {
+      Some(u) => u.name_and_title(),
+      None => String::new(),
+    };
+    let mut categories: Vec<(String, Vec<String>)> = vec![];
+    for (k, v) in &a.get_note_day_notes_by_category(nd.clone()) {
+      if !v.is_empty() {
+        let notes = v.iter()
+          .map(|n| n.generate_display_content_string_with_blanks(None, None, None, None, None).0 )
+          .collect();
+        categories.push((k.to_string(), notes));
+      }
+    }
+    NoteDayExport {
+      date: nd.date,
+      heading_date: nd.heading_date(),
+      client_name,
+      user_name_and_title,
+      categories,
+    }
+  }
+  fn title(&self) -> String {
+    format!("{} notes for {}", self.heading_date, self.client_name)
+  }
+}
+
+/// one or more note days for a single client, in date order
+pub struct ExportDocument {
+  pub days: Vec<NoteDayExport>,
+}
+
+impl ExportDocument {
+  pub fn new(a: &NoteArchive, note_days: &[&NoteDay]) -> ExportDocument {
+    let mut days: Vec<NoteDayExport> = note_days.iter().map(|nd| NoteDayExport::new(a, nd) ).collect();
+    days.sort_by_key(|d| d.date );
+    ExportDocument { days }
+  }
+  pub fn title(&self) -> String {
+    match (self.days.first(), self.days.last()) {
+      (Some(first), Some(last)) if first.date != last.date => format!(
+        "Notes for {}, {} to {}",
+        first.client_name,
+        first.date.format("%Y-%m-%d"),
+        last.date.format("%Y-%m-%d"),
+      ),
+      (Some(day), _) => day.title(),
+      _ => String::from("Notes"),
+    }
+  }
+  /// a file name without extension, such as 'lee_ann_2021-03-04' or 'lee_ann_2021-03-01_to_2021-03-31'
+  pub fn file_stem(&self) -> String {
+    let (first, last) = match (self.days.first(), self.days.last()) {
+      (Some(first), Some(last)) => (first, last),
+      _ => return String::from("notes"),
+    };
+    let name = client_file_name(&first.client_name);
+    if first.date == last.date {
+      format!("{}_{}", name, first.date.format("%Y-%m-%d"))
+    } else {
+      format!("{}_{}_to_{}", name, first.date.format("%Y-%m-%d"), last.date.format("%Y-%m-%d"))
+    }
+  }
+  pub fn render(&self, format: ExportFormat) -> String {
+    match format {
+      Markdown => self.to_markdown(),
+      Html => self.to_html(),
+      PlainText => self.to_plain_text(EXPORT_WRAP_WIDTH),
+    }
+  }
+  pub fn to_markdown(&self) -> String {
+    let mut output = String::new();
+    for day in &self.days {
+      output.push_str(&format!("# {}\n\n", day.title()));
+      output.push_str(&format!("**Client:** {}  \n", day.client_name));
+      output.push_str(&format!("**Written by:** {}\n\n", day.user_name_and_title));
+      for (category, notes) in &day.categories {
+        output.push_str(&format!("## {}\n\n", category));
+        for n in notes {
+          output.push_str(&format!("{}\n\n", n));
+        }
+      }
+    }
+    output
+  }
+  /// a standalone page with its own styles, so it can be opened or attached without other files
+  pub fn to_html(&self) -> String {
+    let mut output = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>{}</title>\n", escape_html(&self.title())));
+    output.push_str("<style>\nbody { font-family: sans-serif; max-width: 50em; margin: 2em auto; line-height: 1.5; }\n");
+    output.push_str("h2 { border-bottom: 1px solid #999; }\n</style>\n</head>\n<body>\n");
+    for day in &self.days {
+      output.push_str("<section>\n");
+      output.push_str(&format!("<h1>{}</h1>\n", escape_html(&day.title())));
+      output.push_str(&format!("<p><strong>Client:</strong> {}<br>\n", escape_html(&day.client_name)));
+      output.push_str(&format!("<strong>Written by:</strong> {}</p>\n", escape_html(&day.user_name_and_title)));
+      for (category, notes) in &day.categories {
+        output.push_str(&format!("<h2>{}</h2>\n", escape_html(category)));
+        for n in notes {
+          output.push_str(&format!("<p>{}</p>\n", escape_html(n)));
+        }
+      }
+      output.push_str("</section>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+  }
+  pub fn to_plain_text(&self, width: usize) -> String {
+    let mut output = String::new();
+    for day in &self.days {
+      let title = day.title();
+      output.push_str(&format!("{}\n{}\n", title, "=".repeat(title.chars().count())));
+      output.push_str(&format!("Client: {}\n", day.client_name));
+      output.push_str(&format!("Written by: {}\n\n", day.user_name_and_title));
+      for (category, notes) in &day.categories {
+        output.push_str(&format!("{}\n{}\n", category, "-".repeat(category.chars().count())));
+        for n in notes {
+          output.push_str(&format!("{}\n\n", wrap_text(n, width)));
+        }
+      }
+    }
+    output
+  }
+  /// writes one file per format into `dir`, returning the paths written
+  pub fn write_files(&self, dir: &Path, formats: &[ExportFormat]) -> Result<Vec<PathBuf>, Error> {
+    std::fs::create_dir_all(dir)?;
+    let mut written: Vec<PathBuf> = vec![];
+    for format in formats {
+      let fp = dir.join(format!("{}.{}", self.file_stem(), format.extension()));
+      write_file_atomically(&fp, self.render(*format).as_bytes())?;
+      written.push(fp);
+    }
+    Ok(written)
+  }
+}
+
+pub fn escape_html(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      '\n' => escaped.push_str("<br>\n"),
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// wraps each paragraph at word boundaries; words longer than the width are left whole
+pub fn wrap_text(s: &str, width: usize) -> String {
+  let mut lines: Vec<String> = vec![];
+  for paragraph in s.lines() {
+    let mut line = String::new();
+    for word in paragraph.split_whitespace() {
+      if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+        lines.push(line);
+        line = String::new();
+      }
+      if !line.is_empty() {
+        line.push(' ');
+      }
+      line.push_str(word);
+    }
+    lines.push(line);
+  }
+  lines.join("\n")
+}
+
+/// a client's name as used in file names, last name first: 'Ann O'Lee' becomes 'olee_ann'
+pub fn client_file_name(client_name: &str) -> String {
+  client_name
+    .split_whitespace()
+    .rev()
+    .map(|w| w.chars().filter(|c| c.is_alphanumeric() ).collect::<String>().to_lowercase() )
+    .filter(|w| !w.is_empty() )
+    .collect::<Vec<String>>()
+    .join("_")
+}
+
+/// the exports folder next to the archive
+pub fn export_dir(archive_filepath: &str) -> PathBuf {
+  match Path::new(archive_filepath).parent() {
+    Some(dir) => dir.join(EXPORT_DIR),
+    None => PathBuf::from(EXPORT_DIR),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_document() -> ExportDocument {
+    ExportDocument {
+      days: vec![
+        NoteDayExport {
+          date: NaiveDate::from_ymd(2021, 3, 4),
+          heading_date: String::from("Thursday 3/4"),
+          client_name: String::from("Ann O'Lee"),
+          user_name_and_title: String::from("Bob Smith, ICC"),
+          categories: vec![(
+            String::from("ICC Note - Care Coordination"),
+            vec![String::from("ICC met with <Ann> & family to review the plan and discuss next steps for school.")],
+          )],
+        },
+      ],
+    }
+  }
+
+  #[test]
+  fn renders_each_format() {
+    let doc = test_document();
+    assert_eq!(doc.file_stem(), String::from("olee_ann_2021-03-04"));
+
+    let md = doc.to_markdown();
+    assert!(md.starts_with("# Thursday 3/4 notes for Ann O'Lee\n"));
+    assert!(md.contains("**Written by:** Bob Smith, ICC"));
+    assert!(md.contains("## ICC Note - Care Coordination\n"));
+
+    let html = doc.to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Thursday 3/4 notes for Ann O&#39;Lee</title>"));
+    assert!(html.contains("with &lt;Ann&gt; &amp; family"));
+
+    let text = doc.to_plain_text(40);
+    assert!(text.lines().all(|l| l.chars().count() <= 40 ));
+    assert!(text.contains("ICC met with <Ann> & family to review\nthe plan"));
+  }
+
+  #[test]
+  fn wraps_at_word_boundaries() {
+    assert_eq!(wrap_text("one two three four", 9), String::from("one two\nthree\nfour"));
+    assert_eq!(wrap_text("a\n\nb", 9), String::from("a\n\nb"));
+    assert_eq!(wrap_text("unbreakable word", 5), String::from("unbreakable\nword"));
+  }
+}
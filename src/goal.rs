@@ -1,10 +1,16 @@
 use std::fmt;
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Goal {
   pub id: u32,
   pub client_id: u32,
   pub goal: String,
+  pub status: GoalStatus,
+  pub objectives: Vec<Objective>,
+  /// ratings of the client's progress, oldest first
+  pub progress: Vec<ProgressEntry>,
 }
 
 impl PartialEq for Goal {
@@ -24,6 +30,36 @@ impl Goal {
       id,
       client_id,
       goal,
+      status: GoalStatus::Active,
+      objectives: vec![],
+      progress: vec![],
+    }
+  }
+  pub fn is_active(&self) -> bool {
+    self.status == GoalStatus::Active
+  }
+  pub fn latest_progress(&self) -> Option<&ProgressEntry> {
+    self.progress.last()
+  }
+  /// keeps entries in date order; an entry dated the same day as an earlier one goes after it
+  pub fn add_progress(&mut self, entry: ProgressEntry) {
+    let pos = self.progress.partition_point(|p| p.date <= entry.date );
+    self.progress.insert(pos, entry);
+  }
+  /// the text a ClientGoal blank is filled with; falls back to the goal itself when there are no objectives or ratings
+  pub fn render(&self, rendering: GoalRendering) -> String {
+    match (rendering, self.latest_progress()) {
+      (GoalRendering::Objectives, _) if !self.objectives.is_empty() => {
+        self.objectives.iter().map(|o| o.to_string() ).collect::<Vec<String>>().join("; ")
+      },
+      (GoalRendering::LatestProgress, Some(p)) => {
+        let mut s = format!("{}: {} as of {}", self.goal, p.rating, p.date.format("%-m/%-d/%Y"));
+        if !p.comment.is_empty() {
+          s.push_str(&format!(" ({})", p.comment));
+        }
+        s
+      },
+      _ => self.goal.clone(),
     }
   }
 }
@@ -38,4 +74,172 @@ impl fmt::Display for Goal {
       &self.goal,
     )
   }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GoalStatus {
+  Active,
+  Met,
+  Discontinued,
+}
+
+impl GoalStatus {
+  pub fn listed() -> Vec<GoalStatus> {
+    vec![GoalStatus::Active, GoalStatus::Met, GoalStatus::Discontinued]
+  }
+  pub fn parse(s: &str) -> Result<GoalStatus, String> {
+    GoalStatus::listed().into_iter()
+      .find(|status| status.to_string().eq_ignore_ascii_case(s.trim()) )
+      .ok_or(format!("Goal status must be active, met or discontinued, not '{}'.", s.trim()))
+  }
+}
+
+impl fmt::Display for GoalStatus {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      GoalStatus::Active => write!(f, "active"),
+      GoalStatus::Met => write!(f, "met"),
+      GoalStatus::Discontinued => write!(f, "discontinued"),
+    }
+  }
+}
+
+/// a measurable step toward a goal, such as 'attend 4 of 5 school days per week'
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Objective {
+  pub description: String,
+  /// where the client started, such as '1 of 5 days'
+  pub baseline: Option<String>,
+  pub target_date: Option<NaiveDate>,
+}
+
+impl Objective {
+  pub fn new(description: String, baseline: Option<String>, target_date: Option<NaiveDate>) -> Objective {
+    Objective { description, baseline, target_date }
+  }
+}
+
+impl fmt::Display for Objective {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.description)?;
+    if let Some(d) = self.target_date {
+      write!(f, " by {}", d.format("%-m/%-d/%Y"))?;
+    }
+    if let Some(b) = &self.baseline {
+      write!(f, " (baseline: {})", b)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ProgressRating {
+  Regressed,
+  NoProgress,
+  SomeProgress,
+  SignificantProgress,
+  Achieved,
+}
+
+impl ProgressRating {
+  /// every rating from worst to best, in menu order
+  pub fn listed() -> Vec<ProgressRating> {
+    use ProgressRating::*;
+    vec![Regressed, NoProgress, SomeProgress, SignificantProgress, Achieved]
+  }
+  /// a rating by its number in the menu or by name
+  pub fn parse(s: &str) -> Result<ProgressRating, String> {
+    let s = s.trim();
+    let listed = ProgressRating::listed();
+    if let Some(r) = s.parse::<usize>().ok().and_then(|i| i.checked_sub(1) ).and_then(|i| listed.get(i) ) {
+      return Ok(*r);
+    }
+    let squashed: String = s.chars().filter(|c| c.is_alphanumeric() ).collect();
+    listed.into_iter()
+      .find(|r| format!("{:?}", r).eq_ignore_ascii_case(&squashed) || r.to_string().eq_ignore_ascii_case(s) )
+      .ok_or(format!("Unknown progress rating '{}'.", s))
+  }
+}
+
+impl fmt::Display for ProgressRating {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use ProgressRating::*;
+    match self {
+      Regressed => write!(f, "regressed"),
+      NoProgress => write!(f, "no progress"),
+      SomeProgress => write!(f, "some progress"),
+      SignificantProgress => write!(f, "significant progress"),
+      Achieved => write!(f, "achieved"),
+    }
+  }
+}
+
+/// a dated rating of progress toward a goal, usually entered on the note day it was reviewed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressEntry {
+  pub date: NaiveDate,
+  pub rating: ProgressRating,
+  pub comment: String,
+  pub note_day_id: Option<u32>,
+}
+
+impl ProgressEntry {
+  pub fn new(date: NaiveDate, rating: ProgressRating, comment: String, note_day_id: Option<u32>) -> ProgressEntry {
+    ProgressEntry { date, rating, comment, note_day_id }
+  }
+}
+
+/// what a ClientGoal blank shows for each chosen goal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalRendering {
+  Goal,
+  Objectives,
+  LatestProgress,
+}
+
+impl GoalRendering {
+  pub fn listed() -> Vec<GoalRendering> {
+    vec![GoalRendering::Goal, GoalRendering::Objectives, GoalRendering::LatestProgress]
+  }
+}
+
+impl fmt::Display for GoalRendering {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      GoalRendering::Goal => write!(f, "the goal"),
+      GoalRendering::Objectives => write!(f, "its objectives"),
+      GoalRendering::LatestProgress => write!(f, "its latest progress rating"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_objectives_and_progress() {
+    let d = |m, day| NaiveDate::from_ymd(2026, m, day);
+    let mut g = Goal::new(1, 1, String::from("attend school regularly"));
+    assert_eq!(g.render(GoalRendering::Objectives), String::from("attend school regularly"));
+    assert_eq!(g.render(GoalRendering::LatestProgress), String::from("attend school regularly"));
+
+    g.objectives.push(Objective::new(String::from("attend 4 of 5 school days per week"), Some(String::from("2 of 5 days")), Some(d(12, 1))));
+    g.objectives.push(Objective::new(String::from("meet with the school counselor weekly"), None, None));
+    assert_eq!(
+      g.render(GoalRendering::Objectives),
+      String::from("attend 4 of 5 school days per week by 12/1/2026 (baseline: 2 of 5 days); meet with the school counselor weekly"),
+    );
+
+    g.add_progress(ProgressEntry::new(d(10, 1), ProgressRating::SomeProgress, String::from("3 of 5 days"), Some(4)));
+    g.add_progress(ProgressEntry::new(d(9, 1), ProgressRating::NoProgress, String::new(), None));
+    assert_eq!(g.progress[0].date, d(9, 1));
+    assert_eq!(g.render(GoalRendering::LatestProgress), String::from("attend school regularly: some progress as of 10/1/2026 (3 of 5 days)"));
+
+    assert_eq!(ProgressRating::parse("4"), Ok(ProgressRating::SignificantProgress));
+    assert_eq!(ProgressRating::parse("no progress"), Ok(ProgressRating::NoProgress));
+    assert!(ProgressRating::parse("great").is_err());
+    assert_eq!(GoalStatus::parse("Met"), Ok(GoalStatus::Met));
+    assert!(GoalStatus::parse("paused").is_err());
+  }
+}
@@ -0,0 +1,185 @@
+use chrono::NaiveDate;
+
+use crate::billing::csv_field;
+use crate::goal::*;
+use crate::note::Blank;
+use crate::note_archive::*;
+
+/// a note day on which a goal was rated or written about, or a rating entered without a note day
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalEvent {
+  pub date: NaiveDate,
+  pub note_day_id: Option<u32>,
+  pub rating: Option<ProgressRating>,
+  pub comment: String,
+  /// how many notes that day filled a ClientGoal blank with the goal
+  pub notes: usize,
+}
+
+impl GoalEvent {
+  pub fn description(&self) -> String {
+    let mut parts: Vec<String> = vec![];
+    if let Some(r) = self.rating {
+      parts.push(r.to_string());
+    }
+    if !self.comment.is_empty() {
+      parts.push(self.comment.clone());
+    }
+    if self.notes > 0 {
+      parts.push(format!("addressed in {} note{}", self.notes, if self.notes == 1 { "" } else { "s" }));
+    }
+    parts.join("; ")
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalReportLine {
+  pub goal_id: u32,
+  pub goal: String,
+  pub status: GoalStatus,
+  pub objectives: Vec<Objective>,
+  pub events: Vec<GoalEvent>,
+}
+
+/// each of a client's goals with its objectives, ratings and the note days it came up, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgressReport {
+  pub client_name: String,
+  pub first: Option<NaiveDate>,
+  pub last: Option<NaiveDate>,
+  pub lines: Vec<GoalReportLine>,
+}
+
+impl GoalProgressReport {
+  pub fn new(a: &NoteArchive, client_id: u32, first: Option<NaiveDate>, last: Option<NaiveDate>) -> GoalProgressReport {
+    let in_range = |d: NaiveDate| first.is_none_or(|f| d >= f ) && last.is_none_or(|l| d <= l );
+    let note_days: Vec<_> = a.note_days.iter()
+      .filter(|nd| nd.foreign_key["client_id"] == client_id && in_range(nd.date) )
+      .collect();
+    let lines = a.goals.iter().filter(|g| g.client_id == client_id ).map(|g| {
+      let mut events: Vec<GoalEvent> = g.progress.iter()
+        .filter(|p| in_range(p.date) )
+        .map(|p| GoalEvent { date: p.date, note_day_id: p.note_day_id, rating: Some(p.rating), comment: p.comment.clone(), notes: 0 } )
+        .collect();
+      for nd in &note_days {
+        let notes = a.notes.iter()
+          .filter(|n| nd.foreign_keys["note_ids"].contains(&n.id) )
+          .filter(|n| n.blanks.values().any(|(b, _, ids)| *b == Blank::ClientGoal && ids.contains(&g.id) ) )
+          .count();
+        if notes == 0 {
+          continue;
+        }
+        match events.iter_mut().find(|e| e.note_day_id == Some(nd.id) ) {
+          Some(e) => e.notes += notes,
+          None => events.push(GoalEvent { date: nd.date, note_day_id: Some(nd.id), rating: None, comment: String::new(), notes }),
+        }
+      }
+      events.sort_by_key(|e| e.date );
+      GoalReportLine { goal_id: g.id, goal: g.goal.clone(), status: g.status, objectives: g.objectives.clone(), events }
+    }).collect();
+    let client_name = match a.get_client_by_id(client_id) {
+      Some(c) => c.full_name(),
+      None => format!("Client {}", client_id),
+    };
+    GoalProgressReport { client_name, first, last, lines }
+  }
+  pub fn to_display_string(&self) -> String {
+    let mut output = format!("Goal progress for {}", self.client_name);
+    match (self.first, self.last) {
+      (Some(f), Some(l)) => output.push_str(&format!(" from {} to {}", f.format("%Y-%m-%d"), l.format("%Y-%m-%d"))),
+      (Some(f), None) => output.push_str(&format!(" since {}", f.format("%Y-%m-%d"))),
+      (None, Some(l)) => output.push_str(&format!(" until {}", l.format("%Y-%m-%d"))),
+      (None, None) => (),
+    }
+    output.push_str("\n\n");
+    if self.lines.is_empty() {
+      output.push_str("No goals entered.\n");
+    }
+    for line in &self.lines {
+      output.push_str(&format!("Goal {}: {} ({})\n", line.goal_id, line.goal, line.status));
+      for o in &line.objectives {
+        output.push_str(&format!("  Objective: {}\n", o));
+      }
+      if line.events.is_empty() {
+        output.push_str("  No ratings or notes in this period.\n");
+      }
+      for e in &line.events {
+        output.push_str(&format!("  {}  {}\n", e.date.format("%Y-%m-%d"), e.description()));
+      }
+      output.push('\n');
+    }
+    output
+  }
+  /// one row per goal event, with goals that have none listed once with the event columns empty
+  pub fn to_csv(&self) -> String {
+    let mut output = String::from("goal_id,goal,status,date,note_day_id,rating,comment,notes\n");
+    for line in &self.lines {
+      let mut rows: Vec<[String; 5]> = line.events.iter().map(|e| [
+        e.date.format("%Y-%m-%d").to_string(),
+        e.note_day_id.map(|id| id.to_string() ).unwrap_or_default(),
+        e.rating.map(|r| r.to_string() ).unwrap_or_default(),
+        e.comment.clone(),
+        e.notes.to_string(),
+      ]).collect();
+      if rows.is_empty() {
+        rows.push(Default::default());
+      }
+      for row in rows {
+        let mut fields = vec![line.goal_id.to_string(), line.goal.clone(), line.status.to_string()];
+        fields.extend(row);
+        output.push_str(&fields.iter().map(|f| csv_field(f) ).collect::<Vec<String>>().join(","));
+        output.push('\n');
+      }
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+  use crate::note::*;
+  use crate::note_day::NoteDay;
+
+  #[test]
+  fn reports_ratings_and_notes_by_date() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_goal_report_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    let d = |m, day| NaiveDate::from_ymd(2026, m, day);
+    a.goals.clear();
+    a.note_days.clear();
+    a.notes.clear();
+    let mut g = Goal::new(1, 1, String::from("attend school regularly"));
+    g.objectives.push(Objective::new(String::from("attend 4 of 5 school days per week"), None, Some(d(12, 1))));
+    g.add_progress(ProgressEntry::new(d(9, 8), ProgressRating::SomeProgress, String::from("3 of 5 days"), Some(2)));
+    g.add_progress(ProgressEntry::new(d(8, 1), ProgressRating::NoProgress, String::new(), None));
+    a.goals.push(g);
+    a.goals.push(Goal::new(2, 1, String::from("improve sleep")));
+    a.note_days.push(NoteDay::new(1, d(9, 1), 1, 1, vec![1, 2]));
+    a.note_days.push(NoteDay::new(2, d(9, 8), 1, 1, vec![3]));
+    let category = NoteCategory::ICCNote(ICCNoteCategory::CareCoordination);
+    for (id, date) in [(1, d(9, 1)), (2, d(9, 1)), (3, d(9, 8))] {
+      let mut n = Note::new(id, date, category, StructureType::Sncd, String::from("Discussed (---go---)."), 1, 1, vec![]);
+      n.blanks.insert(0, (Blank::ClientGoal, String::from("attend school regularly"), vec![1]));
+      a.notes.push(n);
+    }
+
+    let report = GoalProgressReport::new(&a, 1, Some(d(9, 1)), None);
+    assert_eq!(report.lines[0].events.len(), 2);
+    assert_eq!(report.lines[0].events[0].description(), String::from("addressed in 2 notes"));
+    assert_eq!(report.lines[0].events[1].description(), String::from("some progress; 3 of 5 days; addressed in 1 note"));
+    let shown = report.to_display_string();
+    assert!(shown.starts_with("Goal progress for Pete McLastName since 2026-09-01"));
+    assert!(shown.contains("  Objective: attend 4 of 5 school days per week by 12/1/2026\n"));
+    assert!(shown.contains("Goal 2: improve sleep (active)\n  No ratings or notes in this period."));
+    let csv = report.to_csv();
+    assert_eq!(csv.lines().count(), 4);
+    assert!(csv.contains("1,attend school regularly,active,2026-09-08,2,some progress,3 of 5 days,1\n"));
+    assert!(csv.ends_with("2,improve sleep,active,,,,,\n"));
+    assert_eq!(GoalProgressReport::new(&a, 1, None, None).lines[0].events.len(), 3);
+  }
+}
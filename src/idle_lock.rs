@@ -0,0 +1,76 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Mutex, MutexGuard, Once};
+use std::{process, thread, time};
+use std::time::{Duration, Instant};
+
+use crate::note_archive::*;
+
+pub const DEFAULT_IDLE_LOCK_MINUTES: u64 = 15;
+
+/// what the watchdog needs to re-encrypt the archive when the session has been idle too long
+pub struct IdleLockSettings {
+  pub limit: Duration,
+  pub filepaths: HashMap<String, String>,
+  pub password: String,
+}
+
+lazy_static! {
+  static ref LAST_INPUT: Mutex<Instant> = Mutex::new(Instant::now());
+  static ref IDLE_LOCK: Mutex<Option<IdleLockSettings>> = Mutex::new(None);
+  static ref FILE_WRITE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+static START_WATCHDOG: Once = Once::new();
+
+/// reads a line from stdin and records the time, so every menu counts as activity
+pub fn read_input_line(buf: &mut String) -> io::Result<usize> {
+  let result = io::stdin().read_line(buf);
+  *LAST_INPUT.lock().unwrap() = Instant::now();
+  result
+}
+
+/// held while data files are written so the watchdog never encrypts a half-written file
+pub fn lock_file_writes() -> MutexGuard<'static, ()> {
+  match FILE_WRITE_LOCK.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+/// replaces the idle lock settings; None turns the idle lock off
+pub fn set_idle_lock(settings: Option<IdleLockSettings>) {
+  *LAST_INPUT.lock().unwrap() = Instant::now();
+  *IDLE_LOCK.lock().unwrap() = settings;
+  START_WATCHDOG.call_once(|| {
+    thread::spawn(idle_watchdog);
+  });
+}
+
+fn idle_watchdog() {
+  loop {
+    thread::sleep(time::Duration::from_secs(5));
+    let settings = IDLE_LOCK.lock().unwrap();
+    let s = match settings.as_ref() {
+      Some(s) => s,
+      None => continue,
+    };
+    if LAST_INPUT.lock().unwrap().elapsed() < s.limit {
+      continue;
+    }
+    // keep the write lock until the process exits so nothing is written after the files are encrypted
+    let _write_guard = lock_file_writes();
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    match NoteArchive::encrypt_files_at(&s.filepaths, &s.password) {
+      Ok(_) => {
+        println!("Locked after {} minutes without input. Run the program again to unlock.", s.limit.as_secs() / 60);
+        process::exit(0);
+      },
+      Err(e) => {
+        println!("Failed to lock files after inactivity: {}", e);
+        process::exit(1);
+      },
+    }
+  }
+}
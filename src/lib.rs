@@ -63,4 +63,66 @@ pub mod constants;
 pub use constants::*;
 
 pub mod blank_enums;
-pub use blank_enums::*;
\ No newline at end of file
+pub use blank_enums::*;
+
+pub mod cli;
+pub use cli::*;
+
+pub mod config;
+pub use config::*;
+pub mod idle_lock;
+pub use idle_lock::*;
+
+pub mod archive;
+pub use archive::*;
+
+pub mod migrations;
+pub use migrations::*;
+
+pub mod load_errors;
+pub use load_errors::*;
+
+pub mod backups;
+pub use backups::*;
+
+pub mod export;
+pub use export::*;
+
+pub mod ehr_entry;
+pub use ehr_entry::*;
+
+pub mod pdf;
+pub use pdf::*;
+
+pub mod billing;
+pub use billing::*;
+
+pub mod travel;
+pub use travel::*;
+
+pub mod dashboard;
+pub use dashboard::*;
+
+pub mod compliance;
+pub use compliance::*;
+
+pub mod search;
+pub use search::*;
+
+pub mod note_filter;
+pub use note_filter::*;
+
+pub mod vocabulary;
+pub use vocabulary::*;
+
+pub mod template_syntax;
+pub use template_syntax::*;
+
+pub mod template_lint;
+pub use template_lint::*;
+
+pub mod template_library;
+pub use template_library::*;
+
+pub mod goal_report;
+pub use goal_report::*;
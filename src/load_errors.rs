@@ -0,0 +1,185 @@
+use chrono::NaiveDate;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{Error, Write};
+
+/// which list in the archive a record belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+  UserRecord,
+  ClientRecord,
+  GoalRecord,
+  CollateralRecord,
+  GeneralCollateralRecord,
+  PronounsRecord,
+  NoteDayRecord,
+  NoteTemplateRecord,
+  NoteRecord,
+}
+
+use RecordKind::{
+  UserRecord,
+  ClientRecord,
+  GoalRecord,
+  CollateralRecord,
+  GeneralCollateralRecord,
+  PronounsRecord,
+  NoteDayRecord,
+  NoteTemplateRecord,
+  NoteRecord,
+};
+
+impl fmt::Display for RecordKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let s = match self {
+      UserRecord => "user",
+      ClientRecord => "client",
+      GoalRecord => "goal",
+      CollateralRecord => "collateral",
+      GeneralCollateralRecord => "general collateral",
+      PronounsRecord => "pronouns",
+      NoteDayRecord => "note day",
+      NoteTemplateRecord => "note template",
+      NoteRecord => "note",
+    };
+    write!(f, "{}", s)
+  }
+}
+
+/// whether a record is a ' | '-separated line from an old text file or an entry in the archive file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+  TextLine,
+  JsonRecord,
+}
+
+/// a record that could not be read, kept so the user can decide what to do with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+  pub file: String,
+  pub line: usize,
+  pub kind: RecordKind,
+  pub format: RecordFormat,
+  pub field: String,
+  pub reason: String,
+  pub raw: String,
+}
+
+impl LoadError {
+  pub fn new(file: &str, line: usize, kind: RecordKind, format: RecordFormat, error: FieldError, raw: String) -> LoadError {
+    LoadError {
+      file: file.to_string(),
+      line,
+      kind,
+      format,
+      field: error.field,
+      reason: error.reason,
+      raw,
+    }
+  }
+  pub fn location(&self) -> String {
+    match self.format {
+      RecordFormat::TextLine => format!("{}, line {}", self.file, self.line),
+      RecordFormat::JsonRecord => format!("{}, {} record {}", self.file, self.kind, self.line),
+    }
+  }
+  /// appends the unreadable record to '<file>.quarantine' so it is kept out of the archive but not lost
+  pub fn quarantine(&self) -> Result<String, Error> {
+    let quarantine_filepath = format!("{}.quarantine", self.file);
+    let mut file = OpenOptions::new().create(true).append(true).open(&quarantine_filepath)?;
+    writeln!(file, "{} | {} | {}", self.kind, self.line, self.raw)?;
+    Ok(quarantine_filepath)
+  }
+}
+
+impl fmt::Display for LoadError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: field '{}': {}", self.location(), self.field, self.reason)
+  }
+}
+
+/// the field that stopped a single record from being read
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+  pub field: String,
+  pub reason: String,
+}
+
+impl FieldError {
+  pub fn new(field: &str, reason: String) -> FieldError {
+    FieldError { field: field.to_string(), reason }
+  }
+}
+
+/// the values of one ' | '-separated line, with checked access by position
+pub struct LineFields<'a> {
+  values: Vec<&'a str>,
+}
+
+impl<'a> LineFields<'a> {
+  pub fn new(line: &'a str) -> LineFields<'a> {
+    LineFields { values: line.split(" | ").collect() }
+  }
+  pub fn len(&self) -> usize {
+    self.values.len()
+  }
+  pub fn is_empty(&self) -> bool {
+    self.values.is_empty()
+  }
+  pub fn text(&self, i: usize, field: &str) -> Result<&'a str, FieldError> {
+    match self.values.get(i) {
+      Some(v) => Ok(v),
+      None => Err(FieldError::new(field, format!("missing (line has {} values)", self.values.len()))),
+    }
+  }
+  pub fn id(&self, i: usize, field: &str) -> Result<u32, FieldError> {
+    let v = self.text(i, field)?;
+    v.trim().parse().map_err(|_| FieldError::new(field, format!("'{}' is not a valid ID", v)) )
+  }
+  /// '#'-separated IDs, where an empty value is an empty list
+  pub fn ids(&self, i: usize, field: &str) -> Result<Vec<u32>, FieldError> {
+    let v = self.text(i, field)?;
+    if v.is_empty() {
+      return Ok(vec![]);
+    }
+    v.split('#')
+      .map(|id| id.trim().parse().map_err(|_| FieldError::new(field, format!("'{}' is not a valid ID", id)) ) )
+      .collect()
+  }
+  /// a date saved as year-month-day
+  pub fn date(&self, i: usize, field: &str) -> Result<NaiveDate, FieldError> {
+    let v = self.text(i, field)?;
+    let parts: Vec<&str> = v.split('-').collect();
+    let invalid = || FieldError::new(field, format!("'{}' is not a valid date", v));
+    if parts.len() != 3 {
+      return Err(invalid());
+    }
+    let year: i32 = parts[0].parse().map_err(|_| invalid() )?;
+    let month: u32 = parts[1].parse().map_err(|_| invalid() )?;
+    let day: u32 = parts[2].parse().map_err(|_| invalid() )?;
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid)
+  }
+  pub fn boolean(&self, i: usize, field: &str) -> Result<bool, FieldError> {
+    match self.text(i, field)? {
+      "true" => Ok(true),
+      "false" => Ok(false),
+      v => Err(FieldError::new(field, format!("'{}' is not 'true' or 'false'", v))),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reports_the_failing_field() {
+    let fields = LineFields::new("4 | 2021-2-30 | x#2 | maybe");
+    assert_eq!(fields.id(0, "id"), Ok(4));
+    assert_eq!(fields.date(1, "date").unwrap_err().field, String::from("date"));
+    assert_eq!(fields.ids(2, "client_ids").unwrap_err().reason, String::from("'x' is not a valid ID"));
+    assert!(fields.boolean(3, "guardian").is_err());
+    assert_eq!(fields.text(7, "title").unwrap_err().reason, String::from("missing (line has 4 values)"));
+    assert_eq!(LineFields::new(" | ").ids(0, "ids"), Ok(vec![]));
+  }
+}
@@ -11,30 +11,115 @@ pub mod note;
 pub mod utils;
 pub mod constants;
 pub mod blank_enums;
-
-pub const USR_FL: &str = "users.txt";
-pub const CLT_FL: &str = "clients.txt";
-pub const G_FL: &str = "goals.txt";
-pub const COL_FL: &str = "collaterals.txt";
-pub const GCOL_FL: &str = "general_collaterals.txt";
-pub const PRN_FL: &str = "pronouns.txt";
-pub const ND_FL: &str = "note_days.txt";
-pub const NT_FL: &str = "note_templates.txt";
-pub const N_FL: &str = "note.txt";
+pub mod cli;
+pub mod config;
+pub mod idle_lock;
+pub mod archive;
+pub mod migrations;
+pub mod load_errors;
+pub mod backups;
+pub mod export;
+pub mod ehr_entry;
+pub mod pdf;
+pub mod billing;
+pub mod travel;
+pub mod dashboard;
+pub mod compliance;
+pub mod search;
+pub mod note_filter;
+pub mod vocabulary;
+pub mod template_syntax;
+pub mod template_lint;
+pub mod template_library;
+pub mod goal_report;
 
 fn main() {
+  #[cfg(windows)]
   let _enabled = ansi_term::enable_ansi_support();
-  let filepaths = [
-    (String::from("user_filepath"), String::from(USR_FL),),
-    (String::from("client_filepath"), String::from(CLT_FL),),
-    (String::from("goal_filepath"), String::from(G_FL),),
-    (String::from("collateral_filepath"), String::from(COL_FL),),
-    (String::from("general_collateral_filepath"), String::from(GCOL_FL),),
-    (String::from("pronouns_filepath"), String::from(PRN_FL),),
-    (String::from("note_day_filepath"), String::from(ND_FL),),
-    (String::from("note_template_filepath"), String::from(NT_FL),),
-    (String::from("note_filepath"), String::from(N_FL),),
-  ].iter().cloned().collect();
-  let mut a = NoteArchive::new(filepaths);
-  a.run();
+  let all_args: Vec<String> = std::env::args().skip(1).collect();
+  let (global_options, args) = match config::GlobalOptions::split_from_args(&all_args) {
+    Ok(split) => split,
+    Err(e) => {
+      eprintln!("{}", e);
+      std::process::exit(1);
+    }
+  };
+  if let Some(result) = cli::run_profile_command(&args) {
+    match result {
+      Ok(output) => print!("{}", output),
+      Err(e) => {
+        eprintln!("{}", e);
+        std::process::exit(1);
+      }
+    }
+    return;
+  }
+  let config = match config::Config::load() {
+    Ok(c) => c,
+    Err(e) => {
+      eprintln!("{}", e);
+      std::process::exit(1);
+    }
+  };
+  let data_dir = match config.resolve_data_dir(&global_options, &config::GlobalOptions::from_env()) {
+    Ok(dir) => dir,
+    Err(e) => {
+      eprintln!("{}", e);
+      std::process::exit(1);
+    }
+  };
+  if let Err(e) = std::fs::create_dir_all(&data_dir) {
+    eprintln!("Unable to create data directory {}: {}", data_dir.display(), e);
+    std::process::exit(1);
+  }
+  let filepaths = config::filepaths_for_dir(&data_dir);
+  if let Some(result) = cli::run_migrate_command(&args, &filepaths) {
+    match result {
+      Ok(output) => print!("{}", output),
+      Err(e) => {
+        eprintln!("{}", e);
+        std::process::exit(1);
+      }
+    }
+    return;
+  }
+  let backup_count = config.backup_count.unwrap_or(backups::DEFAULT_BACKUP_COUNT);
+  if let Some(result) = cli::run_restore_command(&args, &filepaths, backup_count) {
+    match result {
+      Ok(output) => print!("{}", output),
+      Err(e) => {
+        eprintln!("{}", e);
+        std::process::exit(1);
+      }
+    }
+    return;
+  }
+  if args.is_empty() {
+    let mut a = NoteArchive::new(filepaths);
+    a.idle_lock_minutes = config.idle_lock_minutes.unwrap_or(idle_lock::DEFAULT_IDLE_LOCK_MINUTES);
+    a.backup_count = backup_count;
+    a.run();
+  } else {
+    let mut a = match NoteArchive::open(filepaths) {
+      Ok(a) => a,
+      Err(e) => {
+        eprintln!("Unable to read archive files (run without arguments to unlock encrypted files): {}", e);
+        std::process::exit(1);
+      }
+    };
+    a.backup_count = backup_count;
+    if !a.load_errors.is_empty() {
+      eprintln!("Warning: {} records could not be read and were left out:", a.load_errors.len());
+      for e in &a.load_errors {
+        eprintln!("  {}", e);
+      }
+    }
+    match cli::run_command(&mut a, &args) {
+      Ok(output) => print!("{}", output),
+      Err(e) => {
+        eprintln!("{}", e);
+        std::process::exit(1);
+      }
+    }
+  }
 }
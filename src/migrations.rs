@@ -0,0 +1,343 @@
+use serde_json::Value;
+use std::fs;
+use std::io::{Error, ErrorKind};
+
+use crate::archive::*;
+use crate::utils::write_file_atomically;
+
+/// one upgrade step, turning an archive at `from_version` into one at `from_version + 1`;
+/// `apply` edits the JSON in place and describes each change it made
+pub struct Migration {
+  pub from_version: u32,
+  pub description: &'static str,
+  pub apply: fn(&mut Value) -> Result<Vec<String>, String>,
+}
+
+/// every upgrade step in order; add one here whenever ARCHIVE_SCHEMA_VERSION is raised
+pub const MIGRATIONS: &[Migration] = &[
+  Migration {
+    from_version: 1,
+    description: "keep existing IDs permanently and record where new IDs start",
+    apply: record_next_ids,
+  },
+  Migration {
+    from_version: 2,
+    description: "add a start time and duration to each note",
+    apply: add_note_times,
+  },
+  Migration {
+    from_version: 3,
+    description: "add travel and mileage to each note",
+    apply: add_note_travel,
+  },
+  Migration {
+    from_version: 4,
+    description: "add enrollment episodes to each client",
+    apply: add_client_episodes,
+  },
+  Migration {
+    from_version: 5,
+    description: "add a status, objectives and progress ratings to each goal",
+    apply: add_goal_progress,
+  },
+];
+
+/// IDs used to be renumbered on every sort, with references patched to match, so the saved IDs
+/// and references already agree; keeping them as they are preserves every cross-reference
+fn record_next_ids(value: &mut Value) -> Result<Vec<String>, String> {
+  let mut next_ids = serde_json::Map::new();
+  let mut changes: Vec<String> = vec![];
+  for list in ID_LISTS {
+    let records = value[list].as_array().ok_or(format!("Archive file has no list of {}.", list))?;
+    let mut highest: u32 = 0;
+    for record in records {
+      match record["id"].as_u64() {
+        Some(id) => highest = highest.max(id as u32),
+        None => return Err(format!("A record in {} has no ID.", list)),
+      }
+    }
+    next_ids.insert(String::from(list), Value::from(highest + 1));
+    changes.push(format!("{}: {} records keep their IDs, new IDs start at {}", list, records.len(), highest + 1));
+  }
+  value["next_ids"] = Value::Object(next_ids);
+  Ok(changes)
+}
+
+/// existing notes have no time entered, so they are left out of billing reports until one is added
+fn add_note_times(value: &mut Value) -> Result<Vec<String>, String> {
+  let notes = value["notes"].as_array_mut().ok_or("Archive file has no list of notes.")?;
+  for n in notes.iter_mut() {
+    let record = n.as_object_mut().ok_or("A note in the archive is not a record.")?;
+    record.entry("start_time").or_insert(Value::Null);
+    record.entry("duration_minutes").or_insert(Value::Null);
+  }
+  Ok(vec![format!("notes: {} notes have no time entered", notes.len())])
+}
+
+fn add_note_travel(value: &mut Value) -> Result<Vec<String>, String> {
+  let notes = value["notes"].as_array_mut().ok_or("Archive file has no list of notes.")?;
+  for n in notes.iter_mut() {
+    let record = n.as_object_mut().ok_or("A note in the archive is not a record.")?;
+    record.entry("travel").or_insert(Value::Null);
+  }
+  Ok(vec![format!("notes: {} notes have no travel entered", notes.len())])
+}
+
+/// clients with note days are taken as enrolled since their first one; the rest have no episodes and stay active
+fn add_client_episodes(value: &mut Value) -> Result<Vec<String>, String> {
+  let mut first_days: std::collections::HashMap<u64, String> = std::collections::HashMap::new();
+  if let Some(note_days) = value["note_days"].as_array() {
+    for nd in note_days {
+      if let (Some(c_id), Some(date)) = (nd["foreign_key"]["client_id"].as_u64(), nd["date"].as_str()) {
+        let first = first_days.entry(c_id).or_insert_with(|| date.to_string() );
+        if date < first.as_str() {
+          *first = date.to_string();
+        }
+      }
+    }
+  }
+  let clients = value["clients"].as_array_mut().ok_or("Archive file has no list of clients.")?;
+  let mut enrolled = 0;
+  for c in clients.iter_mut() {
+    let first_day = c["id"].as_u64().and_then(|id| first_days.get(&id) );
+    let record = c.as_object_mut().ok_or("A client in the archive is not a record.")?;
+    let episodes = match first_day {
+      Some(date) => {
+        enrolled += 1;
+        serde_json::json!([{ "referral_date": null, "enrollment_date": date, "discharge_date": null, "discharge_reason": null }])
+      },
+      None => serde_json::json!([]),
+    };
+    record.entry("episodes").or_insert(episodes);
+  }
+  Ok(vec![format!(
+    "clients: {} clients enrolled from their first note day, {} with no note days left without an episode",
+    enrolled,
+    clients.len() - enrolled,
+  )])
+}
+
+/// existing goals stay active, with no objectives or ratings until they are entered
+fn add_goal_progress(value: &mut Value) -> Result<Vec<String>, String> {
+  let goals = value["goals"].as_array_mut().ok_or("Archive file has no list of goals.")?;
+  for g in goals.iter_mut() {
+    let record = g.as_object_mut().ok_or("A goal in the archive is not a record.")?;
+    record.entry("status").or_insert(Value::from("Active"));
+    record.entry("objectives").or_insert(Value::Array(vec![]));
+    record.entry("progress").or_insert(Value::Array(vec![]));
+  }
+  Ok(vec![format!("goals: {} goals marked active with no objectives or progress", goals.len())])
+}
+
+/// what a migration changed, or would change in a dry run
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+  pub from_version: u32,
+  pub to_version: u32,
+  pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+  pub fn is_needed(&self) -> bool {
+    self.from_version < self.to_version
+  }
+  pub fn to_display_string(&self) -> String {
+    if !self.is_needed() {
+      return format!("Archive is up to date (schema version {}).\n", self.from_version);
+    }
+    let mut s = format!("Schema version {} -> {}:\n", self.from_version, self.to_version);
+    for change in &self.changes {
+      s.push_str(&format!("  {}\n", change));
+    }
+    s
+  }
+}
+
+pub fn schema_version(value: &Value) -> Result<u32, String> {
+  match value.get("schema_version").and_then(|v| v.as_u64() ) {
+    Some(v) => Ok(v as u32),
+    None => Err(String::from("Archive file has no schema version.")),
+  }
+}
+
+/// applies each step in order until the archive is at `to_version`
+pub fn migrate(value: &mut Value, migrations: &[Migration], to_version: u32) -> Result<MigrationReport, String> {
+  let from_version = schema_version(value)?;
+  if from_version > to_version {
+    return Err(format!(
+      "Archive uses schema version {}, but this version of the program only reads up to version {}.",
+      from_version,
+      to_version,
+    ));
+  }
+  let mut changes: Vec<String> = vec![];
+  let mut version = from_version;
+  while version < to_version {
+    let step = match migrations.iter().find(|m| m.from_version == version ) {
+      Some(m) => m,
+      None => return Err(format!("No migration from schema version {}.", version)),
+    };
+    changes.push(format!("{} -> {}: {}", version, version + 1, step.description));
+    for change in (step.apply)(value)? {
+      changes.push(format!("  {}", change));
+    }
+    version += 1;
+    value["schema_version"] = Value::from(version);
+  }
+  Ok(MigrationReport { from_version, to_version, changes })
+}
+
+/// upgrades the archive file to the current schema; with `dry_run`, only reports what would change.
+/// The original file is kept next to the archive as '<archive>.schema-v<version>', and records that
+/// can't be read are written as they are so they show up on the repair screen.
+pub fn migrate_archive_file(filepath: &str, dry_run: bool) -> Result<MigrationReport, Error> {
+  let invalid = |e: String| Error::new(ErrorKind::InvalidData, e);
+  let original = fs::read_to_string(filepath)?;
+  let mut value: Value = serde_json::from_str(&original).map_err(|e| invalid(e.to_string()) )?;
+  let report = migrate(&mut value, MIGRATIONS, ARCHIVE_SCHEMA_VERSION).map_err(invalid)?;
+  if dry_run || !report.is_needed() {
+    return Ok(report);
+  }
+  let migrated = serde_json::to_string_pretty(&value).map_err(|e| invalid(e.to_string()) )?;
+  ArchiveFile::from_json_with_errors(&migrated, filepath)
+    .map_err(|e| invalid(format!("Migrated archive could not be read: {}", e)) )?;
+  fs::write(format!("{}.schema-v{}", filepath, report.from_version), &original)?;
+  write_file_atomically(filepath, migrated.as_bytes())?;
+  Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn rename_goal_text(value: &mut Value) -> Result<Vec<String>, String> {
+    let mut changes = vec![];
+    for goal in value["goals"].as_array_mut().ok_or("missing goals")? {
+      let text = goal["text"].take();
+      goal["goal"] = text;
+      changes.push(format!("goal {} renamed 'text' to 'goal'", goal["id"]));
+    }
+    Ok(changes)
+  }
+
+  fn add_client_notes(value: &mut Value) -> Result<Vec<String>, String> {
+    value["client_notes"] = json!([]);
+    Ok(vec![String::from("added client_notes")])
+  }
+
+  const TEST_MIGRATIONS: &[Migration] = &[
+    Migration { from_version: 2, description: "add client notes", apply: add_client_notes },
+    Migration { from_version: 1, description: "rename goal text", apply: rename_goal_text },
+  ];
+
+  #[test]
+  fn records_next_ids_without_renumbering() {
+    let mut value = json!({
+      "schema_version": 1,
+      "users": [{ "id": 2 }, { "id": 1 }],
+      "clients": [],
+      "goals": [],
+      "collaterals": [{ "id": 7 }],
+      "general_collaterals": [],
+      "pronouns": [{ "id": 1 }, { "id": 2 }, { "id": 3 }],
+      "note_days": [],
+      "note_templates": [],
+      "notes": [{ "id": 4 }],
+    });
+    let report = migrate(&mut value, MIGRATIONS, 2).unwrap();
+    assert_eq!(report.changes.len(), 10);
+    assert_eq!(value["users"], json!([{ "id": 2 }, { "id": 1 }]));
+    assert_eq!(value["next_ids"]["users"], json!(3));
+    assert_eq!(value["next_ids"]["collaterals"], json!(8));
+    assert_eq!(value["next_ids"]["clients"], json!(1));
+    assert_eq!(value["next_ids"]["notes"], json!(5));
+    assert!(migrate(&mut json!({ "schema_version": 1, "users": [{ "name": "x" }] }), MIGRATIONS, 2).is_err());
+  }
+
+  #[test]
+  fn adds_note_times() {
+    let mut value = json!({ "schema_version": 2, "notes": [{ "id": 4 }] });
+    let report = migrate(&mut value, MIGRATIONS, 3).unwrap();
+    assert_eq!(report.changes[1], String::from("  notes: 1 notes have no time entered"));
+    assert_eq!(value["notes"], json!([{ "id": 4, "start_time": null, "duration_minutes": null }]));
+  }
+
+  #[test]
+  fn adds_note_travel() {
+    let mut value = json!({ "schema_version": 3, "notes": [{ "id": 4, "travel": null }, { "id": 5 }] });
+    let report = migrate(&mut value, MIGRATIONS, 4).unwrap();
+    assert_eq!(report.changes[1], String::from("  notes: 2 notes have no travel entered"));
+    assert_eq!(value["notes"][1], json!({ "id": 5, "travel": null }));
+  }
+
+  #[test]
+  fn adds_client_episodes() {
+    let mut value = json!({
+      "schema_version": 4,
+      "clients": [{ "id": 1 }, { "id": 2 }],
+      "note_days": [
+        { "id": 1, "date": "2021-03-09", "foreign_key": { "client_id": 1 } },
+        { "id": 2, "date": "2021-03-02", "foreign_key": { "client_id": 1 } },
+      ],
+    });
+    let report = migrate(&mut value, MIGRATIONS, 5).unwrap();
+    assert_eq!(
+      report.changes[1],
+      String::from("  clients: 1 clients enrolled from their first note day, 1 with no note days left without an episode"),
+    );
+    assert_eq!(value["clients"][0]["episodes"][0]["enrollment_date"], json!("2021-03-02"));
+    assert_eq!(value["clients"][1]["episodes"], json!([]));
+  }
+
+  #[test]
+  fn adds_goal_progress() {
+    let mut value = json!({ "schema_version": 5, "goals": [{ "id": 1, "client_id": 1, "goal": "Attend school" }] });
+    migrate(&mut value, MIGRATIONS, 6).unwrap();
+    assert_eq!(value["goals"][0]["status"], json!("Active"));
+    assert_eq!(value["goals"][0]["objectives"], json!([]));
+    assert_eq!(value["goals"][0]["progress"], json!([]));
+    assert!(migrate(&mut json!({ "schema_version": 5 }), MIGRATIONS, 6).is_err());
+  }
+
+  #[test]
+  fn keeps_unreadable_records_for_repair() {
+    let filepath = "test_migrate_unreadable_archive.json";
+    let mut value = serde_json::to_value(ArchiveFile::new()).unwrap();
+    value["schema_version"] = json!(ARCHIVE_SCHEMA_VERSION - 1);
+    value["goals"] = json!([
+      { "id": 1, "client_id": 1, "goal": "Attend school" },
+      { "id": 2, "client_id": "one", "goal": "Sleep through the night" },
+    ]);
+    fs::write(filepath, value.to_string()).unwrap();
+    let report = migrate_archive_file(filepath, false);
+    let read = ArchiveFile::read_with_errors(filepath);
+    fs::remove_file(filepath).unwrap();
+    fs::remove_file(format!("{}.schema-v{}", filepath, ARCHIVE_SCHEMA_VERSION - 1)).unwrap();
+
+    assert!(report.unwrap().is_needed());
+    let (archive, errors) = read.unwrap();
+    assert_eq!(archive.goals.len(), 1);
+    assert_eq!(errors.len(), 1);
+  }
+
+  #[test]
+  fn applies_steps_in_order() {
+    let mut value = json!({ "schema_version": 1, "goals": [{ "id": 4, "text": "Attend school" }] });
+    let report = migrate(&mut value, TEST_MIGRATIONS, 3).unwrap();
+    assert_eq!(report.from_version, 1);
+    assert_eq!(report.changes, vec![
+      String::from("1 -> 2: rename goal text"),
+      String::from("  goal 4 renamed 'text' to 'goal'"),
+      String::from("2 -> 3: add client notes"),
+      String::from("  added client_notes"),
+    ]);
+    assert_eq!(value["schema_version"], json!(3));
+    assert_eq!(value["goals"][0]["goal"], json!("Attend school"));
+
+    let current = migrate(&mut value, TEST_MIGRATIONS, 3).unwrap();
+    assert!(!current.is_needed());
+    assert!(migrate(&mut value, TEST_MIGRATIONS, 2).is_err());
+    assert!(migrate(&mut json!({ "schema_version": 0 }), TEST_MIGRATIONS, 3).is_err());
+  }
+}
@@ -3,11 +3,13 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use ansi_term::Colour::{Black, Yellow, White, RGB};
 use ansi_term::{Style};
-use chrono::{NaiveDate, Datelike, Weekday};
+use chrono::{NaiveDate, NaiveTime, Datelike, Weekday, Duration};
+use serde::{Serialize, Deserialize};
 
 // bold, dimmed, italic, underline, blink, reverse, hidden, strikethrough, on
 
 use crate::constants::*;
+use crate::travel::Travel;
 
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -48,7 +50,7 @@ macro_rules! print_unfocused_blank {
     ($($arg:tt)*) => (print_unfocused_blank(format!("{}", format_args!($($arg)*))));
 }
 
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StructureType {
   CarePlan,
   Intake,
@@ -224,7 +226,7 @@ impl fmt::Display for StructureType {
   }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoteTemplate {
   pub id: u32,
   pub structure: StructureType,
@@ -1161,7 +1163,7 @@ impl fmt::Display for NoteTemplate {
   }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum NoteCategory {
   ICCNote(ICCNoteCategory),
   FPNote(FPNoteCategory),
@@ -1189,7 +1191,7 @@ impl fmt::Display for NoteCategory {
 
 use NoteCategory::{ICCNote, FPNote};
 
-#[derive(Debug, Clone, Copy, PartialOrd, Ord, Eq)]
+#[derive(Debug, Clone, Copy, PartialOrd, Ord, Eq, Serialize, Deserialize)]
 pub enum ICCNoteCategory {
   FaceToFaceContactWithClient,
   TelephoneContactWithClient,
@@ -1247,7 +1249,7 @@ impl fmt::Display for ICCNoteCategory {
 use ICCNoteCategory::{FaceToFaceContactWithClient, TelephoneContactWithClient,
 CareCoordination, Documentation, CarePlanningTeam, TransportClient, MemberOutreachNoShow};
 
-#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FPIntervention {
   FaceToFaceContact,
   CollateralContact,
@@ -1347,7 +1349,7 @@ impl PartialEq for FPIntervention {
   }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FPNoteCategory {
   DescriptionOfIntervention(Option<FPIntervention>),
   ResponseToIntervention(Option<FPIntervention>),
@@ -1466,7 +1468,7 @@ impl PartialEq for FPNoteCategory {
   }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Note {
   pub id: u32,
   pub date: NaiveDate,
@@ -1476,6 +1478,12 @@ pub struct Note {
   pub blanks: HashMap<u32, (Blank, String, Vec<u32>)>, // blank type, display string, foreign keys - hashed by position of blank
   pub foreign_key: HashMap<String, u32>,
   pub foreign_keys: HashMap<String, Vec<u32>>,
+  /// when the contact started, if it was entered as a start and end time
+  pub start_time: Option<NaiveTime>,
+  /// how long the contact took, used to count billing units
+  pub duration_minutes: Option<u32>,
+  /// mileage and travel time for transports and home visits
+  pub travel: Option<Travel>,
 }
 
 impl Note {
@@ -1572,11 +1580,37 @@ impl Note {
       blanks,
       foreign_key,
       foreign_keys,
+      start_time: None,
+      duration_minutes: None,
+      travel: None,
     }
   }
   pub fn fmt_date(&self) -> String {
     self.date.format("%Y-%m-%d").to_string()
   }
+  pub fn set_time(&mut self, start_time: Option<NaiveTime>, duration_minutes: Option<u32>) {
+    self.start_time = start_time;
+    self.duration_minutes = duration_minutes;
+  }
+  pub fn end_time(&self) -> Option<NaiveTime> {
+    match (self.start_time, self.duration_minutes) {
+      (Some(start), Some(minutes)) => Some(start + Duration::minutes(minutes as i64)),
+      _ => None,
+    }
+  }
+  /// such as '9:00 AM to 9:45 AM (45 minutes)' or '45 minutes'
+  pub fn fmt_time(&self) -> String {
+    match (self.start_time, self.end_time(), self.duration_minutes) {
+      (Some(start), Some(end), Some(minutes)) => format!(
+        "{} to {} ({} minutes)",
+        start.format("%-I:%M %p"),
+        end.format("%-I:%M %p"),
+        minutes,
+      ),
+      (_, _, Some(minutes)) => format!("{} minutes", minutes),
+      _ => String::from("no time entered"),
+    }
+  }
   pub fn heading_date(&self) -> String {
     let wd = match self.date.weekday() {
       Weekday::Mon => "Monday",
@@ -2304,7 +2338,7 @@ impl fmt::Display for Note {
   }
 }
 
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Blank {
   CurrentUser,
   PartnerICCOrFP,
@@ -2558,7 +2592,13 @@ impl Blank {
     }
   }
   pub fn get_blank_from_str(s: &str) -> Blank {
-    match &s[..] {
+    match Self::try_from_str(s) {
+      Ok(b) => b,
+      Err(e) => panic!("{}", e),
+    }
+  }
+  pub fn try_from_str(s: &str) -> Result<Blank, String> {
+    Ok(match s {
       "(---u---)" => CurrentUser,
       "(---p---)" => PartnerICCOrFP,
       "(---c---)" => CurrentClientName,
@@ -2596,10 +2636,13 @@ impl Blank {
       "(---cu---)" => CustomBlank,
       _ => { // only other options are pb@id@ which is a pronouns for blank, requires parsing 'id' to int
         let components = s.split("@").map(|st| st.to_string() ).collect::<Vec<String>>();
+        if components.len() != 3 {
+          return Err(format!("Failed to read Blank type from string: {}", s));
+        }
 
         let blank_id: u32 = match components[1].parse() {
           Ok(num) => num,
-          Err(e) => panic!("Attempted to read Blank type from str: {}, {}", components[1], e),
+          Err(e) => return Err(format!("Attempted to read Blank type from str: {}, {}", components[1], e)),
         };
 
         match &format!("{}{}", components[0], components[2])[..] {
@@ -2607,10 +2650,10 @@ impl Blank {
           "(---pb2---)" => Pronoun2ForBlank(Some(blank_id)),
           "(---pb3---)" => Pronoun3ForBlank(Some(blank_id)),
           "(---pb4---)" => Pronoun4ForBlank(Some(blank_id)),
-          _ => panic!("Failed to read Blank type from string: {}", &format!("{}{}{}", components[0], components[1], components[2])),
+          _ => return Err(format!("Failed to read Blank type from string: {}", &format!("{}{}{}", components[0], components[1], components[2]))),
         }
       },
-    }
+    })
   }
   pub fn display_to_user(&self) -> String {
     match self {
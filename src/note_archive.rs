@@ -5,12 +5,12 @@ use ansi_term::Colour::{Black, Red, Green, Yellow, Blue, RGB};
 use ansi_term::Style;
 
 use chrono::{Local, NaiveDate, Datelike};
-use std::{fs, io, thread, time};
-use std::fs::File;
+use std::{fs, thread, time};
+use std::path::Path;
 use std::fs::OpenOptions;
-use std::io::prelude::*;
 use std::io::{BufRead, BufReader, Error, ErrorKind};
 use std::collections::{HashMap, BTreeMap};
+use serde::de::DeserializeOwned;
 
 use crate::user::*;
 use crate::client::*;
@@ -19,7 +19,6 @@ use crate::collateral::*;
 use crate::pronouns::*;
 use crate::note_day::*;
 use crate::note::*;
-use crate::blank_enums::*;
 use EmployeeRole::{Fp, Icc};
 use SupportType::{Natural, Formal};
 use StructureType::{
@@ -117,6 +116,38 @@ use Blank::{
 };
 
 use crate::utils::*;
+use crate::idle_lock::*;
+use crate::config::Config;
+use crate::archive::*;
+use crate::migrations::*;
+use crate::load_errors::*;
+use crate::backups::*;
+use crate::export::*;
+use crate::ehr_entry::*;
+use crate::pdf::*;
+use crate::billing::*;
+use crate::travel::*;
+use crate::dashboard::*;
+use crate::compliance::*;
+use crate::search::*;
+use crate::note_filter::*;
+use crate::vocabulary::*;
+use crate::template_syntax::*;
+use crate::template_lint::*;
+use crate::template_library::*;
+use crate::goal_report::*;
+use RecordKind::{
+  UserRecord,
+  ClientRecord,
+  GoalRecord,
+  CollateralRecord,
+  GeneralCollateralRecord,
+  PronounsRecord,
+  NoteDayRecord,
+  NoteTemplateRecord,
+  NoteRecord,
+};
+use RecordFormat::{TextLine, JsonRecord};
 use crate::constants::*;
 
 pub struct NoteArchive {
@@ -132,7 +163,12 @@ pub struct NoteArchive {
   pub foreign_key: HashMap<String, u32>,
   pub foreign_keys: HashMap<String, Vec<u32>>,
   pub encrypted: bool,
+  pub password: Option<String>,
+  pub idle_lock_minutes: u64,
+  pub backup_count: usize,
+  pub next_ids: BTreeMap<String, u32>,
   pub filepaths: HashMap<String, String>,
+  pub load_errors: Vec<LoadError>,
 }
 
 // general functions
@@ -225,7 +261,7 @@ fn choose_blanks() -> usize {
   loop {
     display_blanks_empty();
     let mut input = String::new();
-    let input_attempt = io::stdin().read_line(&mut input);
+    let input_attempt = read_input_line(&mut input);
     match input_attempt {
       Ok(_) => (),
       Err(e) => {
@@ -257,7 +293,7 @@ fn choose_blanks_option() -> Option<usize> {
     display_blanks_empty();
     println_inst!("Enter 'QUIT / Q' at any time to cancel.");
     let mut input = String::new();
-    let input_attempt = io::stdin().read_line(&mut input);
+    let input_attempt = read_input_line(&mut input);
     match input_attempt {
       Ok(_) => (),
       Err(e) => {
@@ -338,6 +374,11 @@ pub fn get_spacing_buffers(last_content_char: Option<char>, next_content_char: O
 
 impl NoteArchive {
   pub fn run(&mut self) {
+    self.update_idle_lock();
+    if !self.load_errors.is_empty() && !self.choose_repair_records() {
+      self.lock_on_exit();
+      return;
+    }
     match self.choose_user() {
       Some(_) => {
         self.write_to_files();
@@ -345,6 +386,7 @@ impl NoteArchive {
       },
       None => (),
     }
+    self.lock_on_exit();
   }
   fn display_decrypt_files() {
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
@@ -429,20 +471,11 @@ impl NoteArchive {
       _ => panic!("String other than 'client' or 'collateral' passed to fn 'delete_from_blanks'."),
     }
   }
-  fn choose_decrypt_files(
-    user_filepath: &str,
-    client_filepath: &str,
-    goal_filepath: &str,
-    collateral_filepath: &str,
-    general_collateral_filepath: &str,
-    pronouns_filepath: &str,
-    note_day_filepath: &str,
-    note_template_filepath: &str,
-    note_filepath: &str) -> bool {
+  fn choose_decrypt_files(filepaths: &HashMap<String, String>, password: &mut Option<String>) -> bool {
     loop {
       Self::display_decrypt_files();
       let mut choice = String::new();
-      let choice_attempt = io::stdin().read_line(&mut choice);
+      let choice_attempt = read_input_line(&mut choice);
       match choice_attempt {
         Ok(_) => (),
         Err(e) => {
@@ -454,7 +487,7 @@ impl NoteArchive {
         "decrypt" | "d" => {
           println_inst!("Enter password to attempt decryption.");
           let mut pw = String::new();
-          let pw_attempt = io::stdin().read_line(&mut pw);
+          let pw_attempt = read_input_line(&mut pw);
           match pw_attempt {
             Ok(_) => (),
             Err(e) => {
@@ -462,36 +495,37 @@ impl NoteArchive {
             }
           }
           pw = pw.trim().to_string();
-          break match Self::decrypt_all_files(
-            user_filepath,
-            client_filepath,
-            goal_filepath,
-            collateral_filepath,
-            general_collateral_filepath,
-            pronouns_filepath,
-            note_day_filepath,
-            note_template_filepath,
-            note_filepath,
-            &pw
-          ) {
-            Ok(_) => true,
-            Err(_) => {
-              println_err!("Incorrect password. Option to try again in 10 seconds.");
+          break match Self::decrypt_all_files(filepaths, &pw) {
+            Ok(legacy_found) => {
+              if legacy_found {
+                println_yel!("Files were encrypted with an older format and have been unlocked.");
+                println_yel!("They will use the current format the next time they are encrypted.");
+                thread::sleep(time::Duration::from_secs(3));
+              }
+              *password = Some(pw);
+              true
+            },
+            Err(e) => {
+              match e.kind() {
+                ErrorKind::PermissionDenied => {
+                  println_err!("Incorrect password. Option to try again in 10 seconds.");
+                },
+                ErrorKind::InvalidData => {
+                  println_err!("{} Restore the file from a backup or delete all data to start over.", e);
+                },
+                _ => {
+                  println_err!("Failed to decrypt files: {}", e);
+                },
+              }
               thread::sleep(time::Duration::from_secs(10));
               continue;
             }
           }
         },
         "delete" => {
-          fs::remove_file(user_filepath).unwrap();
-          fs::remove_file(client_filepath).unwrap();
-          fs::remove_file(goal_filepath).unwrap();
-          fs::remove_file(collateral_filepath).unwrap();
-          fs::remove_file(general_collateral_filepath).unwrap();
-          fs::remove_file(pronouns_filepath).unwrap();
-          fs::remove_file(note_day_filepath).unwrap();
-          fs::remove_file(note_template_filepath).unwrap();
-          fs::remove_file(note_filepath).unwrap();
+          for (fp, _) in Self::data_files(filepaths) {
+            fs::remove_file(fp).unwrap();
+          }
           break true;
         },
         "quit" | "q" => {
@@ -506,48 +540,336 @@ impl NoteArchive {
     }
   }
   pub fn new(filepaths: HashMap<String, String>) -> NoteArchive {
-    let foreign_key: HashMap<String, u32> = HashMap::new();
-    let foreign_keys: HashMap<String, Vec<u32>> = HashMap::new();
-    let encrypted = false;
-    let mut build_note_archive = true;
-    match Self::read_users(&filepaths["user_filepath"]) {
-      Ok(_) => (),
-      Err(_) => {
-        build_note_archive = Self::choose_decrypt_files(
-          &filepaths["user_filepath"],
-          &filepaths["client_filepath"],
-          &filepaths["goal_filepath"],
-          &filepaths["collateral_filepath"],
-          &filepaths["general_collateral_filepath"],
-          &filepaths["pronouns_filepath"],
-          &filepaths["note_day_filepath"],
-          &filepaths["note_template_filepath"],
-          &filepaths["note_filepath"],
-        );
+    if let Ok(a) = Self::open(filepaths.clone()) {
+      return a;
+    }
+    let mut password: Option<String> = None;
+    if !Self::archive_needs_migration(&filepaths) && !Self::choose_decrypt_files(&filepaths, &mut password) {
+      panic!("Unable to access data.");
+    }
+    if Self::archive_needs_migration(&filepaths) && !Self::choose_migrate_archive(&filepaths) {
+      panic!("Archive was not upgraded.");
+    }
+    let mut a = Self::open(filepaths).unwrap();
+    a.password = password;
+    a
+  }
+  fn archive_needs_migration(filepaths: &HashMap<String, String>) -> bool {
+    match migrate_archive_file(&filepaths["archive_filepath"], true) {
+      Ok(report) => report.is_needed(),
+      Err(_) => false,
+    }
+  }
+  /// shows what upgrading the archive would change and applies it if confirmed
+  fn choose_migrate_archive(filepaths: &HashMap<String, String>) -> bool {
+    let archive_filepath = &filepaths["archive_filepath"];
+    let report = match migrate_archive_file(archive_filepath, true) {
+      Ok(report) => report,
+      Err(e) => {
+        println_err!("Failed to read archive: {}", e);
+        return false;
+      },
+    };
+    loop {
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_yel!("{:-^58}", "-");
+      println_yel!("{:-^58}", " Archive upgrade needed ");
+      println_yel!("{:-^58}", "-");
+      print!("{}", report.to_display_string());
+      println_yel!("{:-^58}", "-");
+      println_inst!("Enter YES / Y to upgrade the archive (the original is kept as a copy) or QUIT / Q to close the program.");
+      let mut choice = String::new();
+      let choice_attempt = read_input_line(&mut choice);
+      match choice_attempt {
+        Ok(_) => (),
+        Err(e) => {
+          println_err!("Failed to read input: {}.", e);
+          continue;
+        }
+      }
+      match choice.to_ascii_lowercase().trim() {
+        "yes" | "y" => {
+          break match migrate_archive_file(archive_filepath, false) {
+            Ok(_) => {
+              println_suc!("Archive upgraded. The original was saved as {}.schema-v{}.", archive_filepath, report.from_version);
+              thread::sleep(time::Duration::from_secs(2));
+              true
+            },
+            Err(e) => {
+              println_err!("Failed to upgrade archive: {}", e);
+              thread::sleep(time::Duration::from_secs(3));
+              false
+            },
+          };
+        },
+        "quit" | "q" => {
+          break false;
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
       }
     }
-    if build_note_archive {
-      let mut a = NoteArchive {
-        users: Self::read_users(&filepaths["user_filepath"]).unwrap(),
-        clients: Self::read_clients(&filepaths["client_filepath"]).unwrap(),
-        goals: Self::read_goals(&filepaths["goal_filepath"]).unwrap(),
-        collaterals: Self::read_collaterals(&filepaths["collateral_filepath"]).unwrap(),
-        general_collaterals: Self::read_general_collaterals(&filepaths["general_collateral_filepath"]).unwrap(),
-        pronouns: vec![],
-        note_days: Self::read_note_days(&filepaths["note_day_filepath"]).unwrap(),
-        note_templates: Self::read_note_templates(&filepaths["note_template_filepath"]).unwrap(),
-        notes: Self::read_notes(&filepaths["note_filepath"]).unwrap(),
-        foreign_key,
-        foreign_keys,
-        encrypted,
-        filepaths,
+  }
+  fn display_load_errors(&self) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_yel!("{:-^58}", "-");
+    println_yel!("{:-^58}", " Records that could not be read ");
+    println_yel!("{:-^58}", "-");
+    for (i, e) in self.load_errors.iter().enumerate() {
+      println_yel!("{: >5} | {}", i + 1, e);
+      println_on_bg!("{: >5} | {}", "", e.raw);
+    }
+    println_yel!("{:-^58}", "-");
+    println_yel!("{: >15} | {: <40}", " SKIP / S ", " Leave the record out of the archive ");
+    println_yel!("{: >15} | {: <40}", " EDIT / E ", " Type a corrected version of the record ");
+    println_yel!("{: >15} | {: <40}", " QUARANTINE / QR ", " Move the record to a side file ");
+    println_yel!("{: >15} | {: <40}", " QUIT / Q ", " Close program without changes ");
+    println_yel!("{:-^58}", "-");
+  }
+  /// lets the user skip, edit, or quarantine each record that could not be read;
+  /// returns false if the user quits, in which case nothing is saved
+  fn choose_repair_records(&mut self) -> bool {
+    let mut quarantined: Vec<LoadError> = vec![];
+    while !self.load_errors.is_empty() {
+      self.display_load_errors();
+      println_inst!("Enter a command followed by a record number (for example, 'EDIT 2'), or SKIP ALL / QUARANTINE ALL.");
+      let mut choice = String::new();
+      let choice_attempt = read_input_line(&mut choice);
+      match choice_attempt {
+        Ok(_) => (),
+        Err(e) => {
+          println_err!("Failed to read input: {}.", e);
+          continue;
+        }
+      }
+      let choice = choice.to_ascii_lowercase();
+      let words: Vec<&str> = choice.split_whitespace().collect();
+      let (command, target) = match words[..] {
+        [command] => (command, "1"),
+        [command, target] => (command, target),
+        _ => ("", ""),
+      };
+      if matches!(command, "quit" | "q") {
+        return false;
+      }
+      let indices: Vec<usize> = match target {
+        "all" => (0..self.load_errors.len()).collect(),
+        n => match n.parse::<usize>() {
+          Ok(num) if num > 0 && num <= self.load_errors.len() => vec![num - 1],
+          _ => {
+            println_err!("Invalid record number.");
+            thread::sleep(time::Duration::from_secs(1));
+            continue;
+          },
+        },
       };
-      a.pronouns = a.read_pronouns().unwrap();
-      a
+      match command {
+        "skip" | "s" => {
+          for i in indices.into_iter().rev() {
+            self.load_errors.remove(i);
+          }
+        },
+        "quarantine" | "qr" => {
+          for i in indices.into_iter().rev() {
+            quarantined.push(self.load_errors.remove(i));
+          }
+        },
+        "edit" | "e" => {
+          if indices.len() != 1 {
+            println_err!("Records can only be edited one at a time.");
+            thread::sleep(time::Duration::from_secs(1));
+            continue;
+          }
+          let i = indices[0];
+          let error = self.load_errors[i].clone();
+          println_inst!("Enter a corrected version of the {} record, or leave blank to cancel:", error.kind);
+          println_on_bg!("{}", error.raw);
+          let mut raw = String::new();
+          if let Err(e) = read_input_line(&mut raw) {
+            println_err!("Failed to read input: {}.", e);
+            thread::sleep(time::Duration::from_secs(1));
+            continue;
+          }
+          let raw = raw.trim_end_matches(['\n', '\r']);
+          if raw.trim().is_empty() {
+            continue;
+          }
+          match self.repair_record(&error, raw) {
+            Ok(()) => {
+              self.load_errors.remove(i);
+            },
+            Err(e) => {
+              println_err!("Record could not be read: field '{}': {}", e.field, e.reason);
+              thread::sleep(time::Duration::from_secs(3));
+            },
+          }
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+    }
+    for error in quarantined {
+      match error.quarantine() {
+        Ok(fp) => println_suc!("{} record saved to {}.", error.kind, fp),
+        Err(e) => {
+          println_err!("Failed to quarantine record from {}: {}", error.location(), e);
+          thread::sleep(time::Duration::from_secs(3));
+          return false;
+        },
+      }
+    }
+    self.write_to_files();
+    true
+  }
+  /// reads a corrected record in the same format as the one that failed and adds it to the archive
+  pub fn repair_record(&mut self, error: &LoadError, raw: &str) -> Result<(), FieldError> {
+    match error.kind {
+      UserRecord => {
+        let u = Self::parse_repaired(error, raw, Self::parse_user_line)?;
+        Self::check_id_unused(u.id, self.users.iter().map(|u| u.id ))?;
+        self.users.push(u);
+      },
+      ClientRecord => {
+        let c = Self::parse_repaired(error, raw, Self::parse_client_line)?;
+        Self::check_id_unused(c.id, self.clients.iter().map(|c| c.id ))?;
+        self.clients.push(c);
+      },
+      GoalRecord => {
+        let g = Self::parse_repaired(error, raw, Self::parse_goal_line)?;
+        Self::check_id_unused(g.id, self.goals.iter().map(|g| g.id ))?;
+        self.goals.push(g);
+      },
+      CollateralRecord => {
+        let co = Self::parse_repaired(error, raw, Self::parse_collateral_line)?;
+        Self::check_id_unused(co.id, self.collaterals.iter().map(|co| co.id ))?;
+        self.collaterals.push(co);
+      },
+      GeneralCollateralRecord => {
+        let co = Self::parse_repaired(error, raw, Self::parse_collateral_line)?;
+        Self::check_id_unused(co.id, self.general_collaterals.iter().map(|co| co.id ))?;
+        self.general_collaterals.push(co);
+      },
+      PronounsRecord => {
+        let p = Self::parse_repaired(error, raw, Self::parse_pronouns_line)?;
+        Self::check_id_unused(p.id, self.pronouns.iter().map(|p| p.id ))?;
+        self.pronouns.push(p);
+      },
+      NoteDayRecord => {
+        let nd = Self::parse_repaired(error, raw, Self::parse_note_day_line)?;
+        Self::check_id_unused(nd.id, self.note_days.iter().map(|nd| nd.id ))?;
+        self.note_days.push(nd);
+      },
+      NoteTemplateRecord => {
+        let nt = Self::parse_repaired(error, raw, Self::parse_note_template_line)?;
+        Self::check_id_unused(nt.id, self.note_templates.iter().map(|nt| nt.id ))?;
+        self.note_templates.push(nt);
+      },
+      NoteRecord => {
+        let n = Self::parse_repaired(error, raw, Self::parse_note_line)?;
+        Self::check_id_unused(n.id, self.notes.iter().map(|n| n.id ))?;
+        self.notes.push(n);
+      },
+    }
+    Ok(())
+  }
+  fn parse_repaired<T: DeserializeOwned>(
+    error: &LoadError,
+    raw: &str,
+    parse_line: fn(&str) -> Result<T, FieldError>,
+  ) -> Result<T, FieldError> {
+    match error.format {
+      TextLine => parse_line(raw),
+      JsonRecord => parse_json_record(raw),
+    }
+  }
+  fn check_id_unused(id: u32, mut ids: impl Iterator<Item = u32>) -> Result<(), FieldError> {
+    if ids.any(|existing| existing == id ) {
+      Err(FieldError::new("id", format!("ID {} is already in use", id)))
     } else {
-      panic!("Unable to access data.");
+      Ok(())
     }
   }
+  /// loads the archive without prompting, failing if it cannot be read (for example, if it is encrypted);
+  /// the first time, data is imported from the older text files if there are any
+  pub fn open(filepaths: HashMap<String, String>) -> Result<NoteArchive, Error> {
+    let archive_filepath = filepaths["archive_filepath"].clone();
+    if Path::new(&archive_filepath).exists() {
+      let (data, load_errors) = ArchiveFile::read_with_errors(&archive_filepath)?;
+      let mut a = Self::from_archive_file(data, filepaths);
+      a.load_errors = load_errors;
+      Ok(a)
+    } else if filepaths.get("user_filepath").is_some_and(|fp| Path::new(fp).exists() ) {
+      let mut a = Self::import_text_files(filepaths)?;
+      // with unreadable lines, the archive is written once they have been repaired
+      if a.load_errors.is_empty() {
+        a.write_archive()?;
+      }
+      Ok(a)
+    } else {
+      Ok(Self::from_archive_file(ArchiveFile::new(), filepaths))
+    }
+  }
+  pub fn from_archive_file(data: ArchiveFile, filepaths: HashMap<String, String>) -> NoteArchive {
+    NoteArchive {
+      users: data.users,
+      clients: data.clients,
+      goals: data.goals,
+      collaterals: data.collaterals,
+      general_collaterals: data.general_collaterals,
+      pronouns: data.pronouns,
+      note_days: data.note_days,
+      note_templates: data.note_templates,
+      notes: data.notes,
+      foreign_key: HashMap::new(),
+      foreign_keys: HashMap::new(),
+      encrypted: false,
+      password: None,
+      idle_lock_minutes: DEFAULT_IDLE_LOCK_MINUTES,
+      backup_count: DEFAULT_BACKUP_COUNT,
+      next_ids: data.next_ids,
+      filepaths,
+      load_errors: vec![],
+    }
+  }
+  /// reads the pipe-delimited text files used before the archive file; they are left in place unchanged
+  pub fn import_text_files(filepaths: HashMap<String, String>) -> Result<NoteArchive, Error> {
+    let mut data = ArchiveFile::new();
+    let mut load_errors: Vec<LoadError> = vec![];
+    let (users, errors) = Self::read_users(&filepaths["user_filepath"])?;
+    data.users = users;
+    load_errors.extend(errors);
+    let (clients, errors) = Self::read_clients(&filepaths["client_filepath"])?;
+    data.clients = clients;
+    load_errors.extend(errors);
+    let (goals, errors) = Self::read_goals(&filepaths["goal_filepath"])?;
+    data.goals = goals;
+    load_errors.extend(errors);
+    let (collaterals, errors) = Self::read_collaterals(&filepaths["collateral_filepath"])?;
+    data.collaterals = collaterals;
+    load_errors.extend(errors);
+    let (general_collaterals, errors) = Self::read_general_collaterals(&filepaths["general_collateral_filepath"])?;
+    data.general_collaterals = general_collaterals;
+    load_errors.extend(errors);
+    let (note_days, errors) = Self::read_note_days(&filepaths["note_day_filepath"])?;
+    data.note_days = note_days;
+    load_errors.extend(errors);
+    let (note_templates, errors) = Self::read_note_templates(&filepaths["note_template_filepath"])?;
+    data.note_templates = note_templates;
+    load_errors.extend(errors);
+    let (notes, errors) = Self::read_notes(&filepaths["note_filepath"])?;
+    data.notes = notes;
+    load_errors.extend(errors);
+    let mut a = Self::from_archive_file(data, filepaths);
+    let (pronouns, errors) = a.read_pronouns()?;
+    a.pronouns = pronouns;
+    load_errors.extend(errors);
+    a.load_errors = load_errors;
+    Ok(a)
+  }
   pub fn new_test(filepaths: HashMap<String, String>) -> NoteArchive {
     let user_1 = User::new(
       1,
@@ -653,6 +975,8 @@ impl NoteArchive {
     let note_days = vec![nd1, nd2];
 
     let mut notes = NoteArchive::new(filepaths);
+    // test archives are saved in the working directory, so keep backups out of it
+    notes.backup_count = 0;
 
     let nt1 = NoteTemplate::new(
       1,
@@ -681,120 +1005,126 @@ impl NoteArchive {
 
     notes
   }
-  fn write_to_files(&mut self) {
-    self.write_users().unwrap();
-    self.write_clients().unwrap();
-    self.write_goals().unwrap();
-    self.write_collaterals().unwrap();
-    self.write_general_collaterals().unwrap();
-    self.write_pronouns().unwrap();
-    self.write_note_days().unwrap();
-    self.write_note_templates().unwrap();
-    self.write_notes().unwrap();
+  pub fn write_to_files(&mut self) {
+    let _write_guard = lock_file_writes();
+    self.write_archive().unwrap();
   }
-  fn encrypt_all_files(&self, pw: &str) -> Result<(), Error> {
-    match Self::read_users(&self.filepaths["user_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["user_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_clients(&self.filepaths["client_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["client_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_goals(&self.filepaths["goal_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["goal_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_collaterals(&self.filepaths["collateral_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["collateral_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_general_collaterals(&self.filepaths["general_collateral_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["general_collateral_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_pronouns_from_file_without_reindexing(&self.filepaths["pronouns_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["pronouns_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_note_days(&self.filepaths["note_day_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["note_day_filepath"], pw)?,
-      Err(_) => (),
-    }
-    match Self::read_note_templates(&self.filepaths["note_template_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["note_template_filepath"], pw)?,
-      Err(_) => (),
+  pub fn write_archive(&mut self) -> Result<(), Error> {
+    self.delete_duplicate_pronouns();
+    self.update_next_ids();
+    let archive_filepath = &self.filepaths["archive_filepath"];
+    let json = self.to_archive_file().to_json()?;
+    backup_if_due(archive_filepath, json.as_bytes(), self.backup_count, Local::now().naive_local())?;
+    write_file_atomically(archive_filepath, json.as_bytes())
+  }
+  pub fn to_archive_file(&self) -> ArchiveFile {
+    ArchiveFile {
+      schema_version: ARCHIVE_SCHEMA_VERSION,
+      users: self.users.clone(),
+      clients: self.clients.clone(),
+      goals: self.goals.clone(),
+      collaterals: self.collaterals.clone(),
+      general_collaterals: self.general_collaterals.clone(),
+      pronouns: self.pronouns.clone(),
+      note_days: self.note_days.clone(),
+      note_templates: self.note_templates.clone(),
+      notes: self.notes.clone(),
+      next_ids: self.next_ids.clone(),
+    }
+  }
+  fn ids_in(&self, list: &str) -> Vec<u32> {
+    match list {
+      "users" => self.users.iter().map(|u| u.id ).collect(),
+      "clients" => self.clients.iter().map(|c| c.id ).collect(),
+      "goals" => self.goals.iter().map(|g| g.id ).collect(),
+      "collaterals" => self.collaterals.iter().map(|co| co.id ).collect(),
+      "general_collaterals" => self.general_collaterals.iter().map(|co| co.id ).collect(),
+      "pronouns" => self.pronouns.iter().map(|p| p.id ).collect(),
+      "note_days" => self.note_days.iter().map(|nd| nd.id ).collect(),
+      "note_templates" => self.note_templates.iter().map(|nt| nt.id ).collect(),
+      "notes" => self.notes.iter().map(|n| n.id ).collect(),
+      _ => panic!("Unknown list '{}' passed to fn 'ids_in'.", list),
+    }
+  }
+  /// IDs are never reused, so a new record gets an ID above any that has been saved, even if that record was deleted
+  pub fn next_id(&self, list: &str) -> u32 {
+    let after_highest = self.ids_in(list).into_iter().max().map_or(1, |id| id + 1);
+    after_highest.max(self.next_ids.get(list).copied().unwrap_or(1))
+  }
+  fn update_next_ids(&mut self) {
+    for list in ID_LISTS {
+      let next = self.next_id(list);
+      self.next_ids.insert(String::from(list), next);
+    }
+  }
+  /// every data file that exists, including backups of the archive, with a check that it can be read as plain text
+  fn data_files(filepaths: &HashMap<String, String>) -> Vec<(String, fn(&str) -> Result<(), Error>)> {
+    let checks: [(&str, fn(&str) -> Result<(), Error>); 10] = [
+      ("archive_filepath", ArchiveFile::check_readable),
+      ("user_filepath", |fp| Self::read_users(fp).map(|_| ()) ),
+      ("client_filepath", |fp| Self::read_clients(fp).map(|_| ()) ),
+      ("goal_filepath", |fp| Self::read_goals(fp).map(|_| ()) ),
+      ("collateral_filepath", |fp| Self::read_collaterals(fp).map(|_| ()) ),
+      ("general_collateral_filepath", |fp| Self::read_general_collaterals(fp).map(|_| ()) ),
+      ("pronouns_filepath", |fp| Self::read_pronouns_from_file_without_reindexing(fp).map(|_| ()) ),
+      ("note_day_filepath", |fp| Self::read_note_days(fp).map(|_| ()) ),
+      ("note_template_filepath", |fp| Self::read_note_templates(fp).map(|_| ()) ),
+      ("note_filepath", |fp| Self::read_notes(fp).map(|_| ()) ),
+    ];
+    let mut files: Vec<(String, fn(&str) -> Result<(), Error>)> = checks.iter()
+      .filter_map(|(key, check)| filepaths.get(*key).map(|fp| (fp.clone(), *check) ) )
+      .filter(|(fp, _)| Path::new(fp).exists() )
+      .collect();
+    if let Some(archive_filepath) = filepaths.get("archive_filepath") {
+      for backup in list_backups(archive_filepath).unwrap_or_default() {
+        files.push((backup.path.to_string_lossy().to_string(), ArchiveFile::check_readable));
+      }
     }
-    match Self::read_notes(&self.filepaths["note_filepath"]) {
-      Ok(_) => encrypt_file(&self.filepaths["note_filepath"], pw)?,
-      Err(_) => (),
+    files
+  }
+  fn encrypt_all_files(&self, pw: &str) -> Result<(), Error> {
+    let _write_guard = lock_file_writes();
+    Self::encrypt_files_at(&self.filepaths, pw)
+  }
+  /// encrypts every data file that is currently readable, leaving files that are already encrypted alone
+  pub fn encrypt_files_at(filepaths: &HashMap<String, String>, pw: &str) -> Result<(), Error> {
+    for (fp, check_readable) in Self::data_files(filepaths) {
+      if check_readable(&fp).is_ok() {
+        encrypt_file(&fp, pw)?;
+      }
     }
     Ok(())
   }
-  fn decrypt_all_files(
-    user_filepath: &str,
-    client_filepath: &str,
-    goal_filepath: &str,
-    collateral_filepath: &str,
-    general_collateral_filepath: &str,
-    pronouns_filepath: &str,
-    note_day_filepath: &str,
-    note_template_filepath: &str,
-    note_filepath: &str,
-    pw: &str) -> Result<(), Error> {
-    decrypt_file(user_filepath, "decrypt_attempt_user.txt", pw)?;
-    decrypt_file(client_filepath, "decrypt_attempt_client.txt", pw)?;
-    decrypt_file(goal_filepath, "decrypt_attempt_goal.txt", pw)?;
-    decrypt_file(collateral_filepath, "decrypt_attempt_collateral.txt", pw)?;
-    decrypt_file(general_collateral_filepath, "decrypt_attempt_general_collateral.txt", pw)?;
-    decrypt_file(pronouns_filepath, "decrypt_attempt_pronouns.txt", pw)?;
-    decrypt_file(note_day_filepath, "decrypt_attempt_note_day.txt", pw)?;
-    decrypt_file(note_template_filepath, "decrypt_attempt_note_template.txt", pw)?;
-    decrypt_file(note_filepath, "decrypt_attempt_note.txt", pw)?;
-    let user_result = Self::read_users("decrypt_attempt_user.txt");
-    let client_result = Self::read_clients("decrypt_attempt_client.txt");
-    let goal_result = Self::read_goals("decrypt_attempt_goal.txt");
-    let collateral_result = Self::read_collaterals("decrypt_attempt_collateral.txt");
-    let general_collateral_result = Self::read_general_collaterals("decrypt_attempt_collateral.txt");
-    let pronouns_result = Self::read_pronouns_from_file_without_reindexing("decrypt_attempt_pronouns.txt");
-    let note_day_result = Self::read_note_days("decrypt_attempt_note_day.txt");
-    let note_template_result = Self::read_note_templates("decrypt_attempt_note_template.txt");
-    let note_result = Self::read_notes("decrypt_attempt_note.txt");
-    fs::remove_file("decrypt_attempt_user.txt")?;
-    fs::remove_file("decrypt_attempt_client.txt")?;
-    fs::remove_file("decrypt_attempt_collateral.txt")?;
-    fs::remove_file("decrypt_attempt_pronouns.txt")?;
-    fs::remove_file("decrypt_attempt_note_day.txt")?;
-    fs::remove_file("decrypt_attempt_note_template.txt")?;
-    fs::remove_file("decrypt_attempt_note.txt")?;
-    match (
-      user_result,
-      client_result,
-      goal_result,
-      collateral_result,
-      general_collateral_result,
-      pronouns_result,
-      note_day_result,
-      note_template_result,
-      note_result
-    ) {
-      (Ok(_), Ok(_), Ok(_), Ok(_), Ok(_), Ok(_), Ok(_), Ok(_), Ok(_)) => {
-        decrypt_file(user_filepath, user_filepath, pw)?;
-        decrypt_file(client_filepath, client_filepath, pw)?;
-        decrypt_file(goal_filepath, goal_filepath, pw)?;
-        decrypt_file(collateral_filepath, collateral_filepath, pw)?;
-        decrypt_file(general_collateral_filepath, general_collateral_filepath, pw)?;
-        decrypt_file(pronouns_filepath, pronouns_filepath, pw)?;
-        decrypt_file(note_day_filepath, note_day_filepath, pw)?;
-        decrypt_file(note_template_filepath, note_template_filepath, pw)?;
-        decrypt_file(note_filepath, note_filepath, pw)?;
-        Ok(())
-      },
-      _ => Err(Error::new(
-        ErrorKind::Other,
-        "Failed to decrypt files with the given password.",
-      )),
+  /// decrypts every data file in place, returning whether any were in the legacy format;
+  /// nothing is written unless every file decrypts successfully
+  fn decrypt_all_files(filepaths: &HashMap<String, String>, pw: &str) -> Result<bool, Error> {
+    let mut legacy_found = false;
+    let mut decrypted: Vec<(String, Vec<u8>)> = vec![];
+    for (fp, check_readable) in Self::data_files(filepaths) {
+      let data = fs::read(&fp)?;
+      if is_encrypted_container(&data) {
+        decrypted.push((fp, decrypt_bytes(&data, pw)?));
+      } else if data.is_empty() || check_readable(&fp).is_ok() {
+        // already plain text, for example a file that was empty when the archive was encrypted
+        continue;
+      } else {
+        // the legacy format cannot detect a wrong password, so check that the output can be read
+        let attempt_fp = format!("{}.decrypt_attempt", fp);
+        let plaintext = decrypt_legacy_bytes(&data, pw);
+        fs::write(&attempt_fp, &plaintext)?;
+        let readable = check_readable(&attempt_fp);
+        fs::remove_file(&attempt_fp)?;
+        if readable.is_err() {
+          return Err(wrong_password_error());
+        }
+        legacy_found = true;
+        decrypted.push((fp, plaintext));
+      }
+    }
+    for (fp, plaintext) in decrypted {
+      write_file_atomically(fp, &plaintext)?;
     }
+    Ok(legacy_found)
   }
   fn display_actions(&self) {
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
@@ -802,10 +1132,18 @@ impl NoteArchive {
     let heading_with_spaces = format!(" Notes archive for {} ", self.current_user().name_and_title()); 
     println_on_bg!("{:-^58}", heading_with_spaces);
     println_on_bg!("{:-^58}", "-");
+
+    self.display_caseload();
+    self.display_compliance_alerts();
     
     println_on_bg!("{: >15} | {: <40}", " NOTE / N ", " Write, view, and edit note records ");
     println_on_bg!("{: >15} | {: <40}", " CLIENT / C ", " View/edit client records ");
     println_on_bg!("{: >15} | {: <40}", " COL / CO ", " View/edit collateral records ");
+    println_on_bg!("{: >15} | {: <40}", " SEARCH / SE ", " Search notes, templates and collaterals ");
+    println_on_bg!("{: >15} | {: <40}", " FILTER / FI ", " List notes by client, collateral, type or date ");
+    println_on_bg!("{: >15} | {: <40}", " BILLING / B ", " Monthly billing units report ");
+    println_on_bg!("{: >15} | {: <40}", " MILEAGE / MI ", " Mileage log for a pay period ");
+    println_on_bg!("{: >15} | {: <40}", " DEADLINES / DL ", " Overdue and upcoming deadlines ");
     
     println_on_bg!("{:-^58}", "-");
     
@@ -821,141 +1159,430 @@ impl NoteArchive {
 
     println_on_bg!("{:-^58}", "-");
   }
-  fn sort_data_by_dates(&mut self) {
-    let current_collateral_id: Option<u32> = self.foreign_key.get("current_collateral_id").map_or(None, |v| Some(*v) );
-    let current_note_day_id: Option<u32> = self.foreign_key.get("current_note_day_id").map_or(None, |v| Some(*v) );
-    let current_note_id: Option<u32> = self.foreign_key.get("current_note_id").map_or(None, |v| Some(*v) );
-    self.sort_collaterals();
-    let new_collateral_id = self.reindex_collaterals(current_collateral_id);
-    self.note_days.sort_by(|a, b| b.date.cmp(&a.date) );
-    let new_note_day_id = self.reindex_note_days(current_note_day_id);
-    self.notes.sort_by(|a, b| b.date.cmp(&a.date) );
-    let new_note_id = self.reindex_notes(current_note_id);
-    let new_collateral_id_return_option = match new_collateral_id {
-      Some(collateral_id) => self.foreign_key.insert(String::from("current_collateral_id"), collateral_id),
-      None => None,
-    };
-    match new_collateral_id_return_option {
-      _ => (),
-    }
-    let new_note_day_id_return_option = match new_note_day_id {
-      Some(note_day_id) => {
-        let current_client = self.get_client_by_note_day_id(note_day_id).unwrap().clone();
-        self.foreign_key.insert(String::from("current_client_id"), current_client.id);
-        self.foreign_key.insert(String::from("current_note_day_id"), note_day_id)
-      }
-      None => None,
-    };
-    match new_note_day_id_return_option {
-      _ => (),
-    }
-    let new_note_id_return_option = match new_note_id {
-      Some(note_id) => self.foreign_key.insert(String::from("current_note_id"), note_id),
-      None => None,
-    };
-    match new_note_id_return_option {
-      _ => (),
-    }
-  }
-  fn logged_in_action(&mut self) {
+  fn choose_billing_report(&self) {
+    let settings = Config::load().unwrap_or_default().billing_settings();
+    let today = Local::now().naive_local().date();
+    let (mut first, mut last) = month_bounds(today.year(), today.month()).unwrap();
     loop {
-      self.sort_data_by_dates();
-      self.display_actions();
-
+      let report = BillingReport::new(self, first, last, Some(self.current_user().id), None, &settings);
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_on_bg!("{:-^58}", " Billing units ");
+      println!("{}", report.to_display_string());
+      println_inst!(
+        "| {} | {} | {}",
+        "MONTH / M: choose another month",
+        "CSV / S: save as CSV",
+        "QUIT / Q: quit menu",
+      );
       let mut choice = String::new();
-      let choice_attempt = io::stdin().read_line(&mut choice);
-      match choice_attempt {
-        Ok(_) => (),
-        Err(e) => {
-          println_err!("Failed to read input: {}.", e);
-        }
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
       }
-      choice = choice.to_ascii_lowercase().trim().to_string();
-      match &choice[..] {
-        "note" | "n" => {
-          self.choose_note_days();
-        }
-        "client" | "c" => {
-          self.choose_clients();
-        },
-        "collateral" | "co" | "col" => {
-          self.choose_collaterals();
-        },
-        "edit" | "e" => {
-          self.choose_edit_user();
-        },
-        "user" | "u" => {
-          self.foreign_key.remove("current_user_id");
-          self.foreign_key.remove("current_client_id");
-          self.foreign_key.remove("current_collateral_id");
-          self.foreign_key.remove("current_note_id");
-          self.foreign_key.remove("current_note_day_id");
-          self.foreign_key.remove("current_note_template_id");
-          self.foreign_key.remove("current_goal_id");
-          match self.choose_user() {
-            Some(_) => (),
-            None => break,
-          }
-        },
-        "p" | "prns" | "pronouns" => {
-          let chosen_pronoun = self.choose_pronouns_option();
-          match chosen_pronoun {
-            Some(prn) => {
-              self.view_pronoun(prn);
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        "month" | "m" => {
+          println_inst!("Enter a month as YYYY-MM.");
+          let mut month_choice = String::new();
+          let _ = read_input_line(&mut month_choice);
+          match parse_month(month_choice.trim()) {
+            Ok((f, l)) => {
+              first = f;
+              last = l;
+            },
+            Err(e) => {
+              println_err!("{}", e);
+              thread::sleep(time::Duration::from_secs(2));
             },
-            None => (),
-          }
-        },
-        "delete" | "d" => {
-          match self.choose_delete_user() {
-            Some(_) => {
-              loop {
-                match self.choose_user() {
-                  _ => break,
-                }
-
-              }
-            }
-            None => (),
           }
         },
-        "security" | "s" => {
-          self.choose_security_options();
-          if self.encrypted {
-            break;
+        "csv" | "s" => {
+          let fp = export_dir(&self.filepaths["archive_filepath"])
+            .join(format!("billing_{}.csv", first.format("%Y-%m")));
+          match fs::create_dir_all(fp.parent().unwrap()).and_then(|_| write_file_atomically(&fp, report.to_csv().as_bytes()) ) {
+            Ok(_) => println_suc!("Saved {}", fp.display()),
+            Err(e) => println_err!("Failed to save {}: {}", fp.display(), e),
           }
-        },
-        "quit" | "q" => {
-          break ();
+          thread::sleep(time::Duration::from_secs(2));
         },
         _ => {
           println_err!("Invalid command.");
           thread::sleep(time::Duration::from_secs(1));
         },
       }
-      self.write_to_files();
     }
   }
-  fn choose_security_options(&mut self) {
-    self.display_security_options();
+  fn choose_mileage_log(&self) {
+    let pay_period = Config::load().unwrap_or_default().pay_period();
+    let mut date = Local::now().naive_local().date();
     loop {
+      let (first, last) = pay_period.containing(date);
+      let log = MileageLog::new(self, self.current_user().id, first, last);
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_on_bg!("{:-^58}", " Mileage ");
+      println!("{}", log.to_display_string());
+      println_inst!(
+        "| {} | {} | {} | {}",
+        "PREVIOUS / P: previous pay period",
+        "NEXT / N: next pay period",
+        "CSV / S: save as CSV",
+        "QUIT / Q: quit menu",
+      );
       let mut choice = String::new();
-      let choice_attempt = io::stdin().read_line(&mut choice);
-      match choice_attempt {
-        Ok(_) => (),
-        Err(e) => {
-          println_err!("Failed to read input: {}.", e);
-          continue;
-        }
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
       }
-      choice = choice.to_ascii_lowercase().trim().to_string();
-      match &choice[..] {
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        "previous" | "p" => date = first.pred_opt().unwrap_or(first),
+        "next" | "n" => date = last.succ_opt().unwrap_or(last),
+        "csv" | "s" => {
+          let fp = export_dir(&self.filepaths["archive_filepath"])
+            .join(format!("mileage_{}_to_{}.csv", first.format("%Y-%m-%d"), last.format("%Y-%m-%d")));
+          match fs::create_dir_all(fp.parent().unwrap()).and_then(|_| write_file_atomically(&fp, log.to_csv().as_bytes()) ) {
+            Ok(_) => println_suc!("Saved {}", fp.display()),
+            Err(e) => println_err!("Failed to save {}: {}", fp.display(), e),
+          }
+          thread::sleep(time::Duration::from_secs(2));
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+    }
+  }
+  /// the current user's clients, most urgent first, with overdue contacts highlighted
+  fn display_caseload(&self) {
+    let today = Local::now().naive_local().date();
+    let contact_due_days = Config::load().unwrap_or_default().contact_due_days();
+    let rows = caseload_rows(self, self.current_user(), today, contact_due_days);
+    if rows.is_empty() {
+      return;
+    }
+    println_on_bg!("{:-^104}", " Caseload ");
+    println_on_bg!(
+      "{:-^25} | {:-^12} | {:-^30} | {:-^10} | {:-^16}",
+      " Client ", " Last day ", " Last contact ", " Unfilled ", " Next contact ",
+    );
+    for row in rows {
+      let line = format!(
+        "{: <25} | {: <12} | {: <30} | {: ^10} | {: <16}",
+        row.client_name,
+        row.fmt_days_since(today),
+        row.last_contact.clone().unwrap_or_else(|| String::from("-") ),
+        row.notes_with_unfilled_blanks,
+        row.fmt_due(today),
+      );
+      if row.is_overdue(today) {
+        println_err!("{}", line);
+      } else if row.notes_with_unfilled_blanks > 0 {
+        println_yel!("{}", line);
+      } else {
+        println_on_bg!("{}", line);
+      }
+    }
+    println_on_bg!("{:-^104}", "-");
+  }
+  fn current_compliance_alerts(&self, today: NaiveDate) -> Vec<ComplianceAlert> {
+    let rules = Config::load().unwrap_or_default().compliance_rules();
+    compliance_alerts(self, &self.current_user().foreign_keys["client_ids"], &rules, today)
+  }
+  /// the most pressing deadlines for the current user's clients, below the caseload
+  fn display_compliance_alerts(&self) {
+    let today = Local::now().naive_local().date();
+    let alerts = self.current_compliance_alerts(today);
+    if alerts.is_empty() {
+      return;
+    }
+    println_on_bg!("{:-^104}", " Deadlines ");
+    for alert in alerts.iter().take(MAX_MENU_ALERTS) {
+      match alert.status {
+        DeadlineStatus::Overdue => println_err!("{}", alert.description(today)),
+        DeadlineStatus::Upcoming => println_yel!("{}", alert.description(today)),
+      }
+    }
+    if alerts.len() > MAX_MENU_ALERTS {
+      println_inst!("...and {} more (DEADLINES / DL to see all)", alerts.len() - MAX_MENU_ALERTS);
+    }
+    println_on_bg!("{:-^104}", "-");
+  }
+  fn choose_compliance_report(&self) {
+    loop {
+      let today = Local::now().naive_local().date();
+      let alerts = self.current_compliance_alerts(today);
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_on_bg!("{:-^58}", " Deadlines ");
+      if alerts.is_empty() {
+        println!("No overdue or upcoming deadlines.");
+      }
+      for alert in &alerts {
+        match alert.status {
+          DeadlineStatus::Overdue => println_err!("{}", alert.description(today)),
+          DeadlineStatus::Upcoming => println_yel!("{}", alert.description(today)),
+        }
+      }
+      println!();
+      println_inst!("| {} | {}", "CSV / S: save as CSV", "QUIT / Q: quit menu");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        "csv" | "s" => {
+          let fp = export_dir(&self.filepaths["archive_filepath"])
+            .join(format!("deadlines_{}.csv", today.format("%Y-%m-%d")));
+          match fs::create_dir_all(fp.parent().unwrap()).and_then(|_| write_file_atomically(&fp, compliance_alerts_csv(&alerts).as_bytes()) ) {
+            Ok(_) => println_suc!("Saved {}", fp.display()),
+            Err(e) => println_err!("Failed to save {}: {}", fp.display(), e),
+          }
+          thread::sleep(time::Duration::from_secs(2));
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+    }
+  }
+  fn choose_search(&self) {
+    let index = SearchIndex::new(self, Some(self.current_user().id));
+    loop {
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_on_bg!("{:-^58}", " Search ");
+      println_inst!("Enter words to search for, with \"quotes\" around phrases.");
+      println_inst!("Narrow the search with client:ID, since:YYYY-MM-DD, until:YYYY-MM-DD or category:NAME.");
+      println_inst!("| {}", "QUIT / Q: quit menu");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        _ => match SearchQuery::parse(choice.trim()) {
+          Ok(query) => self.display_search_results(&index.search(&query)),
+          Err(e) => {
+            println_err!("{}", e);
+            thread::sleep(time::Duration::from_secs(2));
+          },
+        },
+      }
+    }
+  }
+  fn display_search_results(&self, results: &[SearchResult]) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_on_bg!("{:-^58}", format!(" {} results ", results.len()));
+    for r in results {
+      println_on_bg!("{} | {}", r.document.record, r.document.title);
+      for (piece, is_match) in r.snippet.pieces() {
+        if is_match {
+          print_highlighted_content!("{}", piece);
+        } else {
+          print!("{}", piece);
+        }
+      }
+      println!();
+      println!();
+    }
+    println_inst!("Press ENTER to search again.");
+    let mut s = String::new();
+    let _ = read_input_line(&mut s);
+  }
+  /// lists the current user's notes that meet conditions typed in the same form as NoteFilter::parse
+  fn choose_note_filter(&self) {
+    let mut filter = NoteFilter { user_id: Some(self.current_user().id), ..NoteFilter::default() };
+    loop {
+      self.display_filtered_notes(&filter);
+      println_inst!("Enter conditions to list notes, separated by spaces:");
+      println_inst!("client:ID collateral:ID structure:NAME category:NAME since:YYYY-MM-DD until:YYYY-MM-DD last:DAYS unfilled:yes/no");
+      println_inst!("| {} | {}", "ALL / A: clear conditions", "QUIT / Q: quit menu");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        "all" | "a" => filter = NoteFilter { user_id: Some(self.current_user().id), ..NoteFilter::default() },
+        _ => match NoteFilter::parse(choice.trim(), Local::now().naive_local().date()) {
+          Ok(f) => filter = NoteFilter { user_id: Some(self.current_user().id), ..f },
+          Err(e) => {
+            println_err!("{}", e);
+            thread::sleep(time::Duration::from_secs(2));
+          },
+        },
+      }
+    }
+  }
+  fn display_filtered_notes(&self, filter: &NoteFilter) {
+    let notes = self.filter_notes(filter);
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_on_bg!("{:-^146}", "-");
+    println_on_bg!("{:-^146}", format!(" {} notes: {} ", notes.len(), filter.description()));
+    println_on_bg!("{:-^146}", "-");
+    println_on_bg!("{:-^10} | {:-^12} | {:-^25} | {:-^30} | {:-^30} | {:-^25}", " ID ", " Date ", " Client ", " Category ", " Structure ", " Preview ");
+    for n in notes.iter().rev() {
+      let client_name = self.get_client_by_id(n.foreign_key["client_id"]).map(|c| c.full_name() ).unwrap_or_default();
+      let line = format!(
+        "{: ^10} | {: <12} | {: <25} | {: <30} | {: <30} | {: <25}",
+        n.id,
+        n.fmt_date(),
+        client_name,
+        n.category.to_string(),
+        n.structure.to_string(),
+        n.preview(),
+      );
+      if n.has_unfilled_blanks() {
+        println_yel!("{}", line);
+      } else {
+        println_on_bg!("{}", line);
+      }
+    }
+    println_on_bg!("{:-^146}", "-");
+  }
+  /// orders records for display; IDs are permanent, so nothing that refers to a record changes
+  fn sort_data_by_dates(&mut self) {
+    self.sort_collaterals();
+    self.note_days.sort_by(|a, b| b.date.cmp(&a.date) );
+    self.notes.sort_by(|a, b| b.date.cmp(&a.date) );
+  }
+  fn logged_in_action(&mut self) {
+    loop {
+      self.sort_data_by_dates();
+      self.display_actions();
+
+      let mut choice = String::new();
+      let choice_attempt = read_input_line(&mut choice);
+      match choice_attempt {
+        Ok(_) => (),
+        Err(e) => {
+          println_err!("Failed to read input: {}.", e);
+        }
+      }
+      choice = choice.to_ascii_lowercase().trim().to_string();
+      match &choice[..] {
+        "note" | "n" => {
+          self.choose_note_days();
+        }
+        "client" | "c" => {
+          self.choose_clients();
+        },
+        "collateral" | "co" | "col" => {
+          self.choose_collaterals();
+        },
+        "billing" | "b" => {
+          self.choose_billing_report();
+        },
+        "mileage" | "mi" => {
+          self.choose_mileage_log();
+        },
+        "deadlines" | "dl" => {
+          self.choose_compliance_report();
+        },
+        "search" | "se" => {
+          self.choose_search();
+        },
+        "filter" | "fi" => {
+          self.choose_note_filter();
+        },
+        "edit" | "e" => {
+          self.choose_edit_user();
+        },
+        "user" | "u" => {
+          self.foreign_key.remove("current_user_id");
+          self.foreign_key.remove("current_client_id");
+          self.foreign_key.remove("current_collateral_id");
+          self.foreign_key.remove("current_note_id");
+          self.foreign_key.remove("current_note_day_id");
+          self.foreign_key.remove("current_note_template_id");
+          self.foreign_key.remove("current_goal_id");
+          match self.choose_user() {
+            Some(_) => (),
+            None => break,
+          }
+        },
+        "p" | "prns" | "pronouns" => {
+          let chosen_pronoun = self.choose_pronouns_option();
+          match chosen_pronoun {
+            Some(prn) => {
+              self.view_pronoun(prn);
+            },
+            None => (),
+          }
+        },
+        "delete" | "d" => {
+          match self.choose_delete_user() {
+            Some(_) => {
+              loop {
+                match self.choose_user() {
+                  _ => break,
+                }
+
+              }
+            }
+            None => (),
+          }
+        },
+        "security" | "s" => {
+          self.choose_security_options();
+          if self.encrypted {
+            break;
+          }
+        },
+        "quit" | "q" => {
+          break ();
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+      self.write_to_files();
+    }
+  }
+  fn choose_security_options(&mut self) {
+    loop {
+      self.display_security_options();
+      let mut choice = String::new();
+      let choice_attempt = read_input_line(&mut choice);
+      match choice_attempt {
+        Ok(_) => (),
+        Err(e) => {
+          println_err!("Failed to read input: {}.", e);
+          continue;
+        }
+      }
+      choice = choice.to_ascii_lowercase().trim().to_string();
+      match &choice[..] {
         "encrypt" => {
+          if self.password.is_some() {
+            println_err!("Files are already protected with a password. Use PASSWORD to change it.");
+            thread::sleep(time::Duration::from_secs(2));
+            continue;
+          }
           self.choose_encrypt_all_files();
           break;
         },
+        "password" | "pw" => {
+          self.choose_change_password();
+        },
+        "decrypt" => {
+          self.choose_remove_encryption();
+        },
+        "lock" | "l" => {
+          if self.choose_lock_files() {
+            break;
+          }
+        },
+        "idle" | "i" => {
+          self.choose_idle_lock_minutes();
+        },
         "quit" | "q" => {
-          break ();
+          break;
         },
         _ => {
           println_err!("Invalid command.");
@@ -971,10 +1598,41 @@ impl NoteArchive {
     println_on_bg!("{:-^58}", "-");
     println_on_bg!("{:-^58}", " Security ");
     println_on_bg!("{:-^58}", "-");
+    match self.password {
+      Some(_) => println_on_bg!("{:-^58}", " Files are encrypted when the program closes "),
+      None => println_on_bg!("{:-^58}", " Files are not encrypted "),
+    }
+    match self.idle_lock_minutes {
+      0 => println_on_bg!("{:-^58}", " Lock on idle: off "),
+      m => println_on_bg!("{:-^58}", format!(" Lock on idle: after {} minutes ", m)),
+    }
+    println_on_bg!("{:-^58}", "-");
     
+    match self.password {
+      Some(_) => {
+        println_on_bg!(
+          "{: >15} | {: <40}",
+          " PASSWORD / PW ", " Change the password "
+        );
+        println_on_bg!(
+          "{: >15} | {: <40}",
+          " LOCK / L ", " Encrypt all files now and close the program "
+        );
+        println_on_bg!(
+          "{: >15} | {: <40}",
+          " DECRYPT ", " Remove the password and leave files unencrypted (for export) "
+        );
+      },
+      None => {
+        println_on_bg!(
+          "{: >15} | {: <40}",
+          " ENCRYPT ", " Encrypt all files and protect with a password | WARNING: data cannot be recovered if password is lost. "
+        );
+      },
+    }
     println_on_bg!(
       "{: >15} | {: <40}",
-      " ENCRYPT ", " Encrypt all files and protect with a password | WARNING: cannot be reversed if password is lost. "
+      " IDLE / I ", " Set minutes without input before files are locked "
     );
     println_on_bg!(
       "{: >15} | {: <40}",
@@ -983,64 +1641,228 @@ impl NoteArchive {
     
     println_on_bg!("{:-^58}", "-");
   }
-  fn choose_encrypt_all_files(&mut self) {
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    println_yel!("If you forget your password, accessing this program's data will be impossible.");
-    println_yel!("This applies to all data for all users and clients associated with this program on your computer.");
-    println_yel!("Encryption is not and is not intended to be HIPPA compliant.");
-    println_yel!("By continuing, you agree that the responsibility to store and transfer data privately and securely is entirely your own.");
-    println_yel!("To cancel and return to the program, enter QUIT / Q.");
-    println_yel!("To continue with secure encryption, enter YES / Y.");
-
+  /// starts, updates or stops the idle lock to match the current password and timeout
+  fn update_idle_lock(&self) {
+    let settings = match (&self.password, self.idle_lock_minutes) {
+      (Some(pw), minutes) if minutes > 0 => Some(IdleLockSettings {
+        limit: time::Duration::from_secs(minutes * 60),
+        filepaths: self.filepaths.clone(),
+        password: pw.clone(),
+      }),
+      _ => None,
+    };
+    set_idle_lock(settings);
+  }
+  /// re-encrypts the files when the program closes if the archive has a password
+  fn lock_on_exit(&mut self) {
+    set_idle_lock(None);
+    if self.encrypted {
+      return;
+    }
+    if let Some(pw) = self.password.clone() {
+      match self.encrypt_all_files(&pw) {
+        Ok(_) => println_suc!("Files locked."),
+        Err(e) => println_err!("Failed to encrypt files: {}", e),
+      }
+    }
+  }
+  fn clear_data(&mut self) {
+    self.users.clear();
+    self.clients.clear();
+    self.goals.clear();
+    self.collaterals.clear();
+    self.general_collaterals.clear();
+    self.pronouns.clear();
+    self.note_days.clear();
+    self.note_templates.clear();
+    self.notes.clear();
+    self.foreign_key.clear();
+    self.foreign_keys.clear();
+    self.password = None;
+  }
+  /// asks for the current password, returning false if it was not entered correctly
+  fn confirm_current_password(&self) -> bool {
+    let current = match &self.password {
+      Some(pw) => pw,
+      None => {
+        println_err!("Files are not protected with a password. Use ENCRYPT to add one.");
+        thread::sleep(time::Duration::from_secs(2));
+        return false;
+      },
+    };
+    println_inst!("Enter current password:");
+    let mut entered = String::new();
+    match read_input_line(&mut entered) {
+      Ok(_) => (),
+      Err(e) => {
+        println_err!("Failed to read input: {}.", e);
+        thread::sleep(time::Duration::from_secs(2));
+        return false;
+      }
+    }
+    if entered.trim() != current {
+      println_err!("Incorrect password.");
+      thread::sleep(time::Duration::from_secs(2));
+      return false;
+    }
+    true
+  }
+  fn read_new_password() -> String {
     loop {
+      println_inst!("Enter new password for encryption (minimum 8 characters):");
       let mut choice = String::new();
-      let choice_attempt = io::stdin().read_line(&mut choice);
+      let choice_attempt = read_input_line(&mut choice);
       match choice_attempt {
-        Ok(_) => (),
+        Ok(_) => {
+          if choice.trim().len() < 8 {
+            println_err!("Password not long enough.");
+            continue;
+          } else {
+            println_inst!("Confirm password:");
+            let mut confirm = String::new();
+            let confirm_attempt = read_input_line(&mut confirm);
+            match confirm_attempt {
+              Ok(_) => {
+                if confirm.trim() != choice.trim() {
+                  println_err!("Passwords do not match.");
+                  continue;
+                } else {
+                  break confirm.trim().to_string()
+                }
+              },
+              Err(e) => {
+                println_err!("Passwords do not match (error: {})", e);
+                continue;
+              }
+            }
+            
+          }
+        },
         Err(e) => {
           println_err!("Failed to read input: {}.", e);
           continue;
         }
       }
-      choice = choice.to_ascii_lowercase().trim().to_string();
+    }
+  }
+  fn choose_change_password(&mut self) {
+    if !self.confirm_current_password() {
+      return;
+    }
+    let new_password = Self::read_new_password();
+    self.password = Some(new_password);
+    self.update_idle_lock();
+    println_suc!("Password changed. Files will be encrypted with the new password when they are locked.");
+    thread::sleep(time::Duration::from_secs(2));
+  }
+  fn choose_remove_encryption(&mut self) {
+    if !self.confirm_current_password() {
+      return;
+    }
+    println_yel!("Files will be left unencrypted on this computer when the program closes.");
+    println_yel!("To continue, enter YES / Y. To cancel, enter anything else.");
+    let mut choice = String::new();
+    match read_input_line(&mut choice) {
+      Ok(_) => (),
+      Err(e) => {
+        println_err!("Failed to read input: {}.", e);
+        thread::sleep(time::Duration::from_secs(2));
+        return;
+      }
+    }
+    if matches!(choice.to_ascii_lowercase().trim(), "yes" | "y") {
+      self.password = None;
+      self.update_idle_lock();
+      println_suc!("Password removed. Files are not encrypted.");
+      thread::sleep(time::Duration::from_secs(2));
+    }
+  }
+  /// encrypts the files and clears all data from memory, returning true if the program should close
+  fn choose_lock_files(&mut self) -> bool {
+    let pw = match &self.password {
+      Some(pw) => pw.clone(),
+      None => {
+        println_err!("Files are not protected with a password. Use ENCRYPT to add one.");
+        thread::sleep(time::Duration::from_secs(2));
+        return false;
+      },
+    };
+    self.write_to_files();
+    set_idle_lock(None);
+    match self.encrypt_all_files(&pw) {
+      Ok(_) => (),
+      Err(e) => {
+        println_err!("Failed to encrypt files: {}", e);
+        self.update_idle_lock();
+        thread::sleep(time::Duration::from_secs(2));
+        return false;
+      },
+    }
+    self.clear_data();
+    self.encrypted = true;
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_suc!("Files locked. Run the program again and enter your password to unlock.");
+    true
+  }
+  fn choose_idle_lock_minutes(&mut self) {
+    println_inst!("Enter minutes without input before files are locked (0 to turn off):");
+    let mut choice = String::new();
+    match read_input_line(&mut choice) {
+      Ok(_) => (),
+      Err(e) => {
+        println_err!("Failed to read input: {}.", e);
+        thread::sleep(time::Duration::from_secs(2));
+        return;
+      }
+    }
+    let minutes = match choice.trim().parse::<u64>() {
+      Ok(m) => m,
+      Err(_) => {
+        println_err!("Please enter a whole number of minutes.");
+        thread::sleep(time::Duration::from_secs(2));
+        return;
+      }
+    };
+    self.idle_lock_minutes = minutes;
+    self.update_idle_lock();
+    let saved = Config::load().map_err(Error::other).and_then(|mut config| {
+      config.idle_lock_minutes = Some(minutes);
+      config.save()
+    });
+    match saved {
+      Ok(_) => {
+        if self.password.is_none() && minutes > 0 {
+          println_yel!("Lock on idle only applies after files are protected with a password.");
+        }
+        println_suc!("Lock on idle updated.");
+      },
+      Err(e) => println_err!("Lock on idle updated for this session, but the setting could not be saved: {}", e),
+    }
+    thread::sleep(time::Duration::from_secs(2));
+  }
+  fn choose_encrypt_all_files(&mut self) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_yel!("If you forget your password, accessing this program's data will be impossible.");
+    println_yel!("This applies to all data for all users and clients associated with this program on your computer.");
+    println_yel!("Encryption is not and is not intended to be HIPPA compliant.");
+    println_yel!("By continuing, you agree that the responsibility to store and transfer data privately and securely is entirely your own.");
+    println_yel!("To cancel and return to the program, enter QUIT / Q.");
+    println_yel!("To continue with secure encryption, enter YES / Y.");
+
+    loop {
+      let mut choice = String::new();
+      let choice_attempt = read_input_line(&mut choice);
+      match choice_attempt {
+        Ok(_) => (),
+        Err(e) => {
+          println_err!("Failed to read input: {}.", e);
+          continue;
+        }
+      }
+      choice = choice.to_ascii_lowercase().trim().to_string();
       match &choice[..] {
         "yes" | "y" => {
-          let new_password = loop {
-            println_inst!("Enter new password for encryption (minimum 8 characters):");
-            let mut choice = String::new();
-            let choice_attempt = io::stdin().read_line(&mut choice);
-            match choice_attempt {
-              Ok(_) => {
-                if choice.trim().len() < 8 {
-                  println_err!("Password not long enough.");
-                  continue;
-                } else {
-                  println_inst!("Confirm password:");
-                  let mut confirm = String::new();
-                  let confirm_attempt = io::stdin().read_line(&mut confirm);
-                  match confirm_attempt {
-                    Ok(_) => {
-                      if confirm.trim() != choice.trim() {
-                        println_err!("Passwords do not match.");
-                        continue;
-                      } else {
-                        break confirm.trim().to_string()
-                      }
-                    },
-                    Err(e) => {
-                      println_err!("Passwords do not match (error: {})", e);
-                      continue;
-                    }
-                  }
-                  
-                }
-              },
-              Err(e) => {
-                println_err!("Failed to read input: {}.", e);
-                continue;
-              }
-            }
-          };
+          let new_password = Self::read_new_password();
           match self.encrypt_all_files(&new_password) {
             Ok(_) => (),
             Err(e) => {
@@ -1049,6 +1871,7 @@ impl NoteArchive {
             },
           }
           self.encrypted = true;
+          self.password = Some(new_password);
           print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
           println_suc!("Files encrypted successfully.");
           thread::sleep(time::Duration::from_secs(2));
@@ -1162,7 +1985,7 @@ impl NoteArchive {
         let input = loop {
           let mut choice = String::new();
           println_inst!("| {} | {} | {}", "Enter ID to choose user.", "NEW / N: new user", "QUIT / Q: Quit program");
-          let read_attempt = io::stdin().read_line(&mut choice);
+          let read_attempt = read_input_line(&mut choice);
           match read_attempt {
             Ok(_) => break choice.to_ascii_lowercase(),
             Err(e) => {
@@ -1215,7 +2038,7 @@ impl NoteArchive {
         print!("\n");
         print_inst!("First name:");
         print!("\n");
-        let first_name_attempt = io::stdin().read_line(&mut first_name_choice);
+        let first_name_attempt = read_input_line(&mut first_name_choice);
         match first_name_attempt {
           Ok(_) => break String::from(first_name_choice.trim()),
           Err(e) => {
@@ -1230,7 +2053,7 @@ impl NoteArchive {
       let last_name = loop {
         let mut last_name_choice = String::new();
         println_inst!("Last name:");
-        let last_name_attempt = io::stdin().read_line(&mut last_name_choice);
+        let last_name_attempt = read_input_line(&mut last_name_choice);
         match last_name_attempt {
           Ok(_) => break String::from(last_name_choice.trim()),
           Err(e) => {
@@ -1245,7 +2068,7 @@ impl NoteArchive {
       let role: EmployeeRole = loop {
         let mut role_choice = String::new();
         println_inst!("Role ('ICC' or 'FP'):");
-        let role_attempt = io::stdin().read_line(&mut role_choice);
+        let role_attempt = read_input_line(&mut role_choice);
         match role_attempt {
           Ok(_) => match &role_choice.trim().to_ascii_lowercase()[..] {
             "icc" => break Icc,
@@ -1269,7 +2092,7 @@ impl NoteArchive {
             loop {
               println_inst!("Cancel? (Y/N)");
               let mut cancel = String::new();
-              let cancel_attempt = io::stdin().read_line(&mut cancel);
+              let cancel_attempt = read_input_line(&mut cancel);
               match cancel_attempt {
                 Ok(_) => match &cancel.trim().to_lowercase()[..] {
                   "yes" | "y"  => return None,
@@ -1328,7 +2151,7 @@ impl NoteArchive {
       return Err(String::from("Name cannot contain ' | '."));
     }
     
-    let id: u32 = self.users.len() as u32 + 1;
+    let id: u32 = self.next_id("users");
 
     match self.user_dup_id_option(&first_name, &last_name, &role) {
       Some(_) => Err(format!("There is already a {} with the name '{} {}'.", role, first_name, last_name)),
@@ -1340,82 +2163,64 @@ impl NoteArchive {
     self.users.insert(pos, user);
     self.write_to_files();
   }
-  pub fn write_users(&mut self) -> std::io::Result<()> {
-    let mut lines = String::from("##### users #####\n");
-    for u in &self.users {
-      lines.push_str(&u.to_string());
-    }
-    lines.push_str("##### users #####");
-    let mut file = File::create(self.filepaths["user_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
+  pub fn read_users(filepath: &str) -> Result<(Vec<User>, Vec<LoadError>), Error> {
+    let (mut users, errors) = Self::read_text_records(filepath, UserRecord, Self::parse_user_line)?;
+    users.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok((users, errors))
+  }
+  pub fn parse_user_line(line: &str) -> Result<User, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let first_name = String::from(values.text(1, "first name")?);
+    let last_name = String::from(values.text(2, "last name")?);
+    let role = match values.text(3, "role")? {
+      "FP" => Fp,
+      "ICC" => Icc,
+      r => return Err(FieldError::new("role", format!("'{}' is not FP or ICC", r))),
+    };
+    let pronouns = values.id(4, "pronouns")?;
+    let clients = values.ids(5, "client IDs")?;
+    let collaterals = values.ids(6, "collateral IDs")?;
+    Ok(User::new(id, first_name, last_name, role, pronouns, clients, collaterals))
   }
-  pub fn read_users(filepath: &str) -> Result<Vec<User>, Error> {
+  /// the data lines of an old text file, numbered from the top of the file, without the heading lines
+  fn read_data_file_lines(filepath: &str) -> Result<Vec<(usize, String)>, Error> {
     let file = OpenOptions::new()
       .read(true)
       .write(true)
       .create(true)
-      .open(filepath)
-      .unwrap();
+      .open(filepath)?;
 
     let reader = BufReader::new(file);
 
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
+    let mut lines: Vec<(usize, String)> = vec![];
+    for (i, line) in reader.lines().enumerate() {
+      lines.push((i + 1, line?));
+    }
 
-    if lines.len() > 0 {
-      lines.remove(0)?;
+    if !lines.is_empty() {
+      lines.remove(0);
     }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
+    if !lines.is_empty() {
+      lines.remove(lines.len() - 1);
     }
-
-    let mut users: Vec<User> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      if values.len() < 7 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read users from filepath."));
-      }
-
-      let id: u32 = values[0].parse().unwrap();
-      let first_name = String::from(&values[1]);
-      let last_name = String::from(&values[2]);
-      let role = match &values[3][..] {
-        "FP" => Ok(Fp),
-        "ICC" => Ok(Icc),
-        _ => Err("Invalid role."),
+    Ok(lines.into_iter().filter(|(_, line)| !line.trim().is_empty() ).collect())
+  }
+  /// parses every line of an old text file, collecting the lines that could not be read instead of stopping
+  fn read_text_records<T>(
+    filepath: &str,
+    kind: RecordKind,
+    parse: fn(&str) -> Result<T, FieldError>,
+  ) -> Result<(Vec<T>, Vec<LoadError>), Error> {
+    let mut records: Vec<T> = vec![];
+    let mut errors: Vec<LoadError> = vec![];
+    for (line_number, line) in Self::read_data_file_lines(filepath)? {
+      match parse(&line) {
+        Ok(r) => records.push(r),
+        Err(e) => errors.push(LoadError::new(filepath, line_number, kind, TextLine, e, line)),
       }
-      .unwrap();
-      let pronouns: u32 = values[4].parse().unwrap();
-
-      let clients: Vec<u32> = match &values[5][..] {
-        "" => vec![],
-        _ => values[5]
-        .split("#")
-        .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      let collaterals: Vec<u32> = match &values[6][..] {
-        "" => vec![],
-        _ => values[6]
-        .split("#")
-        .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      
-      let u = User::new(id, first_name, last_name, role, pronouns, clients, collaterals);
-      users.push(u);
     }
-    users.sort_by(|a, b| a.id.cmp(&b.id));
-    Ok(users)
+    Ok((records, errors))
   }
   fn change_user_first_name(&mut self, new_name: &str) -> Result<(), String> {
     let names_and_roles: Vec<(&str, &str, &EmployeeRole)> = self
@@ -1502,7 +2307,7 @@ impl NoteArchive {
     loop {
       self.display_edit_user();
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -1521,7 +2326,7 @@ impl NoteArchive {
         "first" | "fst" | "f" | "1st" | "first name" => {
           println_inst!("Enter new first name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_user_first_name(name_choice.trim()) {
               Ok(_) => (),
@@ -1538,7 +2343,7 @@ impl NoteArchive {
         "last" | "lst" | "l" | "last name" => {
           println_inst!("Enter new last name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_user_last_name(name_choice.trim()) {
               Ok(_) => (),
@@ -1577,7 +2382,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete this user?");
       println_yel!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut choice = String::new();
-      let input_attempt = io::stdin().read_line(&mut choice);
+      let input_attempt = read_input_line(&mut choice);
       match input_attempt {
         Ok(_) => choice = choice.trim().to_string(),
         Err(e) => {
@@ -1589,7 +2394,6 @@ impl NoteArchive {
       match &choice.to_ascii_lowercase()[..] {
         "yes" | "y" => {
           self.delete_current_user();
-          self.reindex_users();
           self.write_to_files();
           break Some(());
         },
@@ -1625,7 +2429,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_client(),
       }
     }
-    self.reindex_clients();
     let current_note_days = self.current_user_note_days().iter().map(|nd| nd.id ).collect::<Vec<u32>>();
     for nd_id in current_note_days {
       match self.load_note_day(nd_id) {
@@ -1633,10 +2436,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_note_day(),
       }
     }
-    let new_note_day_id = self.reindex_note_days(None);
-    match new_note_day_id {
-      _ => (),
-    }
     let current_notes = self.current_user_notes().iter().map(|n| n.id ).collect::<Vec<u32>>();
     for n_id in current_notes {
       match self.load_note(n_id) {
@@ -1644,10 +2443,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_note(),
       }
     }
-    let new_note_id = self.reindex_notes(None);
-    match new_note_id {
-      _ => (),
-    }
 
     let id = self.foreign_key.get("current_user_id").unwrap().to_owned();
     self.delete_from_blanks(String::from("user"), id);
@@ -1657,41 +2452,6 @@ impl NoteArchive {
     self.foreign_key.remove("current_client_id");
     self.foreign_key.remove("current_collateral_id");
   }
-  fn reindex_users(&mut self) {
-    let mut i: u32 = 1;
-    let mut new_note_days: Vec<NoteDay> = self.note_days.clone();
-    let mut new_notes: Vec<Note> = self.notes.clone();
-    let mut changes: HashMap<u32, u32> = HashMap::new();
-    for mut u in &mut self.users {
-      for nd in &mut new_note_days {
-        if nd.foreign_key["user_id"] == u.id {
-          nd.foreign_key.insert(String::from("user_id"), i);
-        }
-      }
-      for n in &mut new_notes {
-        if n.foreign_key["user_id"] == u.id {
-          n.foreign_key.insert(String::from("user_id"), i);
-        }
-      }
-      if u.id == i {
-        ()
-      } else {
-        changes.insert(u.id, i);
-        u.id = i;
-      }
-      i += 1;
-    }
-
-    for nt in &mut self.note_templates {
-      let old_ids = nt.foreign_keys["user_ids"].clone();
-      let new_ids: Vec<u32> = old_ids.iter()
-        .map(|co_id| match changes.get(co_id) { Some(val) => *val, None => *co_id } )
-        .collect();
-      nt.foreign_keys.insert(String::from("user_ids"), new_ids);
-    }
-    self.note_days = new_note_days;
-    self.notes = new_notes;
-  }
 
   // clients
   fn current_client_mut(&mut self) -> &mut Client {
@@ -1723,6 +2483,12 @@ impl NoteArchive {
       )
       .collect()
   }
+  fn get_active_clients(&self) -> Vec<&Client> {
+    self.get_current_clients().into_iter().filter(|c| c.is_active() ).collect()
+  }
+  fn get_inactive_clients(&self) -> Vec<&Client> {
+    self.get_current_clients().into_iter().filter(|c| !c.is_active() ).collect()
+  }
   fn display_clients(&self) {
     let heading = format!(" {}'s clients ", &self.current_user().full_name()[..]);
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
@@ -1732,7 +2498,7 @@ impl NoteArchive {
     println_on_bg!("{:-^10} | {:-^40} | {:-^40}", " ID ", " Name ", " DOB ");
     match self.foreign_key.get("current_user_id") {
       Some(_) => {
-        for c in self.get_current_clients() {
+        for c in self.get_active_clients() {
           println_on_bg!(
             "{: ^10} | {: ^40} | {: <12} {: >26}",
             c.id,
@@ -1746,7 +2512,7 @@ impl NoteArchive {
     }
     println_on_bg!("{:-^96}", "-");
     println_inst!("| {} | {} | {}", "Choose client by ID.", "NEW / N: new client", "ADD / A: Add from other user");
-    println_inst!("| {} | {}", "EDIT / E: edit records", "QUIT / Q: quit menu");
+    println_inst!("| {} | {} | {}", "EDIT / E: edit records", "INACTIVE / I: discharged clients", "QUIT / Q: quit menu");
   }
   fn display_select_clients(&self) {
     let mut heading = String::from(" ");
@@ -1759,7 +2525,7 @@ impl NoteArchive {
     println_on_bg!("{:-^10} | {:-^40} | {:-^40}", " ID ", " Name ", " DOB ");
     match self.foreign_key.get("current_user_id") {
       Some(_) => {
-        for c in self.get_current_clients() {
+        for c in self.get_active_clients() {
           println_on_bg!(
             "{: ^10} | {: ^40} | {: <12} {: >26}",
             c.id,
@@ -1867,6 +2633,19 @@ impl NoteArchive {
       self.current_client().fmt_dob(),
       self.current_client().fmt_date_of_birth(),
     );
+    if !self.current_client().episodes.is_empty() {
+      println_on_bg!("{:-^119}", " Enrollment ");
+      println_on_bg!("{:-^20} | {:-^20} | {:-^20} | {:-^50}", " Referred ", " Enrolled ", " Discharged ", " Reason ");
+      for e in &self.current_client().episodes {
+        println_on_bg!(
+          "{: ^20} | {: ^20} | {: ^20} | {: <50}",
+          e.referral_date.map(|d| d.format("%Y-%m-%d").to_string() ).unwrap_or_else(|| String::from("-") ),
+          e.enrollment_date.format("%Y-%m-%d").to_string(),
+          e.discharge_date.map(|d| d.format("%Y-%m-%d").to_string() ).unwrap_or_else(|| String::from("-") ),
+          e.discharge_reason.as_ref().map(|r| r.to_string() ).unwrap_or_default(),
+        );
+      }
+    }
     println_on_bg!("{:-^119}", "-");
   }
   fn load_client(&mut self, id: u32) -> std::io::Result<()> {
@@ -1887,7 +2666,7 @@ impl NoteArchive {
       self.display_add_client();
       let input = loop {
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -1923,7 +2702,7 @@ impl NoteArchive {
                 Ok(_) => {
                   let to_copy = self.get_client_by_id(num).unwrap().clone();
                   let copied = Client::new(
-                    (self.clients.len() + 1) as u32,
+                    self.next_id("clients"),
                     to_copy.first_name.clone(),
                     to_copy.last_name.clone(),
                     to_copy.dob.clone(),
@@ -1955,7 +2734,7 @@ impl NoteArchive {
       let input = loop {
         self.display_clients();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -1982,12 +2761,16 @@ impl NoteArchive {
           self.choose_edit_clients();
           continue;
         },
+        "inactive" | "i" => {
+          self.choose_inactive_clients();
+          continue;
+        },
         "quit" | "q" => {
           break;
         },
         _ => match input.parse() {
           Ok(num) => {
-            if !self.get_current_clients()
+            if !self.get_active_clients()
               .iter()
               .any(|&c| c.id == num) {
                 println_err!("Please choose from among the listed clients, or add a client from another user.");
@@ -2017,7 +2800,7 @@ impl NoteArchive {
       let input = loop {
         self.display_select_clients();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -2046,7 +2829,7 @@ impl NoteArchive {
         },
         _ => match input.parse() {
           Ok(num) => {
-            if !self.get_current_clients()
+            if !self.get_active_clients()
               .iter()
               .any(|&c| c.id == num) {
                 println_err!("Please choose from among the listed clients, or add a client from another user.");
@@ -2076,7 +2859,7 @@ impl NoteArchive {
       let input = loop {
         self.display_edit_clients();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -2146,7 +2929,7 @@ impl NoteArchive {
       let input = loop {
         self.display_specify_clients(purpose.clone());
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -2205,9 +2988,13 @@ impl NoteArchive {
     loop {
       self.display_client();
       println_inst!("| {} | {} | {}", "EDIT / E: edit client", "DELETE: delete client", "COLLATERAL / CO: view/edit client collaterals");
-      println_inst!("| {} | {}", "GOALS / G: View and edit client goals", "QUIT / Q: quit menu");
+      if self.current_client().is_active() {
+        println_inst!("| {} | {} | {}", "GOALS / G: View and edit client goals", "DISCHARGE / DC: discharge client", "QUIT / Q: quit menu");
+      } else {
+        println_inst!("| {} | {} | {}", "GOALS / G: View and edit client goals", "REACTIVATE / R: start a new enrollment", "QUIT / Q: quit menu");
+      }
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -2234,10 +3021,131 @@ impl NoteArchive {
         "goals" | "g" => {
           self.choose_client_goals();
         }
+        "discharge" | "dc" if self.current_client().is_active() => {
+          self.choose_discharge_client();
+        }
+        "reactivate" | "r" if !self.current_client().is_active() => {
+          self.choose_reactivate_client();
+        }
         _ => println_err!("Invalid command."),
       }
     }
   }
+  fn display_inactive_clients(&self) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_on_bg!("{:-^96}", "-");
+    println_on_bg!("{:-^96}", format!(" {}'s discharged clients ", self.current_user().full_name()));
+    println_on_bg!("{:-^96}", "-");
+    println_on_bg!("{:-^10} | {:-^30} | {:-^50}", " ID ", " Name ", " Status ");
+    for c in self.get_inactive_clients() {
+      println_on_bg!("{: ^10} | {: ^30} | {: <50}", c.id, c.full_name(), c.fmt_status());
+    }
+    println_on_bg!("{:-^96}", "-");
+    println_inst!("| {} | {}", "Choose client by ID to view or reactivate.", "QUIT / Q: quit menu");
+  }
+  fn choose_inactive_clients(&mut self) {
+    loop {
+      self.display_inactive_clients();
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Could not read input; try again ({}).", e);
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        input => match input.parse::<u32>() {
+          Ok(num) if self.get_inactive_clients().iter().any(|c| c.id == num ) => {
+            self.foreign_key.insert(String::from("current_client_id"), num);
+            self.choose_client();
+          },
+          _ => {
+            println_err!("Please choose from among the listed clients.");
+            thread::sleep(time::Duration::from_secs(1));
+          },
+        },
+      }
+    }
+  }
+  /// asks for a date as YYYY-MM-DD until a valid one is entered; a blank answer gives `blank`,
+  /// and the outer None means the user cancelled
+  fn read_episode_date(prompt: &str, blank: Option<NaiveDate>) -> Option<Option<NaiveDate>> {
+    loop {
+      println_inst!("{}", prompt);
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Could not read input; try again ({}).", e);
+        continue;
+      }
+      match choice.trim() {
+        "" => return Some(blank),
+        c if c.eq_ignore_ascii_case("cancel") => return None,
+        c => match NaiveDate::parse_from_str(c, "%Y-%m-%d") {
+          Ok(date) => return Some(Some(date)),
+          Err(_) => println_err!("Please enter a date as YYYY-MM-DD."),
+        },
+      }
+    }
+  }
+  fn choose_discharge_client(&mut self) {
+    let today = Local::now().naive_local().date();
+    println_inst!("Enter 'CANCEL' at any time to cancel.");
+    // changes are made to a copy so that cancelling leaves the client as it was
+    let mut client = self.current_client().clone();
+    if client.episodes.is_empty() {
+      let enrollment_date = match Self::read_episode_date("Enter the date the client was enrolled (YYYY-MM-DD).", None) {
+        Some(Some(date)) => date,
+        Some(None) => {
+          println_err!("An enrollment date is needed to discharge a client.");
+          thread::sleep(time::Duration::from_secs(2));
+          return;
+        },
+        None => return,
+      };
+      client.episodes.push(Enrollment::new(None, enrollment_date));
+    }
+    let date = match Self::read_episode_date("Enter the discharge date (YYYY-MM-DD), or press ENTER for today.", Some(today)) {
+      Some(Some(date)) => date,
+      _ => return,
+    };
+    for (i, reason) in DischargeReason::listed().iter().enumerate() {
+      println_inst!("{}: {}", i + 1, reason);
+    }
+    println_inst!("Choose a discharge reason by number, or type another reason.");
+    let mut reason_choice = String::new();
+    let _ = read_input_line(&mut reason_choice);
+    if reason_choice.trim().eq_ignore_ascii_case("cancel") {
+      return;
+    }
+    match client.discharge(date, DischargeReason::parse(&reason_choice)) {
+      Ok(_) => {
+        *self.current_client_mut() = client;
+        self.write_to_files();
+        println_suc!("{}", self.current_client().fmt_status());
+      },
+      Err(e) => println_err!("{}", e),
+    }
+    thread::sleep(time::Duration::from_secs(2));
+  }
+  fn choose_reactivate_client(&mut self) {
+    let today = Local::now().naive_local().date();
+    println_inst!("Enter 'CANCEL' at any time to cancel.");
+    let referral_date = match Self::read_episode_date("Enter the new referral date (YYYY-MM-DD), or press ENTER to skip.", None) {
+      Some(date) => date,
+      None => return,
+    };
+    let enrollment_date = match Self::read_episode_date("Enter the new enrollment date (YYYY-MM-DD), or press ENTER for today.", Some(today)) {
+      Some(Some(date)) => date,
+      _ => return,
+    };
+    match self.current_client_mut().reactivate(referral_date, enrollment_date) {
+      Ok(_) => {
+        self.write_to_files();
+        println_suc!("{} is active again.", self.current_client().full_name());
+      },
+      Err(e) => println_err!("{}", e),
+    }
+    thread::sleep(time::Duration::from_secs(2));
+  }
   fn create_client_get_id(&mut self) -> Option<u32> {
     let client = loop {
       let first_name = loop {
@@ -2246,7 +3154,7 @@ impl NoteArchive {
         print!("\n");
         print_inst!("Enter client's first name.");
         print!("\n");
-        let first_name_attempt = io::stdin().read_line(&mut first_name_choice);
+        let first_name_attempt = read_input_line(&mut first_name_choice);
         match first_name_attempt {
           Ok(_) => break String::from(first_name_choice.trim()),
           Err(e) => {
@@ -2261,7 +3169,7 @@ impl NoteArchive {
       let last_name = loop {
         let mut last_name_choice = String::new();
         println_inst!("Enter client's last name.");
-        let last_name_attempt = io::stdin().read_line(&mut last_name_choice);
+        let last_name_attempt = read_input_line(&mut last_name_choice);
         match last_name_attempt {
           Ok(_) => break String::from(last_name_choice.trim()),
           Err(e) => {
@@ -2277,7 +3185,7 @@ impl NoteArchive {
         let birth_year = loop {
           let mut birth_year_choice = String::new();
           println_inst!("Enter client's birth year.");
-          let birth_year_attempt = io::stdin().read_line(&mut birth_year_choice);
+          let birth_year_attempt = read_input_line(&mut birth_year_choice);
           let birth_year_attempt = match birth_year_attempt {
             Ok(_) => {
               if birth_year_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -2306,7 +3214,7 @@ impl NoteArchive {
         let birth_month = loop {
           let mut birth_month_choice = String::new();
           println_inst!("Enter client's birth month as a decimal number (1-12).");
-          let birth_month_attempt = io::stdin().read_line(&mut birth_month_choice);
+          let birth_month_attempt = read_input_line(&mut birth_month_choice);
           let birth_month_attempt = match birth_month_attempt {
             Ok(_) => {
               if birth_month_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -2335,7 +3243,7 @@ impl NoteArchive {
         let birth_day = loop {
           let mut birth_day_choice = String::new();
           println_inst!("Enter client's birth day as a decimal number (1-31).");
-          let birth_day_attempt = io::stdin().read_line(&mut birth_day_choice);
+          let birth_day_attempt = read_input_line(&mut birth_day_choice);
           let birth_day_attempt = match birth_day_attempt {
             Ok(_) => {
               if birth_day_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -2381,7 +3289,7 @@ impl NoteArchive {
             loop {
               println_yel!("Cancel? (Y/N)");
               let mut cancel = String::new();
-              let cancel_attempt = io::stdin().read_line(&mut cancel);
+              let cancel_attempt = read_input_line(&mut cancel);
               match cancel_attempt {
                 Ok(_) => match &cancel.trim().to_lowercase()[..] {
                   "yes" | "y"  => return None,
@@ -2402,7 +3310,13 @@ impl NoteArchive {
         } 
       };
 
-      let client_attempt = self.generate_unique_new_client(first_name, last_name, dob, pronouns);
+      let today = Local::now().naive_local().date();
+      let enrollment_date = match Self::read_episode_date("Enter the date the client was enrolled (YYYY-MM-DD), or press ENTER for today.", Some(today)) {
+        Some(Some(date)) => date,
+        _ => return None,
+      };
+
+      let client_attempt = self.generate_unique_new_client(first_name, last_name, dob, pronouns, enrollment_date);
       match client_attempt {
         Ok(client) => break client,
         Err(error_hash) => {
@@ -2423,7 +3337,7 @@ impl NoteArchive {
                     let mut conf = String::new();
                     let choice = loop {
                       println_inst!("Would you like to use the existing record? (Y/N)");
-                      let conf_attempt = io::stdin().read_line(&mut conf);
+                      let conf_attempt = read_input_line(&mut conf);
                       match conf_attempt {
                         Ok(_) => break String::from(conf.trim()),
                         Err(_) => {
@@ -2435,7 +3349,7 @@ impl NoteArchive {
                     match &choice.to_ascii_lowercase()[..] {
                       "yes" | "y" => {
                         let mut new_client = client.clone();
-                        new_client.id = (self.clients.len() + 1) as u32;
+                        new_client.id = self.next_id("clients");
                         break new_client;
                       }
                       "no" | "n" => continue,
@@ -2476,94 +3390,43 @@ impl NoteArchive {
         None => None,
       }
   }
-  fn generate_unique_new_client(
+  pub fn generate_unique_new_client(
     &mut self,
     first_name: String,
     last_name: String,
     dob: NaiveDate,
     pronouns: u32,
+    enrollment_date: NaiveDate,
   ) -> Result<Client, HashMap<String, u32>> {
     if first_name.contains(" | ") || last_name.contains(" | ") {
       return Err([(String::from("invalid character string: ' | ' "), 0)].iter().cloned().collect::<HashMap<String, u32>>());
     }
     
-    let id: u32 = self.clients.len() as u32 + 1;
+    let id: u32 = self.next_id("clients");
     
     match self.client_dup_id_option(&first_name, &last_name, &dob ) {
       Some(dup_id) => Err([(String::from("duplicate"), dup_id)].iter().cloned().collect::<HashMap<String, u32>>()),
-      None => Ok(Client::new(id, first_name, last_name, dob, pronouns, vec![]))
+      None => {
+        let mut c = Client::new(id, first_name, last_name, dob, pronouns, vec![]);
+        c.episodes.push(Enrollment::new(None, enrollment_date));
+        Ok(c)
+      },
     }
   }
-  pub fn read_clients(filepath: &str) -> Result<Vec<Client>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut clients: Vec<Client> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      if values.len() < 6 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read clients from filepath."));
-      }
-
-      let id: u32 = values[0].parse().unwrap();
-      let first_name = String::from(&values[1]);
-      let last_name = String::from(&values[2]);
-
-      let date: Vec<i32> = match &values[3][..] {
-        "" => vec![],
-        _ => values[3]
-          .split("-")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-      let (year, month, day): (i32, u32, u32) = (date[0], date[1] as u32, date[2] as u32);
-      let dob = NaiveDate::from_ymd(year, month, day);
-      let pronouns: u32 = values[4].parse().unwrap();
-      let collaterals: Vec<u32> = match &values[5][..] {
-        "" => vec![],
-        _ => values[5]
-          .split("#")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      let c = Client::new(id, first_name, last_name, dob, pronouns, collaterals);
-      clients.push(c);
-    }
+  pub fn read_clients(filepath: &str) -> Result<(Vec<Client>, Vec<LoadError>), Error> {
+    let (mut clients, errors) = Self::read_text_records(filepath, ClientRecord, Self::parse_client_line)?;
     clients.sort_by(|a, b| a.id.cmp(&b.id));
-    Ok(clients)
-  }
-  pub fn write_clients(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### clients #####\n");
-    for c in &self.clients {
-      lines.push_str(&c.to_string()[..]);
-    }
-    lines.push_str("##### clients #####");
-    let mut file = File::create(self.filepaths["client_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
+    Ok((clients, errors))
+  }
+  pub fn parse_client_line(line: &str) -> Result<Client, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let first_name = String::from(values.text(1, "first name")?);
+    let last_name = String::from(values.text(2, "last name")?);
+    let dob = values.date(3, "date of birth")?;
+    let pronouns = values.id(4, "pronouns")?;
+    let collaterals = values.ids(5, "collateral IDs")?;
+    Ok(Client::new(id, first_name, last_name, dob, pronouns, collaterals))
   }
   pub fn save_client(&mut self, client: Client) {
     let pos = self.clients.binary_search_by(|c| c.id.cmp(&client.id) ).unwrap_or_else(|e| e);
@@ -2578,7 +3441,7 @@ impl NoteArchive {
       self.display_client();
       println_inst!("| {} | {} | {} | {}", "FIRST / F: edit first name", "LAST / L: edit surname", "PRNS / P: edit pronouns", "QUIT / Q: quit menu");
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -2597,7 +3460,7 @@ impl NoteArchive {
         "first" | "fst" | "f" | "1st" | "first name" => {
           println_inst!("Enter new first name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_client_first_name(name_choice.trim()) {
               Ok(_) => (),
@@ -2614,7 +3477,7 @@ impl NoteArchive {
         "last" | "lst" | "l" | "last name" => {
           println_inst!("Enter new last name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_client_last_name(name_choice.trim()) {
               Ok(_) => (),
@@ -2703,7 +3566,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete this client?");
       println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -2715,7 +3578,6 @@ impl NoteArchive {
       match &command.to_ascii_lowercase()[..] {
         "yes" | "y" => {
           self.delete_current_client();
-          self.reindex_clients();
           self.write_to_files();
           break;
         }
@@ -2752,10 +3614,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_collateral(),
       }
     }
-    let new_collateral_id = self.reindex_collaterals(None);
-    match new_collateral_id {
-      _ => (),
-    }
     let current_goals = self.current_client_goals().iter().map(|co| co.id ).collect::<Vec<u32>>();
     for g_id in current_goals {
       match self.load_goal(g_id) {
@@ -2763,7 +3621,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_goal(),
       }
     }
-    self.reindex_goals();
     let current_note_days = self.current_client_note_days().iter().map(|co| co.id ).collect::<Vec<u32>>();
     for nd_id in current_note_days {
       match self.load_note_day(nd_id) {
@@ -2771,10 +3628,6 @@ impl NoteArchive {
         Ok(_) => self.delete_current_note_day(),
       }
     }
-    let new_note_day_id = self.reindex_note_days(None);
-    match new_note_day_id {
-      _ => (),
-    }
     let id = self.foreign_key.get("current_client_id").unwrap().to_owned();
     self.delete_from_blanks(String::from("client"), id);
     for u in &mut self.users {
@@ -2786,41 +3639,7 @@ impl NoteArchive {
     self.foreign_key.remove("current_client_id");
     self.foreign_key.remove("current_collateral_id");
   }
-  fn reindex_clients(&mut self) {
-    let mut i: u32 = 1;
-    let mut new_note_days: Vec<NoteDay> = self.note_days.clone();
-    let mut new_notes: Vec<Note> = self.notes.clone();
-    let mut changes: HashMap<u32, u32> = HashMap::new();
-    for mut c in &mut self.clients {
-      for nd in &mut new_note_days {
-        if nd.foreign_key["client_id"] == c.id {
-          nd.foreign_key.insert(String::from("client_id"), i);
-        }
-      }
-      for n in &mut new_notes {
-        if n.foreign_key["client_id"] == c.id {
-          n.foreign_key.insert(String::from("client_id"), i);
-        }
-      }
-      if c.id == i {
-        ()
-      } else {
-        changes.insert(c.id, i);
-        c.id = i;
-      }
-      i += 1;
-    }
-    for u in &mut self.users {
-      let old_ids = u.foreign_keys["client_ids"].clone();
-      let new_ids: Vec<u32> = old_ids.iter()
-        .map(|co_id| match changes.get(co_id) { Some(val) => *val, None => *co_id } )
-        .collect();
-      u.foreign_keys.insert(String::from("client_ids"), new_ids);
-    }
-    self.note_days = new_note_days;
-    self.notes = new_notes;
-  }
-  fn get_client_by_id(&self, id: u32) -> Option<&Client> {
+  pub fn get_client_by_id(&self, id: u32) -> Option<&Client> {
     self.clients.iter().find(|c| c.id == id)
   }
   fn get_client_by_id_mut(&mut self, id: u32) -> Option<&mut Client> {
@@ -3545,7 +4364,7 @@ impl NoteArchive {
         println_inst!("ALL: Select all currently shown collaterals.");
         println_inst!("GENERAL: Select from general/universal collaterals.");
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.trim().to_string(),
           Err(e) => {
@@ -3573,7 +4392,7 @@ impl NoteArchive {
               self.display_select_general_collaterals(Some(general_collat_ids.clone()));
               println_inst!("ALL: Select all");
               let mut choice = String::new();
-              let read_attempt = io::stdin().read_line(&mut choice);
+              let read_attempt = read_input_line(&mut choice);
               match read_attempt {
                 Ok(_) => break choice.trim().to_string(),
                 Err(e) => {
@@ -3605,7 +4424,7 @@ impl NoteArchive {
               "" => {
                 break;
               }
-              _ => match general_input.parse() {
+              _ => match general_input.parse::<u32>() {
                 Ok(num) => {
                   let collat = match self.general_collaterals.iter().find(|co| co.id == num) {
                     Some(co) => co.clone(),
@@ -3704,7 +4523,7 @@ impl NoteArchive {
       let input = loop {
         self.display_client_collaterals(None);
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -3762,7 +4581,7 @@ impl NoteArchive {
       let input = loop {
         self.display_edit_client_collaterals();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice,
           Err(e) => {
@@ -3806,7 +4625,7 @@ impl NoteArchive {
       let input = loop {
         self.display_user_collaterals();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -3865,7 +4684,7 @@ impl NoteArchive {
       let input = loop {
         self.display_general_collaterals();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -3920,7 +4739,7 @@ impl NoteArchive {
       let input = loop {
         self.display_edit_user_collaterals();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice,
           Err(e) => {
@@ -3963,7 +4782,7 @@ impl NoteArchive {
       let input = loop {
         self.display_edit_general_collaterals();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice,
           Err(e) => {
@@ -4015,7 +4834,7 @@ impl NoteArchive {
         "QUIT / Q: quit menu"
       );
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -4068,7 +4887,7 @@ impl NoteArchive {
     loop {
       self.display_general_collateral();
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -4104,7 +4923,7 @@ impl NoteArchive {
         print!("\n");
         print_inst!("Collateral's first name:");
         print!("\n");
-        let first_name_attempt = io::stdin().read_line(&mut first_name_choice);
+        let first_name_attempt = read_input_line(&mut first_name_choice);
         match first_name_attempt {
           Ok(_) => break String::from(first_name_choice.trim()),
           Err(e) => {
@@ -4119,7 +4938,7 @@ impl NoteArchive {
       let last_name = loop {
         let mut last_name_choice = String::new();
         println_inst!("Collateral's last name:");
-        let last_name_attempt = io::stdin().read_line(&mut last_name_choice);
+        let last_name_attempt = read_input_line(&mut last_name_choice);
         match last_name_attempt {
           Ok(_) => break String::from(last_name_choice.trim()),
           Err(e) => {
@@ -4134,7 +4953,7 @@ impl NoteArchive {
       let title = loop {
         let mut title_choice = String::new();
         println_inst!("Enter collateral's role/title.");
-        let title_attempt = io::stdin().read_line(&mut title_choice);
+        let title_attempt = read_input_line(&mut title_choice);
         match title_attempt {
           Ok(_) => break String::from(title_choice.trim()),
           Err(e) => {
@@ -4154,7 +4973,7 @@ impl NoteArchive {
             loop {
               println_yel!("Cancel? (Y/N)");
               let mut cancel = String::new();
-              let cancel_attempt = io::stdin().read_line(&mut cancel);
+              let cancel_attempt = read_input_line(&mut cancel);
               match cancel_attempt {
                 Ok(_) => match &cancel.trim().to_lowercase()[..] {
                   "yes" | "y" => return None,
@@ -4181,7 +5000,7 @@ impl NoteArchive {
         let institution = loop {
           let mut institution_choice = String::new();
           println_inst!("Enter collateral's institution.");
-          let institution_attempt = io::stdin().read_line(&mut institution_choice);
+          let institution_attempt = read_input_line(&mut institution_choice);
           match institution_attempt {
             Ok(_) => break String::from(institution_choice.trim()),
             Err(e) => {
@@ -4195,7 +5014,7 @@ impl NoteArchive {
         let institution = loop {
           let mut institution_choice = String::new();
           println_inst!("Enter collateral's institution.");
-          let institution_attempt = io::stdin().read_line(&mut institution_choice);
+          let institution_attempt = read_input_line(&mut institution_choice);
           match institution_attempt {
             Ok(_) => break String::from(institution_choice.trim()),
             Err(e) => {
@@ -4211,7 +5030,7 @@ impl NoteArchive {
           let mut support_type_choice = String::new();
           println_inst!("Natural or Formal support?");
           println_inst!("NATURAL / N | FORMAL / F");
-          let support_type_attempt = io::stdin().read_line(&mut support_type_choice);
+          let support_type_attempt = read_input_line(&mut support_type_choice);
           let s = match support_type_attempt {
             Ok(_) => match support_type_choice.to_ascii_lowercase().trim() {
               "natural" | "nat" | "n" => Natural,
@@ -4236,7 +5055,7 @@ impl NoteArchive {
               let mut indirect_choice = String::new();
               println_inst!("Is this collateral a provider for the selected client?");
               println_inst!("YES / Y | NO / N");
-              let indirect_attempt = io::stdin().read_line(&mut indirect_choice);
+              let indirect_attempt = read_input_line(&mut indirect_choice);
               i = match indirect_attempt {
                 Ok(_) => match indirect_choice.to_ascii_lowercase().trim() {
                   "yes" | "y" => false,
@@ -4259,7 +5078,7 @@ impl NoteArchive {
             if s == Formal {
               let mut institution_choice = String::new();
               println_inst!("Enter collateral's institution.");
-              let institution_attempt = io::stdin().read_line(&mut institution_choice);
+              let institution_attempt = read_input_line(&mut institution_choice);
               match institution_attempt {
                 Ok(_) => {
                   if institution_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -4288,7 +5107,7 @@ impl NoteArchive {
           let mut guardian_choice = String::new();
           println_inst!("Is this collateral the youth's guardian?");
           println_inst!("YES / Y | NO / N");
-          let guardian_attempt = io::stdin().read_line(&mut guardian_choice);
+          let guardian_attempt = read_input_line(&mut guardian_choice);
           match guardian_attempt {
             Ok(_) => match guardian_choice.to_ascii_lowercase().trim() {
               "yes" | "y" => true,
@@ -4315,7 +5134,7 @@ impl NoteArchive {
           let mut primary_choice = String::new();
           println_inst!("Is this collateral the primary contact for the family?");
           println_inst!("YES / Y | NO / N");
-          let primary_attempt = io::stdin().read_line(&mut primary_choice);
+          let primary_attempt = read_input_line(&mut primary_choice);
           match primary_attempt {
             Ok(_) => match primary_choice.to_ascii_lowercase().trim() {
               "yes" | "y" => true,
@@ -4340,7 +5159,7 @@ impl NoteArchive {
         let mut care_plan_team_choice = String::new();
         println_inst!("Is this collateral on the youth's Care Plan Team?");
         println_inst!("YES / Y | NO / N");
-        let care_plan_team_attempt = io::stdin().read_line(&mut care_plan_team_choice);
+        let care_plan_team_attempt = read_input_line(&mut care_plan_team_choice);
         match care_plan_team_attempt {
           Ok(_) => match care_plan_team_choice.to_ascii_lowercase().trim() {
             "yes" | "y" => true,
@@ -4392,7 +5211,7 @@ impl NoteArchive {
                     let mut conf = String::new();
                     let choice = loop {
                       println_inst!("Would you like to use the existing record? (Y/N)");
-                      let conf_attempt = io::stdin().read_line(&mut conf);
+                      let conf_attempt = read_input_line(&mut conf);
                       match conf_attempt {
                         Ok(_) => break String::from(conf.trim()),
                         Err(_) => {
@@ -4404,7 +5223,7 @@ impl NoteArchive {
                     match &choice[..] {
                       "YES" | "yes" | "Y" | "y" => {
                         let mut c = collat.clone();
-                        c.id = (self.collaterals.len() + 1) as u32;
+                        c.id = self.next_id("collaterals");
                         break c;
                       }
                       "NO" | "no" | "N" | "n" => continue,
@@ -4439,7 +5258,7 @@ impl NoteArchive {
             None => {
               println_yel!("A collateral must be connected with a client. Cancel creating collateral ( Y / N )?");
               let mut answer = String::new();
-              let answer_attempt = io::stdin().read_line(&mut answer);
+              let answer_attempt = read_input_line(&mut answer);
               let final_answer = match answer_attempt {
                 Ok(_) => answer.trim().to_ascii_lowercase(),
                 Err(e) => {
@@ -4478,7 +5297,7 @@ impl NoteArchive {
         print!("\n");
         print_inst!("General collateral's first name:");
         print!("\n");
-        let first_name_attempt = io::stdin().read_line(&mut first_name_choice);
+        let first_name_attempt = read_input_line(&mut first_name_choice);
         match first_name_attempt {
           Ok(_) => break String::from(first_name_choice.trim()),
           Err(e) => {
@@ -4493,7 +5312,7 @@ impl NoteArchive {
       let last_name = loop {
         let mut last_name_choice = String::new();
         println_inst!("General collateral's last name:");
-        let last_name_attempt = io::stdin().read_line(&mut last_name_choice);
+        let last_name_attempt = read_input_line(&mut last_name_choice);
         match last_name_attempt {
           Ok(_) => break String::from(last_name_choice.trim()),
           Err(e) => {
@@ -4508,7 +5327,7 @@ impl NoteArchive {
       let title = loop {
         let mut title_choice = String::new();
         println_inst!("Enter general collateral's role/title.");
-        let title_attempt = io::stdin().read_line(&mut title_choice);
+        let title_attempt = read_input_line(&mut title_choice);
         match title_attempt {
           Ok(_) => break String::from(title_choice.trim()),
           Err(e) => {
@@ -4528,7 +5347,7 @@ impl NoteArchive {
             loop {
               println_yel!("Cancel? (Y/N)");
               let mut cancel = String::new();
-              let cancel_attempt = io::stdin().read_line(&mut cancel);
+              let cancel_attempt = read_input_line(&mut cancel);
               match cancel_attempt {
                 Ok(_) => match &cancel.trim().to_lowercase()[..] {
                   "yes" | "y" => return None,
@@ -4557,7 +5376,7 @@ impl NoteArchive {
         let institution = loop {
           let mut institution_choice = String::new();
           println_inst!("Enter general collateral's institution.");
-          let institution_attempt = io::stdin().read_line(&mut institution_choice);
+          let institution_attempt = read_input_line(&mut institution_choice);
           match institution_attempt {
             Ok(_) => break String::from(institution_choice.trim()),
             Err(e) => {
@@ -4571,7 +5390,7 @@ impl NoteArchive {
         let institution = loop {
           let mut institution_choice = String::new();
           println_inst!("Enter general collateral's institution.");
-          let institution_attempt = io::stdin().read_line(&mut institution_choice);
+          let institution_attempt = read_input_line(&mut institution_choice);
           match institution_attempt {
             Ok(_) => break String::from(institution_choice.trim()),
             Err(e) => {
@@ -4588,7 +5407,7 @@ impl NoteArchive {
           let mut indirect_choice = String::new();
           println_inst!("Should this general collateral be displayed as if a direct provider for every youth, e.g., '[some role] for youth'?");
           println_inst!("YES / Y | NO / N");
-          let indirect_attempt = io::stdin().read_line(&mut indirect_choice);
+          let indirect_attempt = read_input_line(&mut indirect_choice);
           let i = match indirect_attempt {
             Ok(_) => match indirect_choice.to_ascii_lowercase().trim() {
               "yes" | "y" => false,
@@ -4608,7 +5427,7 @@ impl NoteArchive {
           let institution = loop {
             let mut institution_choice = String::new();
             println_inst!("Enter general collateral's institution.");
-            let institution_attempt = io::stdin().read_line(&mut institution_choice);
+            let institution_attempt = read_input_line(&mut institution_choice);
             match institution_attempt {
               Ok(_) => {
                 if institution_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -4719,7 +5538,7 @@ impl NoteArchive {
       }
     }
 
-    let id: u32 = self.collaterals.len() as u32 + 1;
+    let id: u32 = self.next_id("collaterals");
 
     match self.collateral_dup_id_option(&first_name, &last_name, &title, &institution) {
       Some(match_id) => Err([(String::from("duplicate"), match_id)].iter().cloned().collect::<HashMap<String, u32>>()),
@@ -4767,7 +5586,7 @@ impl NoteArchive {
       }
     }
 
-    let id: u32 = self.general_collaterals.len() as u32 + 1;
+    let id: u32 = self.next_id("general_collaterals");
 
     match self.general_collateral_dup_id_option(&first_name, &last_name, &title, &institution) {
       Some(match_id) => Err([(String::from("duplicate"), match_id)].iter().cloned().collect::<HashMap<String, u32>>()),
@@ -4788,250 +5607,79 @@ impl NoteArchive {
     }
 
   }
-  pub fn read_collaterals(filepath: &str) -> Result<Vec<Collateral>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
+  pub fn read_collaterals(filepath: &str) -> Result<(Vec<Collateral>, Vec<LoadError>), Error> {
+    Self::read_text_records(filepath, CollateralRecord, Self::parse_collateral_line)
+  }
+  pub fn parse_collateral_line(line: &str) -> Result<Collateral, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let first_name = String::from(values.text(1, "first name")?);
+    let last_name = String::from(values.text(2, "last name")?);
+    let title = String::from(values.text(3, "title")?);
+    let institution = match values.text(4, "institution")? {
+      "--NONE--" => None,
+      i => Some(String::from(i)),
+    };
+    let pronouns = values.id(5, "pronouns")?;
+    let support_type = match values.text(6, "support type")? {
+      "Natural" => Natural,
+      "Formal" => Formal,
+      st => return Err(FieldError::new("support type", format!("'{}' is not Natural or Formal", st))),
+    };
+    let indirect_support = values.boolean(7, "indirect support")?;
+    let primary_contact = values.boolean(8, "primary contact")?;
+    let guardian = values.boolean(9, "guardian")?;
+    let care_plan_team = values.boolean(10, "care plan team")?;
+
+    // files saved before collaterals had a date leave it off
+    let date = if values.len() > 11 {
+      values.date(11, "date")?
+    } else {
+      Local::now().naive_local().date()
+    };
 
-    let reader = BufReader::new(file);
+    Ok(Collateral::new(
+      id,
+      first_name,
+      last_name,
+      title,
+      institution,
+      pronouns,
+      support_type,
+      indirect_support,
+      primary_contact,
+      guardian,
+      care_plan_team,
+      date,
+    ))
+  }
+  pub fn read_general_collaterals(filepath: &str) -> Result<(Vec<Collateral>, Vec<LoadError>), Error> {
+    Self::read_text_records(filepath, GeneralCollateralRecord, Self::parse_collateral_line)
+  }
+  fn get_first_client_with_collat_id(&self, id: u32) -> Option<&Client> {
+    self.clients.iter().find(|&c| c.foreign_keys["collateral_ids"].iter().any(|c_id| c_id == &id ))
+  }
+  fn sort_collaterals(&mut self) {
 
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
+    // sort by institution
 
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
+    self.collaterals.sort_by(|a, b|
+      match (a.institution.as_ref(), b.institution.as_ref()) {
+        (Some(a_i), Some(b_i)) => a_i.cmp(&b_i),
+        _ => a.institution.cmp(&b.institution),
+      }
+    );
+    
+    // sort by Natural/Formal
+    
+    self.collaterals.sort_by(|a, b| a.support_type.cmp(&b.support_type) );
 
-    let mut collaterals: Vec<Collateral> = vec![];
+    // sort by client ID (first located)
 
-    for line in lines {
-      let line_string = line?;
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      if values.len() < 8 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read collaterals from filepath."));
-      }
-
-      let id: u32 = values[0].parse().unwrap();
-      let first_name = String::from(&values[1]);
-      let last_name = String::from(&values[2]);
-      let title = String::from(&values[3]);
-      let institution = match &values[4][..] {
-        "--NONE--" => None,
-        _ => Some(String::from(&values[4])),
-      };
-      let pronouns: u32 = values[5].parse().unwrap();
-      let support_type = match &values[6][..] {
-        "Natural" => Natural,
-        "Formal" => Formal,
-        _ => panic!("Invalid SupportType saved in file."),
-      };
-      let indirect_support = match &values[7][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'indirect support boolean' value stored in file."),
-      };
-      let primary_contact = match &values[8][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'primary_contact boolean' value stored in file."),
-      };
-      let guardian = match &values[9][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'guardian boolean' value stored in file."),
-      };
-      let care_plan_team = match &values[10][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'care plan team boolean' value stored in file."),
-      };
-
-      let date = if values.len() > 11 {
-        let date_vec: Vec<i32> = match &values[11][..] {
-          "" => vec![],
-          _ => values[11]
-            .split("-")
-            .map(|val| val.parse().unwrap())
-            .collect(),
-        };
-
-        let (year, month, day): (i32, u32, u32) = (date_vec[0], date_vec[1] as u32, date_vec[2] as u32);
-        NaiveDate::from_ymd(year, month, day)
-      } else {
-        Local::now().naive_local().date()
-      };
-
-      let c = Collateral::new(
-        id,
-        first_name,
-        last_name,
-        title,
-        institution,
-        pronouns,
-        support_type,
-        indirect_support,
-        primary_contact,
-        guardian,
-        care_plan_team,
-        date,
-      );
-      collaterals.push(c);
-    }
-    Ok(collaterals)
-  }
-  pub fn read_general_collaterals(filepath: &str) -> Result<Vec<Collateral>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut general_collaterals: Vec<Collateral> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      if values.len() < 8 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read general_collaterals from filepath."));
-      }
-
-      let id: u32 = values[0].parse().unwrap();
-      let first_name = String::from(&values[1]);
-      let last_name = String::from(&values[2]);
-      let title = String::from(&values[3]);
-      let institution = match &values[4][..] {
-        "--NONE--" => None,
-        _ => Some(String::from(&values[4])),
-      };
-      let pronouns: u32 = values[5].parse().unwrap();
-      let support_type = match &values[6][..] {
-        "Natural" => Natural,
-        "Formal" => Formal,
-        _ => panic!("Invalid SupportType saved in file."),
-      };
-      let indirect_support = match &values[7][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'indirect support boolean' value stored in file."),
-      };
-      let primary_contact = match &values[8][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'primary_contact boolean' value stored in file."),
-      };
-      let guardian = match &values[9][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'guardian boolean' value stored in file."),
-      };
-      let care_plan_team = match &values[10][..] {
-        "true" => true,
-        "false" => false,
-        _ => panic!("Invalid 'care plan team boolean' value stored in file."),
-      };
-
-      let date = if values.len() > 11 {
-        let date_vec: Vec<i32> = match &values[11][..] {
-          "" => vec![],
-          _ => values[11]
-            .split("-")
-            .map(|val| val.parse().unwrap())
-            .collect(),
-        };
-
-        let (year, month, day): (i32, u32, u32) = (date_vec[0], date_vec[1] as u32, date_vec[2] as u32);
-        NaiveDate::from_ymd(year, month, day)
-      } else {
-        Local::now().naive_local().date()
-      };
-
-      let c = Collateral::new(
-        id,
-        first_name,
-        last_name,
-        title,
-        institution,
-        pronouns,
-        support_type,
-        indirect_support,
-        primary_contact,
-        guardian,
-        care_plan_team,
-        date,
-      );
-      general_collaterals.push(c);
-    }
-    Ok(general_collaterals)
-  }
-  pub fn write_collaterals(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### collaterals #####\n");
-    for c in &self.collaterals {
-      lines.push_str(&c.to_string()[..]);
-    }
-    lines.push_str("##### collaterals #####");
-    let mut file = File::create(self.filepaths["collateral_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
-  }
-  pub fn write_general_collaterals(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### global collaterals #####\n");
-    for c in &self.general_collaterals {
-      lines.push_str(&c.to_string()[..]);
-    }
-    lines.push_str("##### global collaterals #####");
-    let mut file = File::create(self.filepaths["general_collateral_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
-  }
-  fn get_first_client_with_collat_id(&self, id: u32) -> Option<&Client> {
-    self.clients.iter().find(|&c| c.foreign_keys["collateral_ids"].iter().any(|c_id| c_id == &id ))
-  }
-  fn sort_collaterals(&mut self) {
-
-    // sort by institution
-
-    self.collaterals.sort_by(|a, b|
-      match (a.institution.as_ref(), b.institution.as_ref()) {
-        (Some(a_i), Some(b_i)) => a_i.cmp(&b_i),
-        _ => a.institution.cmp(&b.institution),
-      }
-    );
-    
-    // sort by Natural/Formal
-    
-    self.collaterals.sort_by(|a, b| a.support_type.cmp(&b.support_type) );
-
-    // sort by client ID (first located)
-
-    let first_client_ids: Vec<u32> = self.collaterals
-      .iter()
-      .map(|co| match self.get_first_client_with_collat_id(co.id) {Some(c)=>c.id,None=>0 as u32} )
-      .collect();
+    let first_client_ids: Vec<u32> = self.collaterals
+      .iter()
+      .map(|co| match self.get_first_client_with_collat_id(co.id) {Some(c)=>c.id,None=>0 as u32} )
+      .collect();
 
     let mut sorted_collaterals: Vec<(usize, &Collateral)> = self.collaterals
       .iter()
@@ -5074,7 +5722,6 @@ impl NoteArchive {
   pub fn save_general_collateral(&mut self, collateral: Collateral) {
     self.general_collaterals.push(collateral);
     self.sort_general_collaterals();
-    self.reindex_general_collaterals();
     self.write_to_files();
   }
   fn update_current_collaterals(&mut self, id: u32) {
@@ -5091,7 +5738,7 @@ impl NoteArchive {
       self.display_add_collateral();
       let input = loop {
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -5150,7 +5797,7 @@ impl NoteArchive {
     loop {
       self.display_edit_collateral();
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(e) => {
@@ -5169,7 +5816,7 @@ impl NoteArchive {
         "first" | "fst" | "f" | "1st" | "first name" => {
           println_inst!("Enter new first name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_collateral_first_name(name_choice.trim()) {
               Ok(_) => (),
@@ -5186,7 +5833,7 @@ impl NoteArchive {
         "last" | "lst" | "l" | "last name" => {
           println_inst!("Enter new last name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_collateral_last_name(name_choice.trim()) {
               Ok(_) => (),
@@ -5204,7 +5851,7 @@ impl NoteArchive {
         "title" | "t" => {
           println_inst!("Enter new title:");
           let mut title_choice = String::new();
-          let title_attempt = io::stdin().read_line(&mut title_choice);
+          let title_attempt = read_input_line(&mut title_choice);
           match title_attempt {
             Ok(_) => match self.change_collateral_title(title_choice.trim()) {
               Ok(_) => (),
@@ -5227,7 +5874,7 @@ impl NoteArchive {
           }
           println_inst!("Enter new institution or NONE to remove:");
           let mut inst_choice = String::new();
-          let inst_attempt = io::stdin().read_line(&mut inst_choice);
+          let inst_attempt = read_input_line(&mut inst_choice);
           match inst_attempt {
             Ok(_) => match (self.current_collateral().institution.as_ref(), &inst_choice.trim()[..]) {
               (None, "NONE") => {
@@ -5292,7 +5939,7 @@ impl NoteArchive {
             loop {
               println_inst!("Institution required for formal support. Enter institution below, or 'NONE' to cancel:");
               let mut inst_choice = String::new();
-              let inst_attempt = io::stdin().read_line(&mut inst_choice);
+              let inst_attempt = read_input_line(&mut inst_choice);
               match inst_attempt {
                 Ok(_) => match &inst_choice.trim()[..] {
                   "NONE" => break,
@@ -5347,7 +5994,7 @@ impl NoteArchive {
                   println_yel!("Setting collateral as a natural support will remove the name of any associated institution.");
                   println_yel!("Proceed? (Y/N)");
                   let mut proceed_choice = String::new();
-                  let proceed_attempt = io::stdin().read_line(&mut proceed_choice);
+                  let proceed_attempt = read_input_line(&mut proceed_choice);
                   match proceed_attempt {
                     Ok(_) => match &proceed_choice.trim()[..] {
                       "Y" | "y" | "YES" | "Yes" | "yes" => (),
@@ -5431,7 +6078,7 @@ impl NoteArchive {
     loop {
       self.display_edit_general_collateral();
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(e) => {
@@ -5450,7 +6097,7 @@ impl NoteArchive {
         "first" | "fst" | "f" | "1st" | "first name" => {
           println_inst!("Enter new first name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_general_collateral_first_name(name_choice.trim()) {
               Ok(_) => (),
@@ -5467,7 +6114,7 @@ impl NoteArchive {
         "last" | "lst" | "l" | "last name" => {
           println_inst!("Enter new last name:");
           let mut name_choice = String::new();
-          let name_attempt = io::stdin().read_line(&mut name_choice);
+          let name_attempt = read_input_line(&mut name_choice);
           match name_attempt {
             Ok(_) => match self.change_general_collateral_last_name(name_choice.trim()) {
               Ok(_) => (),
@@ -5485,7 +6132,7 @@ impl NoteArchive {
         "title" | "t" => {
           println_inst!("Enter new title:");
           let mut title_choice = String::new();
-          let title_attempt = io::stdin().read_line(&mut title_choice);
+          let title_attempt = read_input_line(&mut title_choice);
           match title_attempt {
             Ok(_) => match self.change_general_collateral_title(title_choice.trim()) {
               Ok(_) => (),
@@ -5508,7 +6155,7 @@ impl NoteArchive {
           }
           println_inst!("Enter new institution or NONE to remove:");
           let mut inst_choice = String::new();
-          let inst_attempt = io::stdin().read_line(&mut inst_choice);
+          let inst_attempt = read_input_line(&mut inst_choice);
           match inst_attempt {
             Ok(_) => match (self.current_general_collateral().institution.as_ref(), &inst_choice.trim()[..]) {
               (None, "NONE") => {
@@ -5910,7 +6557,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete this collateral?");
       println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -5922,10 +6569,6 @@ impl NoteArchive {
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_collateral();
-          let new_collateral_id = self.reindex_collaterals(None);
-          match new_collateral_id {
-            _ => (),
-          }
           self.write_to_files();
           break true;
         }
@@ -5941,7 +6584,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete this general collateral?");
       println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -5953,7 +6596,6 @@ impl NoteArchive {
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_general_collateral();
-          self.reindex_general_collaterals();
           self.write_to_files();
           break true;
         }
@@ -6056,50 +6698,6 @@ impl NoteArchive {
     self.general_collaterals.retain(|c| c.id != id);
     self.foreign_key.remove("current_general_collateral_id");
   }
-  fn reindex_collaterals(&mut self, current_id: Option<u32>) -> Option<u32> {
-    let mut i: u32 = 1;
-    let mut changes: HashMap<u32, u32> = HashMap::new();
-    let mut new_current_id: Option<u32> = None;
-    for co in &mut self.collaterals {
-      match current_id {
-        Some(id) => {
-          if co.id == id {
-            new_current_id = Some(i);
-          }
-        },
-        None => (),
-      }
-      if co.id == i {
-        ()
-      } else {
-        changes.insert(co.id, i);
-        co.id = i;
-      }
-      i += 1;
-    }
-    for cl in &mut self.clients {
-      let old_ids = cl.foreign_keys["collateral_ids"].clone();
-      let new_ids: Vec<u32> = old_ids.iter()
-        .map(|co_id| match changes.get(co_id) { Some(val) => *val, None => *co_id } )
-        .collect();
-      cl.foreign_keys.insert(String::from("collateral_ids"), new_ids);
-    }
-    for u in &mut self.users {
-      let old_ids = u.foreign_keys["collateral_ids"].clone();
-      let new_ids: Vec<u32> = old_ids.iter()
-        .map(|co_id| match changes.get(co_id) { Some(val) => *val, None => *co_id } )
-        .collect();
-      u.foreign_keys.insert(String::from("collateral_ids"), new_ids);
-    }
-    new_current_id
-  }
-  fn reindex_general_collaterals(&mut self) {
-    let mut i: u32 = 1;
-    for mut co in &mut self.general_collaterals {
-      co.id = i;
-      i += 1;
-    }
-  }
   fn get_collateral_by_id(&self, id: u32) -> Option<&Collateral> {
     self.collaterals.iter().find(|p| p.id == id)
   }
@@ -6108,160 +6706,44 @@ impl NoteArchive {
   }
 
   // pronouns
-  pub fn read_pronouns(&mut self) -> Result<Vec<Pronouns>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(self.filepaths["pronouns_filepath"].clone())
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut pronouns: Vec<Pronouns> = vec![
-      Pronouns::new(
-        1,
-        String::from("he"),
-        String::from("him"),
-        String::from("his"),
-        String::from("his"),
-      ),
-      Pronouns::new(
-        2,
-        String::from("she"),
-        String::from("her"),
-        String::from("her"),
-        String::from("hers"),
-      ),
-      Pronouns::new(
-        3,
-        String::from("they"),
-        String::from("them"),
-        String::from("their"),
-        String::from("theirs"),
-      ),
-    ];
+  pub fn read_pronouns(&mut self) -> Result<(Vec<Pronouns>, Vec<LoadError>), Error> {
+    let filepath = self.filepaths["pronouns_filepath"].clone();
+    let (saved_pronouns, errors) = Self::read_text_records(&filepath, PronounsRecord, Self::parse_pronouns_line)?;
 
-    for line in lines {
-      let line_string = line?;
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
+    let mut pronouns: Vec<Pronouns> = Pronouns::defaults();
 
-      if values.len() < 5 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read pronouns from filepath."));
-      }
-        
+    for saved in saved_pronouns {
       // if any pronouns have a matching ID
       // due to someone editing the default values,
       // change ID to last item in vector + 1, continuing count
         
-      let saved_id: u32 = values[0].parse().unwrap();
+      let saved_id = saved.id;
       let next_id = pronouns[pronouns.len() - 1].id + 1;
-        
-      let subject = String::from(&values[1]);
-      let object = String::from(&values[2]);
-      let possessive_determiner = String::from(&values[3]);
-      let possessive = String::from(&values[4]);
-      
-      let s2 = subject.clone();
-      let o2 = object.clone();
-      let pd2 = possessive_determiner.clone();
-      let p2 = possessive.clone();
 
-      let p = Pronouns::new(next_id, subject, object, possessive_determiner, possessive);
+      let p = Pronouns::new(next_id, saved.subject.clone(), saved.object.clone(), saved.possessive_determiner.clone(), saved.possessive.clone());
       
       if !pronouns.iter().any(|prn| prn == &p) {
         if pronouns.iter().any(|p| p.id == saved_id) {
           self.reassign_pronouns_id(saved_id, next_id);
           pronouns.push(p);
         } else {
-          let p = Pronouns::new(saved_id, s2, o2, pd2, p2);
-          pronouns.push(p);
+          pronouns.push(saved);
         }
       }
     }
-    Ok(pronouns)
+    Ok((pronouns, errors))
   }
-  pub fn read_pronouns_from_file_without_reindexing(filepath: &str) -> Result<Vec<Pronouns>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut pronouns: Vec<Pronouns> = vec![
-      Pronouns::new(
-        1,
-        String::from("he"),
-        String::from("him"),
-        String::from("his"),
-        String::from("his"),
-      ),
-      Pronouns::new(
-        2,
-        String::from("she"),
-        String::from("her"),
-        String::from("her"),
-        String::from("hers"),
-      ),
-      Pronouns::new(
-        3,
-        String::from("they"),
-        String::from("them"),
-        String::from("their"),
-        String::from("theirs"),
-      ),
-    ];
-
-    for line in lines {
-      let line_string = line?;
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      if values.len() < 5 {
-        return Err(Error::new(ErrorKind::Other, "Failed to read pronouns from filepath."));
-      }
-        
-      // if any pronouns have a matching ID
-      // due to someone editing the default values,
-      // change ID to last item in vector + 1, continuing count
-        
-      let next_id = pronouns[pronouns.len() - 1].id + 1;
-        
-      let subject = String::from(&values[1]);
-      let object = String::from(&values[2]);
-      let possessive_determiner = String::from(&values[3]);
-      let possessive = String::from(&values[4]);
-
-      let p = Pronouns::new(next_id, subject, object, possessive_determiner, possessive);
-      pronouns.push(p);
-    }
-    Ok(pronouns)
+  pub fn parse_pronouns_line(line: &str) -> Result<Pronouns, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let subject = String::from(values.text(1, "subject")?);
+    let object = String::from(values.text(2, "object")?);
+    let possessive_determiner = String::from(values.text(3, "possessive determiner")?);
+    let possessive = String::from(values.text(4, "possessive")?);
+    Ok(Pronouns::new(id, subject, object, possessive_determiner, possessive))
+  }
+  pub fn read_pronouns_from_file_without_reindexing(filepath: &str) -> Result<(Vec<Pronouns>, Vec<LoadError>), Error> {
+    Self::read_text_records(filepath, PronounsRecord, Self::parse_pronouns_line)
   }
   fn reassign_pronouns_id(&mut self, old_id: u32, new_id: u32) {
     let mut i = 0;
@@ -6285,7 +6767,7 @@ impl NoteArchive {
           "EDIT / E: edit pronouns",
           "DELETE: delete pronouns",
         );
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -6345,7 +6827,7 @@ impl NoteArchive {
         let mut choice = String::new();
         println_inst!("| {} | {}", "NEW / N: new", "EDIT / E: edit (for all data)");
         println_inst!("| {} | {}", "DELETE / D: delete (for all data)", "QUIT / Q: quit menu/cancel");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -6440,7 +6922,7 @@ impl NoteArchive {
       let subject = loop {
         let mut subject_choice = String::new();
         println_inst!("Enter your subject pronoun (e.g., he, she, they). Example: [pronoun] attended a Care Plan Meeting.");
-        let subject_attempt = io::stdin().read_line(&mut subject_choice);
+        let subject_attempt = read_input_line(&mut subject_choice);
         match subject_attempt {
           Ok(_) => match subject_choice.to_ascii_lowercase().trim() {
             "quit" | "q" => break 'pronouns None,
@@ -6457,7 +6939,7 @@ impl NoteArchive {
         println_inst!(
           "Enter your object pronoun (e.g., him, her, them). Example: Guidance counselor called ICC and left a message for [pronoun]."
         );
-        let object_attempt = io::stdin().read_line(&mut object_choice);
+        let object_attempt = read_input_line(&mut object_choice);
         match object_attempt {
           Ok(_) => match object_choice.to_ascii_lowercase().trim() {
             "quit" | "q" => break 'pronouns None,
@@ -6475,7 +6957,7 @@ impl NoteArchive {
           "Enter your possessive determiner (e.g., his, her, their). Example: ICC used [pronoun] personal vehicle to transport youth home."
         );
         let possessive_determiner_attempt =
-          io::stdin().read_line(&mut possessive_determiner_choice);
+          read_input_line(&mut possessive_determiner_choice);
         match possessive_determiner_attempt {
           Ok(_) => match possessive_determiner_choice.trim() {
             "quit" | "q" => break 'pronouns None,
@@ -6492,7 +6974,7 @@ impl NoteArchive {
         println_inst!(
           "Enter your possessive pronoun (e.g., his, hers, theirs). Example: OPT for youth provided her contact information, and ICC provider [pronoun]."
         );
-        let possessive_attempt = io::stdin().read_line(&mut possessive_choice);
+        let possessive_attempt = read_input_line(&mut possessive_choice);
         match possessive_attempt {
           Ok(_) => match possessive_choice.to_ascii_lowercase().trim() {
             "quit" | "q" => break 'pronouns None,
@@ -6539,7 +7021,7 @@ impl NoteArchive {
       return Err(String::from("Name cannot contain ' | '."));
     }
 
-    let id: u32 = self.pronouns.len() as u32 + 1;
+    let id: u32 = self.next_id("pronouns");
 
     let new_pronouns = Pronouns::new(
       id,
@@ -6560,17 +7042,6 @@ impl NoteArchive {
 
     result
   }
-  pub fn write_pronouns(&mut self) -> std::io::Result<()> {
-    self.delete_duplicate_pronouns();
-    let mut lines = String::from("##### pronouns #####\n");
-    for p in &self.pronouns {
-      lines.push_str(&p.to_string()[..]);
-    }
-    lines.push_str("##### pronouns #####");
-    let mut file = File::create(self.filepaths["pronouns_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
-  }
   pub fn save_pronouns(&mut self, pronouns: Pronouns) {
     self.pronouns.push(pronouns);
     self.write_to_files();
@@ -6679,7 +7150,7 @@ impl NoteArchive {
           let input = loop {
             let mut choice = String::new();
             println_inst!("Enter ID to edit.");
-            let read_attempt = io::stdin().read_line(&mut choice);
+            let read_attempt = read_input_line(&mut choice);
             match read_attempt {
               Ok(_) => break choice,
               Err(e) => {
@@ -6712,7 +7183,7 @@ impl NoteArchive {
         println_inst!("Choose the pronoun to edit (SUBJ, OBJ, POSDET, POS).");
         println_inst!("'Q'/'QUIT' to quit menu.");
         let mut pronoun_to_edit = String::new();
-        let input_attempt = io::stdin().read_line(&mut pronoun_to_edit);
+        let input_attempt = read_input_line(&mut pronoun_to_edit);
         match input_attempt {
           Ok(_) => (),
           Err(_) => {
@@ -6732,7 +7203,7 @@ impl NoteArchive {
           "subj" | "SUBJ" | "subject" | "SUBJECT" | "Subject" => {
             println_inst!("Enter your subject pronoun (e.g., he, she, they). Example: [pronoun] attended a Care Plan Meeting.");
             let mut subject_choice = String::new();
-            let subject_attempt = io::stdin().read_line(&mut subject_choice);
+            let subject_attempt = read_input_line(&mut subject_choice);
             let p = match subject_attempt {
               Ok(_) => String::from(subject_choice.trim()),
               Err(e) => {
@@ -6747,7 +7218,7 @@ impl NoteArchive {
               "Enter your object pronoun (e.g., him, her, them). Example: Guidance counselor called ICC and left a message for [pronoun]."
             );
             let mut object_choice = String::new();
-            let object_attempt = io::stdin().read_line(&mut object_choice);
+            let object_attempt = read_input_line(&mut object_choice);
             let p = match object_attempt {
               Ok(_) => String::from(object_choice.trim()),
               Err(e) => {
@@ -6768,7 +7239,7 @@ impl NoteArchive {
               "Enter your possessive determiner (e.g., his, her, their). Example: ICC used [pronoun] personal vehicle to transport youth home."
             );
             let mut posdet_choice = String::new();
-            let posdet_attempt = io::stdin().read_line(&mut posdet_choice);
+            let posdet_attempt = read_input_line(&mut posdet_choice);
             let p = match posdet_attempt {
               Ok(_) => String::from(posdet_choice.trim()),
               Err(e) => {
@@ -6784,7 +7255,7 @@ impl NoteArchive {
               "Enter your possessive pronoun (e.g., his, hers, theirs). Example: OPT for youth provided her contact information, and ICC provider [pronoun]."
             );
             let mut possessive_choice = String::new();
-            let possessive_attempt = io::stdin().read_line(&mut possessive_choice);
+            let possessive_attempt = read_input_line(&mut possessive_choice);
             let p = match possessive_attempt {
               Ok(_) => String::from(possessive_choice.trim()),
               Err(e) => {
@@ -6810,7 +7281,7 @@ impl NoteArchive {
       let input = loop {
         let mut choice = String::new();
         println_inst!("| {} | {}", "Enter ID to delete.", "QUIT / Q: cancel");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -6840,7 +7311,7 @@ impl NoteArchive {
               println_yel!("Are you sure you want to delete this set of pronouns?");
               println_inst!("'YES'/'Y' to confirm.");
               let mut confirm = String::new();
-              let input_attempt = io::stdin().read_line(&mut confirm);
+              let input_attempt = read_input_line(&mut confirm);
               let command = match input_attempt {
                 Ok(_) => confirm.trim().to_string(),
                 Err(e) => {
@@ -6873,7 +7344,7 @@ impl NoteArchive {
       self.display_view_pronoun(prns_id);
       println_inst!("| {} | {} | {}", "EDIT / E: edit (for all data)", "DELETE / D: delete (for all data)", "QUIT / Q: quit menu");
       let mut decision = String::new();
-      let input_attempt = io::stdin().read_line(&mut decision);
+      let input_attempt = read_input_line(&mut decision);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -6894,7 +7365,7 @@ impl NoteArchive {
           println_inst!("Choose the pronoun to edit (SUBJ, OBJ, POSDET, POS).");
           println_inst!("'Q'/'QUIT' to quit menu.");
           let mut pronoun_to_edit = String::new();
-          let field_input = io::stdin().read_line(&mut pronoun_to_edit);
+          let field_input = read_input_line(&mut pronoun_to_edit);
           match field_input {
             Ok(_) => (),
             Err(_) => {
@@ -6932,7 +7403,7 @@ impl NoteArchive {
             }
           }
           let mut choice = String::new();
-          let attempt = io::stdin().read_line(&mut choice);
+          let attempt = read_input_line(&mut choice);
           let new_prn = match attempt {
             Ok(_) => String::from(choice.trim()),
             Err(e) => {
@@ -6969,44 +7440,15 @@ impl NoteArchive {
   }
 
   // goals
-  pub fn read_goals(filepath: &str) -> Result<Vec<Goal>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut goals: Vec<Goal> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      let id: u32 = values[0].parse().unwrap();
-      let client_id: u32 = values[1].parse().unwrap();
-      let subject = String::from(&values[2]);
-        
-      
-      let g = Goal::new(id, client_id, subject);
-      goals.push(g);
-    }
-      
-    Ok(goals)
+  pub fn read_goals(filepath: &str) -> Result<(Vec<Goal>, Vec<LoadError>), Error> {
+    Self::read_text_records(filepath, GoalRecord, Self::parse_goal_line)
+  }
+  pub fn parse_goal_line(line: &str) -> Result<Goal, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let client_id = values.id(1, "client ID")?;
+    let subject = String::from(values.text(2, "goal")?);
+    Ok(Goal::new(id, client_id, subject))
   }
   fn select_all_goals(&mut self, selected: Option<Vec<u32>>) -> Option<u32> {
     let chosen_id = loop {
@@ -7014,7 +7456,7 @@ impl NoteArchive {
       let input = loop {
         let mut choice = String::new();
         println_inst!("Enter ID to choose goal, or 'CANCEL' to cancel.");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -7066,7 +7508,7 @@ impl NoteArchive {
         println_inst!("Enter ID to choose goal.");
         println_inst!("Press ENTER to continue with the selected options.");
         println_inst!("| {} | {} | {}", "NEW / N: Create a new goal", "ADD / A: Add a goal from the collective list", "CANCEL / C: cancel");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -7138,14 +7580,61 @@ impl NoteArchive {
     };
     Some(chosen_id)
   }
+  /// chooses goals for a ClientGoal blank and whether it shows the goals, their objectives or their latest
+  /// progress, returning the blank's text and the goal IDs, or None if no goals were chosen
+  fn choose_client_goal_fill_in(&mut self) -> Option<(String, Vec<u32>)> {
+    let mut fill_ins: Vec<u32> = vec![];
+    while let Some(blank_id) = self.select_client_goals(Some(fill_ins.clone())) {
+      if !fill_ins.iter().any(|fi| fi == &blank_id ) {
+        fill_ins.push(blank_id);
+      } else {
+        fill_ins.retain(|fi| fi != &blank_id )
+      }
+    }
+    if fill_ins.is_empty() {
+      return None;
+    }
+    let renderings = GoalRendering::listed();
+    let rendering = loop {
+      for (i, r) in renderings.iter().enumerate() {
+        println_inst!("{}: Fill in {}", i + 1, r);
+      }
+      println_inst!("Choose what to fill in by number, or press ENTER for the goal.");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Could not read input; try again ({}).", e);
+        continue;
+      }
+      match choice.trim() {
+        "" => break GoalRendering::Goal,
+        c => match c.parse::<usize>().ok().and_then(|i| i.checked_sub(1) ).and_then(|i| renderings.get(i) ) {
+          Some(r) => break *r,
+          None => println_err!("Please choose from among the listed options."),
+        },
+      }
+    };
+    let fill_in_strings: Vec<String> = fill_ins.iter().map(|g_id| self.get_goal_by_id(*g_id).unwrap().render(rendering) ).collect();
+    let final_blank_string = if fill_in_strings.len() > 1 {
+      format!(
+        "{}{}{}",
+        fill_in_strings[..fill_in_strings.len()-1].join(", "),
+        " and ",
+        fill_in_strings[fill_in_strings.len()-1],
+      )
+    } else {
+      fill_in_strings[0].clone()
+    };
+    Some((final_blank_string, fill_ins))
+  }
   fn choose_client_goals(&mut self) {
     loop {
       self.display_current_client_goals(None);
       let input = loop {
         let mut choice = String::new();
-        println_inst!("Enter ID to edit or delete goal.");
-        println_inst!("| {} | {} | {}", "NEW / N: Create a new goal", "ADD / A: Add a goal from the collective list", "QUIT / Q: exit menu");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        println_inst!("Enter ID to edit or delete goal, or to add objectives and progress ratings.");
+        println_inst!("| {} | {} | {}", "NEW / N: Create a new goal", "ADD / A: Add a goal from the collective list", "REPORT / R: goal progress report");
+        println_inst!("QUIT / Q: exit menu");
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -7185,6 +7674,7 @@ impl NoteArchive {
             }
           }
         }
+        "report" | "r" => self.choose_goal_progress_report(),
         "quit" | "q" => break,
         _ => {
           let id = match input.trim().parse::<u32>() {
@@ -7207,6 +7697,37 @@ impl NoteArchive {
       }
     }
   }
+  fn choose_goal_progress_report(&self) {
+    let report = GoalProgressReport::new(self, self.current_client().id, None, None);
+    loop {
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_on_bg!("{:-^58}", " Goal progress ");
+      println!("{}", report.to_display_string());
+      println_inst!("| {} | {}", "CSV / S: save as CSV", "QUIT / Q: quit menu");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "quit" | "q" => break,
+        "csv" | "s" => {
+          let c = self.current_client();
+          let fp = export_dir(&self.filepaths["archive_filepath"])
+            .join(format!("goal_progress_{}_{}.csv", c.last_name.to_ascii_lowercase(), c.first_name.to_ascii_lowercase()));
+          match fs::create_dir_all(fp.parent().unwrap()).and_then(|_| write_file_atomically(&fp, report.to_csv().as_bytes()) ) {
+            Ok(_) => println_suc!("Saved {}", fp.display()),
+            Err(e) => println_err!("Failed to save {}: {}", fp.display(), e),
+          }
+          thread::sleep(time::Duration::from_secs(2));
+        },
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+    }
+  }
   fn display_goals(&self, selected: Option<Vec<u32>>) {
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
     println_on_bg!("{:-^161}", "-");
@@ -7240,8 +7761,28 @@ impl NoteArchive {
     let heading = format!(" Goal for {} ", client);
     println_on_bg!("{:-^161}", heading);
     println_on_bg!("{:-^161}", "-");
-    println_on_bg!("{:->5} | {:-<120}", " ID", " Goal ");
-    println_on_bg!("{: >5} | {: <120}", g.id, g.goal);
+    println_on_bg!("{:->5} | {:-<120} | {:-<30}", " ID", " Goal ", " Status ");
+    println_on_bg!("{: >5} | {: <120} | {: <30}", g.id, g.goal, g.status.to_string());
+    if !g.objectives.is_empty() {
+      println_on_bg!("{:-^161}", " Objectives ");
+      println_on_bg!("{:->5} | {:-<80} | {:-<40} | {:-<27}", " #", " Objective ", " Baseline ", " Target date ");
+      for (i, o) in g.objectives.iter().enumerate() {
+        println_on_bg!(
+          "{: >5} | {: <80} | {: <40} | {: <27}",
+          i + 1,
+          o.description,
+          o.baseline.clone().unwrap_or_default(),
+          o.target_date.map(|d| d.format("%Y-%m-%d").to_string() ).unwrap_or_default(),
+        );
+      }
+    }
+    if !g.progress.is_empty() {
+      println_on_bg!("{:-^161}", " Progress ");
+      println_on_bg!("{:-<12} | {:-<25} | {:-<118}", "Date ", " Rating ", " Comment ");
+      for p in &g.progress {
+        println_on_bg!("{: <12} | {: <25} | {: <118}", p.date.format("%Y-%m-%d").to_string(), p.rating.to_string(), p.comment);
+      }
+    }
     println_on_bg!("{:-^161}", "-");
   }
   fn current_client_goals(&self) -> Vec<&Goal> {
@@ -7254,17 +7795,17 @@ impl NoteArchive {
     let heading = format!(" All goals for {} ", client.full_name());
     println_on_bg!("{:-^161}", heading);
     println_on_bg!("{:-^161}", "-");
-    println_on_bg!("{:->5} | {:-<120}", " ID", "Goal ");
+    println_on_bg!("{:->5} | {:-<100} | {:-<15} | {:-<33}", " ID", "Goal ", " Status ", " Latest progress ");
     for g in &self.current_client_goals() {
+      let latest = match g.latest_progress() {
+        Some(p) => format!("{} ({})", p.rating, p.date.format("%Y-%m-%d")),
+        None => String::new(),
+      };
       match selected.clone() {
-        None => println_on_bg!("{: >5} | {: <120}", g.id, g.goal),
-        Some(sel) => {
-          if sel.iter().any(|id| id == &g.id ) {
-            println_suc!("{: >5} | {: <120}", g.id, g.goal);
-          } else {
-            println_on_bg!("{: >5} | {: <120}", g.id, g.goal);
-          }
-        }
+        Some(sel) if sel.iter().any(|id| id == &g.id ) => {
+          println_suc!("{: >5} | {: <100} | {: <15} | {: <33}", g.id, g.goal, g.status.to_string(), latest);
+        },
+        _ => println_on_bg!("{: >5} | {: <100} | {: <15} | {: <33}", g.id, g.goal, g.status.to_string(), latest),
       }
     }
     println_on_bg!("{:-^161}", "-");
@@ -7275,7 +7816,7 @@ impl NoteArchive {
       let goal_string = loop {
         let mut subject_choice = String::new();
         println_inst!("Enter the client's goal.");
-        let subject_attempt = io::stdin().read_line(&mut subject_choice);
+        let subject_attempt = read_input_line(&mut subject_choice);
         match subject_attempt {
           Ok(_) => match subject_choice.to_ascii_lowercase().trim() {
             "quit" | "q" => break 'goal None,
@@ -7318,7 +7859,7 @@ impl NoteArchive {
       return Err(String::from("Goal cannot contain ' | '."));
     }
 
-    let id: u32 = self.goals.len() as u32 + 1;
+    let id: u32 = self.next_id("goals");
 
     let new_goal = Goal::new(
       id,
@@ -7337,19 +7878,8 @@ impl NoteArchive {
 
     result
   }
-  pub fn write_goals(&mut self) -> std::io::Result<()> {
-    let mut lines = String::from("##### goals #####\n");
-    for p in &self.goals {
-      lines.push_str(&p.to_string()[..]);
-    }
-    lines.push_str("##### goals #####");
-    let mut file = File::create(self.filepaths["goal_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
-  }
   pub fn save_goal(&mut self, goal: Goal) {
     self.goals.push(goal);
-    self.reindex_goals();
     self.write_to_files();
   }
   fn load_goal(&mut self, id: u32) -> std::io::Result<()> {
@@ -7383,8 +7913,9 @@ impl NoteArchive {
       let input = loop {
         let mut choice = String::new();
         println_inst!("Enter new text for this goal, or 'CANCEL' to go back.");
+        println_inst!("| {} | {} | {}", "OBJECTIVE / O: add an objective", "PROGRESS / P: rate progress", "STATUS / S: change status");
         println_inst!("DELETE / D to delete.");
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.trim().to_string(),
           Err(e) => {
@@ -7401,12 +7932,24 @@ impl NoteArchive {
           self.choose_delete_goal();
           break;
         }
-        _ => (),
-      }
-      let confirm = loop {
-        let mut choice = String::new();
+        "objective" | "o" => {
+          self.choose_add_goal_objective();
+          continue;
+        }
+        "progress" | "p" => {
+          self.choose_add_goal_progress();
+          continue;
+        }
+        "status" | "s" => {
+          self.choose_goal_status();
+          continue;
+        }
+        _ => (),
+      }
+      let confirm = loop {
+        let mut choice = String::new();
         println_inst!("Change goal to '{}' ( Y / N )?", &input);
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.trim().to_ascii_lowercase(),
           Err(e) => {
@@ -7429,13 +7972,96 @@ impl NoteArchive {
       }
     }
   }
+  /// asks for a line of text; a blank answer gives None, and the outer None means the user cancelled
+  fn read_goal_text(prompt: &str) -> Option<Option<String>> {
+    loop {
+      println_inst!("{}", prompt);
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Could not read input; try again ({}).", e);
+        continue;
+      }
+      return match choice.trim() {
+        "" => Some(None),
+        c if c.eq_ignore_ascii_case("cancel") => None,
+        c => Some(Some(c.to_string())),
+      };
+    }
+  }
+  fn choose_add_goal_objective(&mut self) {
+    println_inst!("Enter 'CANCEL' at any time to cancel.");
+    let description = match Self::read_goal_text("Enter the measurable objective, such as 'attend 4 of 5 school days per week'.") {
+      Some(Some(d)) => d,
+      _ => return,
+    };
+    let baseline = match Self::read_goal_text("Enter the baseline, or press ENTER to skip.") {
+      Some(b) => b,
+      None => return,
+    };
+    let target_date = match Self::read_episode_date("Enter the target date (YYYY-MM-DD), or press ENTER to skip.", None) {
+      Some(d) => d,
+      None => return,
+    };
+    self.current_goal_mut().unwrap().objectives.push(Objective::new(description, baseline, target_date));
+    self.write_to_files();
+  }
+  fn choose_add_goal_progress(&mut self) {
+    let today = Local::now().naive_local().date();
+    println_inst!("Enter 'CANCEL' at any time to cancel.");
+    let date = match Self::read_episode_date("Enter the date of the rating (YYYY-MM-DD), or press ENTER for today.", Some(today)) {
+      Some(Some(date)) => date,
+      _ => return,
+    };
+    let rating = loop {
+      for (i, r) in ProgressRating::listed().iter().enumerate() {
+        println_inst!("{}: {}", i + 1, r);
+      }
+      match Self::read_goal_text("Choose a progress rating by number.") {
+        Some(Some(r)) => match ProgressRating::parse(&r) {
+          Ok(rating) => break rating,
+          Err(e) => println_err!("{}", e),
+        },
+        Some(None) => println_err!("A rating is needed to record progress."),
+        None => return,
+      }
+    };
+    let comment = match Self::read_goal_text("Enter a comment, such as how progress was measured, or press ENTER to skip.") {
+      Some(c) => c.unwrap_or_default(),
+      None => return,
+    };
+    let client_id = self.current_goal().unwrap().client_id;
+    let note_day_id = self.note_days.iter()
+      .find(|nd| nd.date == date && nd.foreign_key["client_id"] == client_id )
+      .map(|nd| nd.id );
+    self.current_goal_mut().unwrap().add_progress(ProgressEntry::new(date, rating, comment, note_day_id));
+    self.write_to_files();
+  }
+  fn choose_goal_status(&mut self) {
+    let statuses = GoalStatus::listed();
+    for (i, status) in statuses.iter().enumerate() {
+      println_inst!("{}: {}", i + 1, status);
+    }
+    let status = match Self::read_goal_text("Choose the goal's status by number, or press ENTER to keep it.") {
+      Some(Some(s)) => match s.parse::<usize>().ok().and_then(|i| i.checked_sub(1) ).and_then(|i| statuses.get(i) ) {
+        Some(status) => *status,
+        None => {
+          println_err!("Please choose from among the listed statuses.");
+          thread::sleep(time::Duration::from_secs(1));
+          return;
+        },
+      },
+      _ => return,
+    };
+    self.current_goal_mut().unwrap().status = status;
+    self.write_to_files();
+  }
   fn choose_delete_goal(&mut self) {
     loop {
       self.display_goal();
       println_yel!("Are you sure you want to delete this goal?");
       println_inst!("'YES'/'Y' to confirm.");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -7447,7 +8073,6 @@ impl NoteArchive {
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_goal();
-          self.reindex_goals();
           break;
         },
         _ => {
@@ -7462,7 +8087,7 @@ impl NoteArchive {
   //     let input = loop {
   //       let mut choice = String::new();
   //       println_inst!("| {} | {}", "Enter ID to delete.", "CANCEL / C: cancel");
-  //       let read_attempt = io::stdin().read_line(&mut choice);
+  //       let read_attempt = read_input_line(&mut choice);
   //       match read_attempt {
   //         Ok(_) => break choice.to_ascii_lowercase(),
   //         Err(e) => {
@@ -7492,7 +8117,7 @@ impl NoteArchive {
   //             println_yel!("Are you sure you want to delete this goal?");
   //             println_inst!("'YES'/'Y' to confirm.");
   //             let mut confirm = String::new();
-  //             let input_attempt = io::stdin().read_line(&mut confirm);
+  //             let input_attempt = read_input_line(&mut confirm);
   //             let command = match input_attempt {
   //               Ok(_) => confirm.trim().to_string(),
   //               Err(e) => {
@@ -7530,13 +8155,6 @@ impl NoteArchive {
     self.delete_from_blanks(String::from("goal"), id);
     self.delete_goal(id);
   }
-  fn reindex_goals(&mut self) {
-    let mut i: u32 = 1;
-    for mut g in &mut self.goals {
-      g.id = i;
-      i += 1;
-    }
-  }
 
   // note_days
   fn current_note_day_mut(&mut self) -> &mut NoteDay {
@@ -7653,7 +8271,7 @@ impl NoteArchive {
         }
         println_inst!("| {} | {} | {}", "Choose note by ID.", "ALL / A: View all notes", "QUIT / Q: quit menu");
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -7710,7 +8328,7 @@ impl NoteArchive {
         'today: loop {
 
           println_inst!("Change date to today? (Y/N)");
-          let today_attempt = io::stdin().read_line(&mut today_choice);
+          let today_attempt = read_input_line(&mut today_choice);
           match today_attempt {
             Ok(_) => (),
             Err(e) => {
@@ -7737,7 +8355,7 @@ impl NoteArchive {
       let year = loop {
         let mut this_year_choice = String::new();
         println_inst!("This year ({})? (Y/N)", today.year());
-        let this_year_attempt = io::stdin().read_line(&mut this_year_choice);
+        let this_year_attempt = read_input_line(&mut this_year_choice);
         match this_year_attempt {
           Ok(_) => match &this_year_choice.trim()[..] {
             "YES" | "yes" | "Yes" | "Y" | "y" => {
@@ -7747,7 +8365,7 @@ impl NoteArchive {
             "NO" | "no" | "No" | "N" | "n" => {
               let mut year_choice = String::new();
               println_inst!("What year?");
-              let year_attempt = io::stdin().read_line(&mut year_choice);
+              let year_attempt = read_input_line(&mut year_choice);
               match year_attempt {
                 Ok(_) => {
                   if year_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -7792,7 +8410,7 @@ impl NoteArchive {
           loop {
             let mut this_month_choice = String::new();
             println_inst!("This month ({})? (Y/N)", today.month());
-            let this_month_attempt = io::stdin().read_line(&mut this_month_choice);
+            let this_month_attempt = read_input_line(&mut this_month_choice);
             match this_month_attempt {
               Ok(_) => match &this_month_choice.trim()[..] {
                 "YES" | "yes" | "Yes" | "Y" | "y" => break 'month today.month(),
@@ -7815,7 +8433,7 @@ impl NoteArchive {
         }
         let mut month_choice = String::new();
         println_inst!("What month?");
-        let month_attempt = io::stdin().read_line(&mut month_choice);
+        let month_attempt = read_input_line(&mut month_choice);
         match month_attempt {
           Ok(_) => {
             if month_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -7845,7 +8463,7 @@ impl NoteArchive {
       let day = loop {
         let mut day_choice = String::new();
         println_inst!("What day?");
-        let day_attempt = io::stdin().read_line(&mut day_choice);
+        let day_attempt = read_input_line(&mut day_choice);
         match day_attempt {
           Ok(_) => {
             if day_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -7903,7 +8521,7 @@ impl NoteArchive {
     }
     println_inst!("{:-^150}", " Enter any input to return to the previous menu. ");
     let mut s = String::new();
-    let input_attempt = io::stdin().read_line(&mut s);
+    let input_attempt = read_input_line(&mut s);
     match input_attempt {
       _ => (),
     }
@@ -7942,7 +8560,7 @@ impl NoteArchive {
           );
         }
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -8024,7 +8642,7 @@ impl NoteArchive {
     }
     output
   }
-  fn get_note_day_notes_by_category(&self, nd: NoteDay) -> BTreeMap<NoteCategory, Vec<Note>> {
+  pub fn get_note_day_notes_by_category(&self, nd: NoteDay) -> BTreeMap<NoteCategory, Vec<Note>> {
     let mut output: BTreeMap<NoteCategory, Vec<Note>> = BTreeMap::new();
     let mut ns = self.note_day_notes(nd);
     ns.sort_by(|a, b| a.id.cmp(&b.id) );
@@ -8053,11 +8671,130 @@ impl NoteArchive {
     }
     println_inst!("{}", "Enter any input to return to the previous menu.");
     let mut s = String::new();
-    let input_attempt = io::stdin().read_line(&mut s);
+    let input_attempt = read_input_line(&mut s);
     match input_attempt {
       _ => (),
     }
   }
+  fn export_current_note_day(&self) {
+    let nd = self.current_note_day();
+    let dir = export_dir(&self.filepaths["archive_filepath"]);
+    match ExportDocument::new(self, &[nd]).write_files(&dir, &ExportFormat::all()) {
+      Ok(written) => {
+        for fp in written {
+          println_suc!("Saved {}", fp.display());
+        }
+      },
+      Err(e) => println_err!("Failed to export note day to {}: {}", dir.display(), e),
+    }
+    println_inst!("{}", "Enter any input to return to the previous menu.");
+    let mut s = String::new();
+    let _ = read_input_line(&mut s);
+  }
+  fn save_current_note_day_pdf(&self) {
+    let nd = self.current_note_day();
+    let (file_name, bytes) = note_day_pdf(self, nd);
+    let fp = export_dir(&self.filepaths["archive_filepath"]).join(file_name);
+    match write_pdf(&fp, &bytes) {
+      Ok(_) => println_suc!("Saved {}", fp.display()),
+      Err(e) => println_err!("Failed to save {}: {}", fp.display(), e),
+    }
+    println_inst!("{}", "Enter any input to return to the previous menu.");
+    let mut s = String::new();
+    let _ = read_input_line(&mut s);
+  }
+  fn display_ehr_entry(&self, paragraphs: &[EhrParagraph]) {
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    let nd = self.current_note_day();
+    let name = self.get_client_by_id(nd.foreign_key["client_id"]).unwrap().full_name();
+    let heading = format!(" EHR entry for {} for {} ", name, nd.heading_date());
+    println_suc!("{:-^150}", &heading);
+    for p in paragraphs {
+      match p.char_limit {
+        Some(limit) => println_suc!("{} ({}/{} characters)", p.heading(), p.char_count(), limit),
+        None => println_suc!("{} ({} characters)", p.heading(), p.char_count()),
+      }
+      if let Some(warning) = p.limit_warning() {
+        println_err!("{}", warning);
+      }
+      println!("{}\n", p.text);
+    }
+  }
+  /// one paragraph per EHR text box, copied in turn with the clipboard command or saved as separate files
+  fn choose_ehr_entry(&self) {
+    let config = Config::load().unwrap_or_default();
+    let nd = self.current_note_day().clone();
+    let paragraphs = ehr_paragraphs(self, &nd, &config.char_limits);
+    if paragraphs.is_empty() {
+      println_err!("There are no notes for this day.");
+      thread::sleep(time::Duration::from_secs(2));
+      return;
+    }
+    loop {
+      self.display_ehr_entry(&paragraphs);
+      println_inst!(
+        "| {} | {} | {}",
+        "COPY / C: copy each category in turn",
+        "FILES / F: save each category to a file",
+        "QUIT / Q: quit menu",
+      );
+      let mut choice = String::new();
+      let read_attempt = read_input_line(&mut choice);
+      let input = match read_attempt {
+        Ok(_) => choice.to_ascii_lowercase(),
+        Err(e) => {
+          println_err!("Could not read input; try again ({}).", e);
+          continue;
+        }
+      };
+      match input.trim() {
+        "copy" | "c" => match &config.clipboard_command {
+          Some(command) => Self::copy_ehr_paragraphs(command, &paragraphs),
+          None => {
+            println_err!("No clipboard command is set. Add 'clipboard_command = COMMAND' to the config file.");
+            thread::sleep(time::Duration::from_secs(3));
+          },
+        },
+        "files" | "f" => {
+          let dir = export_dir(&self.filepaths["archive_filepath"]);
+          match write_ehr_files(&paragraphs, &dir, &ehr_file_stem(self, &nd)) {
+            Ok(written) => {
+              for fp in written {
+                println_suc!("Saved {}", fp.display());
+              }
+            },
+            Err(e) => println_err!("Failed to save files to {}: {}", dir.display(), e),
+          }
+          println_inst!("{}", "Enter any input to return to the previous menu.");
+          let mut s = String::new();
+          let _ = read_input_line(&mut s);
+        },
+        "quit" | "q" => break,
+        _ => {
+          println_err!("Invalid command.");
+          thread::sleep(time::Duration::from_secs(1));
+        },
+      }
+    }
+  }
+  fn copy_ehr_paragraphs(command: &str, paragraphs: &[EhrParagraph]) {
+    for (i, p) in paragraphs.iter().enumerate() {
+      if let Err(e) = pipe_to_command(command, &p.text) {
+        println_err!("Failed to copy {}: {}", p.heading(), e);
+        thread::sleep(time::Duration::from_secs(3));
+        return;
+      }
+      match paragraphs.get(i + 1) {
+        Some(next) => println_suc!("Copied {}. Paste it into the EHR, then press Enter to copy {} (or QUIT / Q to stop).", p.heading(), next.heading()),
+        None => println_suc!("Copied {}, the last category. Press Enter to continue.", p.heading()),
+      }
+      let mut s = String::new();
+      let _ = read_input_line(&mut s);
+      if matches!(s.trim().to_ascii_lowercase().as_str(), "quit" | "q") {
+        return;
+      }
+    }
+  }
   fn print_note_day(&self, nd: NoteDay) {
     let name = self.get_client_by_id(nd.foreign_key["client_id"]).unwrap().full_name();
     let date: String = nd.heading_date();
@@ -8144,7 +8881,7 @@ impl NoteArchive {
       println_inst!("CANCEL / C: Cancel");
 
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice,
         Err(e) => {
@@ -8194,18 +8931,21 @@ impl NoteArchive {
       self.display_note_day();
 
       println_inst!(
-        "| {} | {} | {}",
+        "| {} | {} | {} | {}",
         "NEW / N: new note",
         "PRINT / P: display all client notes for this day",
+        "PDF / F: save this day as a PDF",
         "DELETE: delete individual records",
       );
       println_inst!(
-        "| {} | {}",
+        "| {} | {} | {} | {}",
+        "EHR / H: copy each category for the EHR",
+        "EXPORT / X: save this day as Markdown, HTML and text files",
         "DELETE ALL: delete all",
         "QUIT / Q: quit menu"
       );
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -8236,6 +8976,15 @@ impl NoteArchive {
         "print" | "p" => {
           self.print_current_note_day();
         }
+        "export" | "x" => {
+          self.export_current_note_day();
+        }
+        "ehr" | "h" => {
+          self.choose_ehr_entry();
+        }
+        "pdf" | "f" => {
+          self.save_current_note_day_pdf();
+        }
         _ => {
           match input.to_string().parse() {
             Ok(num) => {
@@ -8280,7 +9029,7 @@ impl NoteArchive {
       print!("\n");
       print_inst!("Note for today? (Y/N)");
       print!("\n");
-      let today_attempt = io::stdin().read_line(&mut today_choice);
+      let today_attempt = read_input_line(&mut today_choice);
       match today_attempt {
         Ok(_) => (),
         Err(e) => {
@@ -8297,14 +9046,14 @@ impl NoteArchive {
           let year = loop {
             let mut this_year_choice = String::new();
             println_inst!("This year ({})? (Y/N)", today.year());
-            let this_year_attempt = io::stdin().read_line(&mut this_year_choice);
+            let this_year_attempt = read_input_line(&mut this_year_choice);
             match this_year_attempt {
               Ok(_) => match &this_year_choice.trim()[..] {
                 "YES" | "yes" | "Yes" | "Y" | "y" => break today.year(),
                 "NO" | "no" | "No" | "N" | "n" => {
                   let mut year_choice = String::new();
                   println_inst!("What year?");
-                  let year_attempt = io::stdin().read_line(&mut year_choice);
+                  let year_attempt = read_input_line(&mut year_choice);
                   match year_attempt {
                     Ok(_) => {
                       if year_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -8346,14 +9095,14 @@ impl NoteArchive {
           let month = loop {
             let mut this_month_choice = String::new();
             println_inst!("This month ({})? (Y/N)", today.month());
-            let this_month_attempt = io::stdin().read_line(&mut this_month_choice);
+            let this_month_attempt = read_input_line(&mut this_month_choice);
             match this_month_attempt {
               Ok(_) => match &this_month_choice.trim()[..] {
                 "YES" | "yes" | "Yes" | "Y" | "y" => break today.month(),
                 "NO" | "no" | "No" | "N" | "n" => {
                   let mut month_choice = String::new();
                   println_inst!("What month?");
-                  let month_attempt = io::stdin().read_line(&mut month_choice);
+                  let month_attempt = read_input_line(&mut month_choice);
                   match month_attempt {
                     Ok(_) => {
                       if month_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -8395,7 +9144,7 @@ impl NoteArchive {
           let day = loop {
             let mut day_choice = String::new();
             println_inst!("What day?");
-            let day_attempt = io::stdin().read_line(&mut day_choice);
+            let day_attempt = read_input_line(&mut day_choice);
             match day_attempt {
               Ok(_) => {
                 if day_choice.trim().to_ascii_lowercase() == String::from("cancel") {
@@ -8450,7 +9199,7 @@ impl NoteArchive {
               None => {
                 println_yel!("A note must be connected with a client. Cancel creating note ( Y / N )?");
                 let mut answer = String::new();
-                let answer_attempt = io::stdin().read_line(&mut answer);
+                let answer_attempt = read_input_line(&mut answer);
                 let final_answer = match answer_attempt {
                   Ok(_) => answer.trim().to_ascii_lowercase(),
                   Err(e) => {
@@ -8499,91 +9248,36 @@ impl NoteArchive {
         None => None,
       }
   }
-  fn generate_unique_new_note_day(
+  pub fn generate_unique_new_note_day(
     &mut self,
     date: NaiveDate,
     user_id: u32,
     client_id: u32,
   ) -> Result<NoteDay, String> {
-    let id: u32 = self.note_days.len() as u32 + 1;
+    let id: u32 = self.next_id("note_days");
 
     match self.note_day_dup_id_option(&date, user_id, client_id) {
       Some(_) => Err(String::from("A note record already exists for that client on the given date.")),
       None => Ok(NoteDay::new(id, date, user_id, client_id, vec![]))
     }
   }
-  pub fn read_note_days(filepath: &str) -> Result<Vec<NoteDay>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut note_days: Vec<NoteDay> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      let id: u32 = values[0].parse().unwrap();
-      
-      let date_vec: Vec<i32> = match &values[1][..] {
-        "" => vec![],
-        _ => values[1]
-        .split("-")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      let (year, month, day): (i32, u32, u32) = (date_vec[0], date_vec[1] as u32, date_vec[2] as u32);
-      let date = NaiveDate::from_ymd(year, month, day);
-
-      let user_id: u32 = values[2].parse().unwrap();
-      let client_id: u32 = values[3].parse().unwrap();
-
-      let notes: Vec<u32> = match &values[4][..] {
-        "" => vec![],
-        _ => values[4]
-          .split("#")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      let nd = NoteDay::new(id, date, user_id, client_id, notes);
-      note_days.push(nd);
-    }
+  pub fn read_note_days(filepath: &str) -> Result<(Vec<NoteDay>, Vec<LoadError>), Error> {
+    let (mut note_days, errors) = Self::read_text_records(filepath, NoteDayRecord, Self::parse_note_day_line)?;
     note_days.sort_by(|a, b| a.foreign_key["client_id"].cmp(&b.foreign_key["client_id"]));
     note_days.sort_by(|a, b| a.foreign_key["user_id"].cmp(&b.foreign_key["user_id"]));
     note_days.sort_by(|a, b| b.date.cmp(&a.date));
-    Ok(note_days)
+    Ok((note_days, errors))
   }
-  pub fn write_note_days(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### note_days #####\n");
-    for c in &self.note_days {
-      lines.push_str(&c.to_string()[..]);
-    }
-    lines.push_str("##### note_days #####");
-    let mut file = File::create(self.filepaths["note_day_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
+  pub fn parse_note_day_line(line: &str) -> Result<NoteDay, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let date = values.date(1, "date")?;
+    let user_id = values.id(2, "user ID")?;
+    let client_id = values.id(3, "client ID")?;
+    let notes = values.ids(4, "note IDs")?;
+    Ok(NoteDay::new(id, date, user_id, client_id, notes))
   }
-  fn save_note_day(&mut self, note_day: NoteDay) {
+  pub fn save_note_day(&mut self, note_day: NoteDay) {
 
     let pos = self.note_days.binary_search_by(|nd| note_day.date.cmp(&nd.date)
       .then_with(|| nd.foreign_key["user_id"].cmp(&note_day.foreign_key["user_id"]))
@@ -8599,7 +9293,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete all records for {} from {}?", self.current_client().full_name(), self.current_note_day().fmt_date_long());
       println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -8611,10 +9305,6 @@ impl NoteArchive {
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_note_day();
-          let new_note_day_id = self.reindex_note_days(None);
-          match new_note_day_id {
-            _ => (),
-          }
           self.write_to_files();
           break;
         }
@@ -8632,33 +9322,12 @@ impl NoteArchive {
         Ok(_) => self.delete_current_note(),
       }
     }
-    let new_note_id = self.reindex_notes(None);
-    match new_note_id {
-      _ => (),
-    }
     let id = self.foreign_key.get("current_note_day_id").unwrap().to_owned();
     self.delete_from_blanks(String::from("note_day"), id);
     self.note_days.retain(|nd| nd.id != id);
     self.foreign_key.remove("current_note_day_id");
   }
-  fn reindex_note_days(&mut self, current_id: Option<u32>) -> Option<u32> {
-    let mut i: u32 = 1;
-    let mut new_current_id: Option<u32> = None;
-    for mut nd in &mut self.note_days {
-      match current_id {
-        Some(id) => {
-          if nd.id == id {
-            new_current_id = Some(i);
-          }
-        },
-        None => (),
-      }
-      nd.id = i;
-      i += 1;
-    }
-    new_current_id
-  }
-  fn get_note_day_by_id(&self, id: u32) -> Option<&NoteDay> {
+  pub fn get_note_day_by_id(&self, id: u32) -> Option<&NoteDay> {
     self.note_days.iter().find(|nd| nd.id == id)
   }
   /// assumes that the given note_day_id is valid
@@ -8740,6 +9409,11 @@ impl NoteArchive {
         "COPY / C: Copy template"
       );
     }
+    println_inst!(
+      "{} | {}",
+      "EXPORT / X: Export templates to a file",
+      "IMPORT / I: Import templates from a file",
+    );
   }
   fn display_copy_user_note_templates(&self) {
     let heading = format!(" Copy note template to generate a new template ");
@@ -8802,7 +9476,7 @@ impl NoteArchive {
       self.display_edit_note_templates();
       println_inst!("QUIT / Q: Cancel editing");
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -8852,6 +9526,7 @@ impl NoteArchive {
   }
   fn display_edit_note_template(&self) {
     self.current_note_template().display_edit_content(None, None);
+    Self::display_template_issues(&self.current_note_template().lint());
   }
   fn choose_edit_note_template(&mut self, s: String) {
     loop {
@@ -8886,7 +9561,7 @@ impl NoteArchive {
       }
       println_inst!("Choose blank by ID to delete or change it to a different type of blank.");
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -8897,6 +9572,13 @@ impl NoteArchive {
       field_to_edit = field_to_edit.trim().to_string();
       match &field_to_edit.to_ascii_lowercase()[..] {
         "quit" | "q" | "save" | "" => {
+          let issues = self.current_note_template().lint();
+          if issues.iter().any(|issue| issue.is_error() ) {
+            println_err!("This template has errors and will misbehave in notes until they are fixed.");
+            if !Self::confirm_template_choice("Stop editing anyway?") {
+              continue;
+            }
+          }
           let current_nt = self.current_note_template();
           match self.note_template_dup_already_saved(current_nt.id) {
             Some(nt_ids) => {
@@ -8907,7 +9589,6 @@ impl NoteArchive {
                   Err(e) => panic!("Detected duplicate note template with ID '{}' after copy, loaded successfully but failed to delete: {}", nt_id, e),
                 }
               }
-              self.reindex_note_templates();
               self.write_to_files();
               print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
               println_yel!("Copies discarded: {}", num_dups);
@@ -8925,7 +9606,7 @@ impl NoteArchive {
             print_inst!("'CANCEL/QUIT' at any time to cancel.");
             print!("\n");
             let mut structure_choice = String::new();
-            let structure_attempt = io::stdin().read_line(&mut structure_choice);
+            let structure_attempt = read_input_line(&mut structure_choice);
             match structure_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -8977,7 +9658,7 @@ impl NoteArchive {
             println_inst!("Choose content section by ID.");
             println_inst!("Enter 'QUIT / Q' at any time to return to the editing menu.");
             let mut content_choice = String::new();
-            let content_attempt = io::stdin().read_line(&mut content_choice);
+            let content_attempt = read_input_line(&mut content_choice);
             match content_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -8996,7 +9677,7 @@ impl NoteArchive {
                 println_inst!("Enter new content to append to the template, including spaces.");
                 let mut new_section = String::new();
                 let new_section_choice = loop {
-                  let section_result = io::stdin().read_line(&mut new_section);
+                  let section_result = read_input_line(&mut new_section);
                   break match section_result {
                     Ok(_) => String::from(&new_section[..new_section.len()-2]),
                     Err(e) => {
@@ -9021,14 +9702,14 @@ impl NoteArchive {
                 };
                 self.current_note_template_mut().content = new_content;
                 self.current_note_template_mut().clean_spacing();
-                self.write_note_templates().unwrap()
+                self.write_to_files()
               },
               "edit" | "e" => {
                 self.current_note_template().display_edit_content(blank_focus_id, content_focus_id);
                 println_inst!("Enter exact content to replace the selected text, including any spaces on the left and right.");
                 let mut new_section = String::new();
                 let new_section_choice = loop {
-                  let section_result = io::stdin().read_line(&mut new_section);
+                  let section_result = read_input_line(&mut new_section);
                   break match section_result {
                     Ok(_) => String::from(&new_section[..new_section.len()-2]),
                     Err(e) => {
@@ -9051,13 +9732,13 @@ impl NoteArchive {
 
                 self.current_note_template_mut().content = new_content;
                 self.current_note_template_mut().clean_spacing();
-                self.write_note_templates().unwrap()
+                self.write_to_files()
               },
               "delete" | "d" => {
                 loop {
                   println_yel!("Are you sure you want to delete the currently selected section of content? (Y/N)");
                   let mut delete_choice = String::new();
-                  let delete_attempt = io::stdin().read_line(&mut delete_choice);
+                  let delete_attempt = read_input_line(&mut delete_choice);
                   match delete_attempt {
                     Ok(_) => (),
                     Err(e) => {
@@ -9078,7 +9759,7 @@ impl NoteArchive {
                       }
                       self.current_note_template_mut().content = new_content;
                       self.current_note_template_mut().clean_spacing();
-                      self.write_note_templates().unwrap();
+                      self.write_to_files();
                       break;
                     },
                     "NO" | "no" | "No" | "N" | "n" => {
@@ -9101,7 +9782,7 @@ impl NoteArchive {
                   if chosen_text == String::new() {
                     println_inst!("Enter new content to insert, without spaces on the ends.");
                     let mut selected_text = String::new();
-                    let enter_result = io::stdin().read_line(&mut selected_text);
+                    let enter_result = read_input_line(&mut selected_text);
                     chosen_text = match enter_result {
                       Ok(_) => String::from(&selected_text[..selected_text.len()-2]),
                       Err(e) => {
@@ -9118,7 +9799,7 @@ impl NoteArchive {
 
                   let insert = loop {
                     let mut insert_choice = String::new();
-                    let insert_attempt = io::stdin().read_line(&mut insert_choice);
+                    let insert_attempt = read_input_line(&mut insert_choice);
                     match insert_attempt {
                       Ok(_) => (),
                       Err(e) => {
@@ -9195,7 +9876,7 @@ impl NoteArchive {
                         );
 
                         let mut insert_string = String::new();
-                        let insert_attempt = io::stdin().read_line(&mut insert_string);
+                        let insert_attempt = read_input_line(&mut insert_string);
                         match insert_attempt {
                           Ok(_) => (),
                           Err(e) => {
@@ -9227,7 +9908,7 @@ impl NoteArchive {
                               println_inst!("{}", &new_content_section);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9250,7 +9931,7 @@ impl NoteArchive {
                                   }
                                   self.current_note_template_mut().content = new_content;
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
                                   break 'insert_content;
                                 },
                                 "no" | "n" => {
@@ -9285,7 +9966,7 @@ impl NoteArchive {
                               println_inst!("{}{}", &last_char, &new_content_section);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9306,7 +9987,7 @@ impl NoteArchive {
                                   }
                                   self.current_note_template_mut().content = new_content;
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
                                   break 'insert_content;
                                 },
                                 "no" | "n" => {
@@ -9359,7 +10040,7 @@ impl NoteArchive {
                               );
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9380,7 +10061,7 @@ impl NoteArchive {
                                     &current_content[idx2..],
                                   );
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
 
                                   break 'insert_content;
                                 },
@@ -9460,7 +10141,7 @@ impl NoteArchive {
                     println_inst!("Any other key to cancel.");
                     let mut new_section = String::new();
                     let new_section_choice = loop {
-                      let section_result = io::stdin().read_line(&mut new_section);
+                      let section_result = read_input_line(&mut new_section);
                       break match section_result {
                         Ok(_) => new_section.trim().to_ascii_lowercase(),
                         Err(e) => {
@@ -9485,7 +10166,7 @@ impl NoteArchive {
     
                         self.current_note_template_mut().content = new_content;
                         self.current_note_template_mut().clean_spacing();
-                        self.write_note_templates().unwrap()
+                        self.write_to_files()
                       },
                       _ => {
                         continue;
@@ -9545,7 +10226,7 @@ impl NoteArchive {
             println_inst!("Choose blank by blank ID.");
             println_inst!("Enter 'QUIT / Q' at any time to return to the editing menu.");
             let mut blank_choice = String::new();
-            let blank_attempt = io::stdin().read_line(&mut blank_choice);
+            let blank_attempt = read_input_line(&mut blank_choice);
             match blank_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -9572,7 +10253,7 @@ impl NoteArchive {
                           self.current_note_template().display_edit_content(Some(0), None);
                           println_inst!("Please enter the ID of the blank to add pronouns for. Enter CANCEL / C to cancel.");
                           let mut input_string = String::new();
-                          let input_result = io::stdin().read_line(&mut input_string);
+                          let input_result = read_input_line(&mut input_string);
                           let input = match input_result {
                             Ok(_) => input_string.trim(),
                             Err(e) => {
@@ -9647,13 +10328,13 @@ impl NoteArchive {
 
                 self.current_note_template_mut().content = new_content;
                 self.current_note_template_mut().clean_spacing();
-                self.write_note_templates().unwrap();
+                self.write_to_files();
               },
               "delete" | "d" => {
                 loop {
                   println_yel!("Are you sure you want to delete the selected blank? (Y/N)");
                   let mut delete_choice = String::new();
-                  let delete_attempt = io::stdin().read_line(&mut delete_choice);
+                  let delete_attempt = read_input_line(&mut delete_choice);
                   match delete_attempt {
                     Ok(_) => (),
                     Err(e) => {
@@ -9678,7 +10359,7 @@ impl NoteArchive {
                       }
                       self.current_note_template_mut().content = new_content;
                       self.current_note_template_mut().clean_spacing();
-                      self.write_note_templates().unwrap();
+                      self.write_to_files();
                       break;
                     },
                     "NO" | "no" | "No" | "N" | "n" => {
@@ -9706,7 +10387,7 @@ impl NoteArchive {
                   println_inst!("Select a section of text in the template by ID, then ENTER to insert a new blank before, after, or in that section.");
                   println_inst!("Enter CANCEL at any time to cancel.");
                   let mut insert_choice = String::new();
-                  let insert_attempt = io::stdin().read_line(&mut insert_choice);
+                  let insert_attempt = read_input_line(&mut insert_choice);
                   match insert_attempt {
                     Ok(_) => (),
                     Err(e) => {
@@ -9772,7 +10453,7 @@ impl NoteArchive {
                         println_inst!("CANCEL: Cancel inserting blank");
 
                         let mut insert_string = String::new();
-                        let insert_attempt = io::stdin().read_line(&mut insert_string);
+                        let insert_attempt = read_input_line(&mut insert_string);
                         match insert_attempt {
                           Ok(_) => (),
                           Err(e) => {
@@ -9824,7 +10505,7 @@ impl NoteArchive {
                               );
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9858,7 +10539,7 @@ impl NoteArchive {
 
                                   self.current_note_template_mut().content = edited_content;
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
                                   content_focus_id = None; // because after inserting, we will go back to focusing on blank
                                   break 'insert_loop;
                                 },
@@ -9903,7 +10584,7 @@ impl NoteArchive {
                               println_inst!("{}", &new_display_content);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9923,7 +10604,7 @@ impl NoteArchive {
                                     &current_content[..],
                                   );
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
                                   content_focus_id = None; // because after inserting, we will go back to focusing on blank
                                   break 'insert_location_blanks;
                                 },
@@ -9967,7 +10648,7 @@ impl NoteArchive {
                               println_inst!("{}", &new_content);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -9987,7 +10668,7 @@ impl NoteArchive {
                                     &new_blank,
                                   );
                                   self.current_note_template_mut().clean_spacing();
-                                  self.write_note_templates().unwrap();
+                                  self.write_to_files();
                                   break 'insert_location_blanks;
                                 },
                                 "no" | "n" => {
@@ -10048,7 +10729,7 @@ impl NoteArchive {
                     }
                   }
                 }
-                self.write_note_templates().unwrap();
+                self.write_to_files();
               },
               _ => {
                 let nt_content = self.current_note_template().content.clone();
@@ -10088,7 +10769,7 @@ impl NoteArchive {
       let input = loop {
         self.display_user_note_templates();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -10119,6 +10800,12 @@ impl NoteArchive {
         "copy" | "c" => {
           self.choose_copy_note_template();
         },
+        "export" | "x" => {
+          self.choose_export_note_templates();
+        },
+        "import" | "i" => {
+          self.choose_import_note_templates();
+        },
         "quit" | "q" => {
           break;
         },
@@ -10152,7 +10839,7 @@ impl NoteArchive {
       let input = loop {
         self.display_user_note_templates();
         let mut choice = String::new();
-        let read_attempt = io::stdin().read_line(&mut choice);
+        let read_attempt = read_input_line(&mut choice);
         match read_attempt {
           Ok(_) => break choice.to_ascii_lowercase(),
           Err(e) => {
@@ -10178,6 +10865,12 @@ impl NoteArchive {
         "copy" | "c" => {
           self.choose_copy_note_template();
         },
+        "export" | "x" => {
+          self.choose_export_note_templates();
+        },
+        "import" | "i" => {
+          self.choose_import_note_templates();
+        },
         "quit" | "q" => {
           break None;
         },
@@ -10237,7 +10930,7 @@ impl NoteArchive {
         );
       }
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -10253,7 +10946,7 @@ impl NoteArchive {
         "delete" | "d" => {
           if self.current_note_template().custom {
             self.choose_delete_note_template();
-            self.write_note_templates().unwrap();
+            self.write_to_files();
             break;
           } else {
             println_err!("Cannot delete default note.");
@@ -10331,7 +11024,7 @@ impl NoteArchive {
         print_inst!("Build a template for what kind of record?");
         print!("\n");
         let mut structure_choice = String::new();
-        let structure_attempt = io::stdin().read_line(&mut structure_choice);
+        let structure_attempt = read_input_line(&mut structure_choice);
         match structure_attempt {
           Ok(_) => (),
           Err(e) => {
@@ -10368,6 +11061,8 @@ impl NoteArchive {
         nt.clean_spacing();
         print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
         nt.display_content(None, None);
+        let issues = nt.lint();
+        Self::display_template_issues(&issues);
         println_inst!("Enter text to add text to the template, or choose from among the following options:");
         println_inst!(
           "{} | {}",
@@ -10379,8 +11074,12 @@ impl NoteArchive {
           "SAVE / S: finish and save template",
           "BACK: Delete last sentence or blank",
         );
+        println_inst!(
+          "{}",
+          "Text may use {if:p} ... {else} ... {end}, {as:name} after a blank, {pb1:name} to {pb4:name} and {verb:name:signs|sign}.",
+        );
         let mut custom_choice = String::new();
-        let custom_attempt = io::stdin().read_line(&mut custom_choice);
+        let custom_attempt = read_input_line(&mut custom_choice);
         let custom_choice = match custom_attempt {
           Ok(_) => custom_choice,
           Err(e) => {
@@ -10413,7 +11112,7 @@ impl NoteArchive {
             loop {
               println_inst!("Enter custom text as you would like it to appear.");
               let mut text_choice = String::new();
-              let text_attempt = io::stdin().read_line(&mut text_choice);
+              let text_attempt = read_input_line(&mut text_choice);
               match text_attempt {
                 Ok(_) => {
                   match content.chars().last() {
@@ -10456,7 +11155,7 @@ impl NoteArchive {
                   nt.display_content(Some(0), None);
                   println_inst!("Please enter the ID of the blank to derive pronouns. Enter CANCEL / C to cancel.");
                   let mut input_string = String::new();
-                  let input_result = io::stdin().read_line(&mut input_string);
+                  let input_result = read_input_line(&mut input_string);
                   let input = match input_result {
                     Ok(_) => input_string.trim(),
                     Err(e) => {
@@ -10524,6 +11223,14 @@ impl NoteArchive {
               thread::sleep(time::Duration::from_secs(4));
               continue;
             }
+            if issues.iter().any(|issue| issue.is_error() ) {
+              println_err!("Please fix the errors listed above before saving the template.");
+              thread::sleep(time::Duration::from_secs(3));
+              continue;
+            }
+            if !issues.is_empty() && !Self::confirm_template_choice("Save the template with the warnings listed above?") {
+              continue;
+            }
             break;
           },
           "cancel" | "Cancel" | "CANCEL" => return None,
@@ -10559,8 +11266,15 @@ impl NoteArchive {
 
     note_template.clean_spacing();
     let id = note_template.id;
-    self.save_note_template(note_template);
-    Some(id)
+    match self.save_note_template(note_template) {
+      Ok(_) => Some(id),
+      Err(issues) => {
+        Self::display_template_issues(&issues);
+        println_err!("The template was not saved.");
+        thread::sleep(time::Duration::from_secs(3));
+        None
+      }
+    }
   }
   fn copy_note_template(&mut self, nt_id: u32) {
     let copied_nt = match self.get_note_template_option_by_id(nt_id) {
@@ -10580,7 +11294,12 @@ impl NoteArchive {
       }
     };
     let new_id = new_nt.id;
-    self.save_note_template(new_nt);
+    if let Err(issues) = self.save_note_template(new_nt) {
+      Self::display_template_issues(&issues);
+      println_err!("The copied template has errors and was not saved.");
+      thread::sleep(time::Duration::from_secs(3));
+      return;
+    }
     self.write_to_files();
     match self.load_note_template(new_id) {
       Ok(_) => (),
@@ -10591,7 +11310,7 @@ impl NoteArchive {
     loop {
       self.display_copy_user_note_templates();
       let mut input_string = String::new();
-      let input_result = io::stdin().read_line(&mut input_string);
+      let input_result = read_input_line(&mut input_string);
       let user_choice = match input_result {
         Ok(_) => input_string.trim(),
         Err(e) => {
@@ -10646,7 +11365,7 @@ impl NoteArchive {
 
     }
   }
-  fn note_template_dup_already_saved(&self, nt_id: u32) -> Option<Vec<u32>> {
+  pub fn note_template_dup_already_saved(&self, nt_id: u32) -> Option<Vec<u32>> {
     let nt_match = match self.note_templates.iter().find(|nt| nt.id == nt_id ) {
       Some(nt) => nt.clone(),
       None => return None,
@@ -10714,8 +11433,11 @@ impl NoteArchive {
         )
       );
     }
+    if let Err(e) = parse_template(&content) {
+      return Err((NoteTemplate::new(0, structure, true, content, vec![user_id]), e));
+    }
     
-    let id = (self.note_templates.len() + 1) as u32;
+    let id = self.next_id("note_templates");
 
     match self.note_template_dup_id_option(&structure, content.clone(), user_id) {
       Some(_) => Err(
@@ -10727,97 +11449,8 @@ impl NoteArchive {
       None => Ok(NoteTemplate::new(id, structure, true, content, vec![user_id]))
     }
   }
-  pub fn read_note_templates(filepath: &str) -> Result<Vec<NoteTemplate>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut note_templates: Vec<NoteTemplate> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      let id: u32 = values[0].parse().unwrap();
-
-      let structure = match &values[1][..] {
-        "Care Plan" => CarePlan,
-        "Intake" => Intake,
-        "Assessment" => Assessment,
-        "SNCD" => Sncd,
-        "Home Visit" => HomeVisit,
-        "Agenda Prep" => AgendaPrep,
-        "Debrief" => Debrief,
-        "Phone Call" => PhoneCall,
-        "Scheduling" => Scheduling,
-        "Sent Email" => SentEmail,
-        "Referral" => Referral,
-        "Custom Structure" => CustomStructure,
-        "Parent Support" => ParentSupport,
-        "Sent Cancellation" => SentCancellation,
-        "Parent Appearance" => ParentAppearance,
-        "Parent Skills" => ParentSkills,
-        "Failed Contact Attempt" => FailedContactAttempt,
-        "Categorized Emails" => CategorizedEmails,
-        "Authorization Requested" => AuthorizationRequested,
-        "Authorization Issued" => AuthorizationIssued,
-        "Collateral Outreach" => CollateralOutreach,
-        "Update From Collateral" => UpdateFromCollateral,
-        "Invited To Meeting" => InvitedToMeeting,
-        "Sent Document" => SentDocument,
-        "Updated Document" => UpdatedDocument,
-        "Discuss Communication" => DiscussCommunication,
-        "Received Verbal Consent" => ReceivedVerbalConsent,
-        "Received Written Consent" => ReceivedWrittenConsent,
-        "Documentation" => DocumentationStructure,
-        "Brainstorm Contribution" => BrainstormContribution,
-        _ => return Err(Error::new(
-          ErrorKind::Other,
-          &format!("Unsupported StructureType saved to file: {}.", &values[1])[..],
-        )),
-      };
-
-      let content = values[2].clone();
-
-      let user_ids: Vec<u32> = match &values[3][..] {
-        "" => vec![],
-        _ => values[3]
-          .split("#")
-          .map(|val| val.parse().unwrap())
-          .collect()
-      };
-
-      // let note_ids: Vec<u32> = match &values[4][..] {
-      //   "" => vec![],
-      //   _ => values[4]
-      //     .split("#")
-      //     .map(|val| val.parse().unwrap())
-      //     .collect(),
-      // };
-
-      // ^^ possibly use again to store which note came from which template
-
-      let nt = NoteTemplate::new(id, structure, true, content, user_ids);
-      note_templates.push(nt);
-    }
+  pub fn read_note_templates(filepath: &str) -> Result<(Vec<NoteTemplate>, Vec<LoadError>), Error> {
+    let (mut note_templates, errors) = Self::read_text_records(filepath, NoteTemplateRecord, Self::parse_note_template_line)?;
 
     for (i, def) in DEFAULT_NOTE_TEMPLATES.iter().enumerate() {
       let i = i as u32;
@@ -10882,21 +11515,61 @@ impl NoteArchive {
     );
     nonduplicates.sort_by(|a, b| a.structure.cmp(&b.structure));
     nonduplicates.sort_by(|a, b| a.custom.cmp(&b.custom));
-    Ok(nonduplicates)
+    Ok((nonduplicates, errors))
+  }
+  fn parse_structure_type(s: &str) -> Result<StructureType, FieldError> {
+    Ok(match s {
+      "Care Plan" => CarePlan,
+      "Intake" => Intake,
+      "Assessment" => Assessment,
+      "SNCD" => Sncd,
+      "Home Visit" => HomeVisit,
+      "Agenda Prep" => AgendaPrep,
+      "Debrief" => Debrief,
+      "Phone Call" => PhoneCall,
+      "Scheduling" => Scheduling,
+      "Sent Email" => SentEmail,
+      "Referral" => Referral,
+      "Custom Structure" => CustomStructure,
+      "Parent Support" => ParentSupport,
+      "Sent Cancellation" => SentCancellation,
+      "Parent Appearance" => ParentAppearance,
+      "Parent Skills" => ParentSkills,
+      "Failed Contact Attempt" => FailedContactAttempt,
+      "Categorized Emails" => CategorizedEmails,
+      "Authorization Requested" => AuthorizationRequested,
+      "Authorization Issued" => AuthorizationIssued,
+      "Collateral Outreach" => CollateralOutreach,
+      "Update From Collateral" => UpdateFromCollateral,
+      "Invited To Meeting" => InvitedToMeeting,
+      "Sent Document" => SentDocument,
+      "Updated Document" => UpdatedDocument,
+      "Discuss Communication" => DiscussCommunication,
+      "Received Verbal Consent" => ReceivedVerbalConsent,
+      "Received Written Consent" => ReceivedWrittenConsent,
+      "Documentation" => DocumentationStructure,
+      "Brainstorm Contribution" => BrainstormContribution,
+      _ => return Err(FieldError::new("structure", format!("unsupported structure type '{}'", s))),
+    })
   }
-  pub fn write_note_templates(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### note_templates #####\n");
-    for nt in &self.note_templates {
-      if nt.custom {
-        lines.push_str(&nt.to_string()[..]);
-      }
-    }
-    lines.push_str("##### note_templates #####");
-    let mut file = File::create(self.filepaths["note_template_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
+  pub fn parse_note_template_line(line: &str) -> Result<NoteTemplate, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let structure = Self::parse_structure_type(values.text(1, "structure")?)?;
+    let content = String::from(values.text(2, "content")?);
+    let user_ids = values.ids(3, "user IDs")?;
+
+    // a fifth value, the IDs of notes made from the template, is not read
+    // ^^ possibly use again to store which note came from which template
+
+    Ok(NoteTemplate::new(id, structure, true, content, user_ids))
   }
-  fn save_note_template(&mut self, note_template: NoteTemplate) {
+  /// saves a template unless linting finds errors, which are returned instead
+  pub fn save_note_template(&mut self, note_template: NoteTemplate) -> Result<(), Vec<TemplateIssue>> {
+    let issues = note_template.lint();
+    if issues.iter().any(|issue| issue.is_error() ) {
+      return Err(issues);
+    }
 
     let pos = self.note_templates.binary_search_by(|nt| nt.id.cmp(&note_template.id)
       .then_with(||
@@ -10912,33 +11585,156 @@ impl NoteArchive {
 
     self.note_templates.insert(pos, note_template);
     self.write_to_files();
+    Ok(())
   }
-  // fn current_user_custom_note_templates(&self) -> Vec<&NoteTemplate> {
-  //   self.note_templates.iter().filter(|nt| nt.custom ).filter(|nt| nt.foreign_key["user_id"] == self.current_user().id).collect()
-  // }
-  // fn current_user_custom_note_templates_mut(&mut self) -> Vec<&mut NoteTemplate> {
-  //   let current_id = self.current_user().id;
-  //   self.note_templates.iter_mut().filter(|nt| nt.custom ).filter(|nt| nt.foreign_key["user_id"] == current_id).collect()
-  // }
-  fn choose_delete_note_template(&mut self) {
+  fn display_template_issues(issues: &[TemplateIssue]) {
+    for issue in issues {
+      if issue.is_error() {
+        println_err!("{}", issue);
+      } else {
+        println_yel!("{}", issue);
+      }
+    }
+  }
+  /// saves the chosen templates of the current user as a library file in the exports folder
+  fn choose_export_note_templates(&mut self) {
+    let user_id = self.current_user().id;
+    let fp = export_dir(&self.filepaths["archive_filepath"]).join(format!("note_templates_user_{}.json", user_id));
     loop {
-      self.display_delete_note_template();
-      println_yel!("Are you sure you want to delete this note template?");
-      println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
-      let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
-      let command = match input_attempt {
-        Ok(_) => confirm.trim().to_string(),
-        Err(e) => {
-          println_err!("Failed to read input: {}", e);
-          thread::sleep(time::Duration::from_secs(1));
-          continue;
+      self.display_user_note_templates();
+      println_inst!("Enter the IDs of the templates to export, separated by spaces, or 'ALL' for every template.");
+      println_inst!("'CANCEL / C' to cancel.");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read line: {}", e);
+        thread::sleep(time::Duration::from_secs(2));
+        continue;
+      }
+      let templates = self.user_note_templates(user_id);
+      let chosen: Vec<&NoteTemplate> = match &choice.trim().to_ascii_lowercase()[..] {
+        "cancel" | "c" => return,
+        "all" | "a" => templates,
+        ids => {
+          let chosen: Option<Vec<&NoteTemplate>> = ids.split_whitespace()
+            .map(|id| id.parse::<u32>().ok().and_then(|id| templates.iter().find(|nt| nt.id == id ).copied() ) )
+            .collect();
+          match chosen {
+            Some(chosen) if !chosen.is_empty() => chosen,
+            _ => {
+              println_err!("Please enter the IDs of listed templates.");
+              thread::sleep(time::Duration::from_secs(2));
+              continue;
+            },
+          }
+        },
+      };
+      match TemplateLibrary::new(&chosen).save(&fp) {
+        Ok(_) => println_suc!("Exported {} templates to {}", chosen.len(), fp.display()),
+        Err(e) => println_err!("{}", e),
+      }
+      thread::sleep(time::Duration::from_secs(2));
+      return;
+    }
+  }
+  /// shows what a library file would add for the current user and imports it after confirmation
+  fn choose_import_note_templates(&mut self) {
+    let user_id = self.current_user().id;
+    let library = loop {
+      print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+      println_inst!("Enter the path of a template library file, or 'CANCEL / C' to cancel.");
+      let mut choice = String::new();
+      if let Err(e) = read_input_line(&mut choice) {
+        println_err!("Failed to read line: {}", e);
+        thread::sleep(time::Duration::from_secs(2));
+        continue;
+      }
+      match &choice.trim().to_ascii_lowercase()[..] {
+        "cancel" | "c" => return,
+        _ => match TemplateLibrary::load(choice.trim()) {
+          Ok(library) => break library,
+          Err(e) => {
+            println_err!("{}", e);
+            thread::sleep(time::Duration::from_secs(2));
+          },
+        },
+      }
+    };
+    let plan = self.plan_template_import(&library, user_id);
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_on_bg!("{:-^156}", " Templates to import ");
+    for entry in &plan {
+      let mut lines = entry.preview().lines().map(|l| l.to_string() ).collect::<Vec<String>>().into_iter();
+      let description = lines.next().unwrap_or_default();
+      if entry.will_import() {
+        println_suc!("{}", description);
+      } else {
+        println_yel!("{}", description);
+      }
+      for line in lines {
+        if line.trim_start().starts_with('-') || !entry.will_import() {
+          println_err!("{}", line);
+        } else {
+          println!("{}", line);
+        }
+      }
+    }
+    let to_import = plan.iter().filter(|entry| entry.will_import() ).count();
+    if to_import == 0 {
+      println_yel!("No new templates to import.");
+      thread::sleep(time::Duration::from_secs(2));
+      return;
+    }
+    if Self::confirm_template_choice(&format!("Import {} of {} templates?", to_import, plan.len())) {
+      let ids = self.import_templates(&plan, user_id);
+      println_suc!("Imported {} templates.", ids.len());
+      thread::sleep(time::Duration::from_secs(2));
+    }
+  }
+  fn confirm_template_choice(prompt: &str) -> bool {
+    loop {
+      println_inst!("{} ( Y / N )", prompt);
+      let mut choice = String::new();
+      match read_input_line(&mut choice) {
+        Ok(_) => match &choice.trim().to_ascii_lowercase()[..] {
+          "yes" | "y" => return true,
+          "no" | "n" => return false,
+          _ => {
+            println_err!("Invalid entry.");
+            thread::sleep(time::Duration::from_secs(2));
+          }
+        },
+        Err(e) => {
+          println_err!("Failed to read line: {}", e);
+          thread::sleep(time::Duration::from_secs(2));
+        }
+      }
+    }
+  }
+  // fn current_user_custom_note_templates(&self) -> Vec<&NoteTemplate> {
+  //   self.note_templates.iter().filter(|nt| nt.custom ).filter(|nt| nt.foreign_key["user_id"] == self.current_user().id).collect()
+  // }
+  // fn current_user_custom_note_templates_mut(&mut self) -> Vec<&mut NoteTemplate> {
+  //   let current_id = self.current_user().id;
+  //   self.note_templates.iter_mut().filter(|nt| nt.custom ).filter(|nt| nt.foreign_key["user_id"] == current_id).collect()
+  // }
+  fn choose_delete_note_template(&mut self) {
+    loop {
+      self.display_delete_note_template();
+      println_yel!("Are you sure you want to delete this note template?");
+      println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
+      let mut confirm = String::new();
+      let input_attempt = read_input_line(&mut confirm);
+      let command = match input_attempt {
+        Ok(_) => confirm.trim().to_string(),
+        Err(e) => {
+          println_err!("Failed to read input: {}", e);
+          thread::sleep(time::Duration::from_secs(1));
+          continue;
         }
       };
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_note_template();
-          self.reindex_note_templates();
           self.write_to_files();
           break;
         }
@@ -10964,13 +11760,6 @@ impl NoteArchive {
     self.note_templates.retain(|nd| nd.id != *id);
     self.foreign_key.remove("current_note_template_id");
   }
-  fn reindex_note_templates(&mut self) {
-    let mut i: u32 = 1;
-    for mut nt in &mut self.note_templates {
-      nt.id = i;
-      i += 1;
-    }
-  }
   fn get_note_template_option_by_id(&self, id: u32) -> Option<&NoteTemplate> {
     self.note_templates.iter().find(|nt| nt.id == id)
   }
@@ -11020,10 +11809,10 @@ impl NoteArchive {
     let current_note_day = self.current_note_day().clone();
     self.notes.iter_mut().filter(|n| current_note_day.foreign_keys["note_ids"].iter().any(|n_id| n_id == &n.id )).collect()
   }
-  fn note_day_notes(&self, nd: NoteDay) -> Vec<&Note> {
+  pub fn note_day_notes(&self, nd: NoteDay) -> Vec<&Note> {
     self.notes.iter().filter(|n| nd.foreign_keys["note_ids"].iter().any(|n_id| n_id == &n.id )).collect()
   }
-  fn get_note_day_by_note_id(&self, id: u32) -> Option<&NoteDay> {
+  pub fn get_note_day_by_note_id(&self, id: u32) -> Option<&NoteDay> {
     self.note_days.iter().find(|nd| nd.foreign_keys["note_ids"].iter().any(|n_id| n_id == &id) )
   }
   // fn get_note_template_by_note_id(&self, id: u32) -> Option<&NoteTemplate> {
@@ -11049,8 +11838,11 @@ impl NoteArchive {
 
     let heading = format!(" {} {} note for {} ", nd.heading_date(), n.structure, c.full_name());
     println_on_bg!("{:-^163}", heading);
-    let heading2 = format!(" ({}) ", n.category);
+    let heading2 = format!(" ({}, {}) ", n.category, n.fmt_time());
     println_on_bg!("{:-^163}", heading2);
+    if let Some(t) = &n.travel {
+      println_on_bg!("{:-^163}", format!(" Travel: {} ", t.fmt_travel()));
+    }
     println_on_bg!("{:-^163}", "-");
     n.display_content(Some(0), None);
   }
@@ -11072,7 +11864,7 @@ impl NoteArchive {
     println_inst!("| {} | {} | {}", " EDIT / E: Edit entry ", " DELETE / D: Delete entry ", " QUIT / Q: Quit menu");
     loop {
       let mut choice = String::new();
-      let read_attempt = io::stdin().read_line(&mut choice);
+      let read_attempt = read_input_line(&mut choice);
       let input = match read_attempt {
         Ok(_) => choice.to_ascii_lowercase(),
         Err(e) => {
@@ -11097,6 +11889,90 @@ impl NoteArchive {
       }
     }
   }
+  fn choose_note_time(&mut self) {
+    loop {
+      println_inst!("Currently: {}", self.current_note().fmt_time());
+      println_inst!("Enter minutes spent (e.g. '45'), or start and end times (e.g. '9:00-9:45' or '1:15pm-2pm').");
+      println_inst!("Enter 'CANCEL' to cancel.");
+      let mut time_choice = String::new();
+      if let Err(e) = read_input_line(&mut time_choice) {
+        println_err!("Failed to read input: {}", e);
+        continue;
+      }
+      let time_choice = time_choice.trim();
+      if time_choice.eq_ignore_ascii_case("cancel") {
+        return;
+      }
+      match parse_note_time(time_choice) {
+        Ok((start_time, minutes)) => {
+          let overlap = start_time.and_then(|start| find_overlapping_note(self, self.current_note(), start, minutes) );
+          if let Some(other) = overlap {
+            let client_name = self.get_client_by_id(other.foreign_key["client_id"]).map(|c| c.full_name() ).unwrap_or_default();
+            println_err!("That time overlaps a note for {} ({}).", client_name, other.fmt_time());
+            thread::sleep(time::Duration::from_secs(2));
+            continue;
+          }
+          self.current_note_mut().set_time(start_time, Some(minutes));
+          return;
+        },
+        Err(e) => {
+          println_err!("{}", e);
+          thread::sleep(time::Duration::from_secs(2));
+        },
+      }
+    }
+  }
+  /// asks for each part of the trip in turn; 'CLEAR' removes travel from the note
+  fn choose_note_travel(&mut self) {
+    match &self.current_note().travel {
+      Some(t) => println_inst!("Currently: {}", t.fmt_travel()),
+      None => println_inst!("No travel entered for this note."),
+    }
+    println_inst!("Enter 'CANCEL' at any time to cancel, or 'CLEAR' to remove travel from this note.");
+    let mut answers: Vec<String> = vec![];
+    for prompt in [
+      "Starting location (leave blank to skip):",
+      "Ending location (leave blank to skip):",
+      "Miles driven:",
+      "Minutes of travel:",
+    ] {
+      loop {
+        println_inst!("{}", prompt);
+        let mut answer = String::new();
+        if let Err(e) = read_input_line(&mut answer) {
+          println_err!("Failed to read input: {}", e);
+          continue;
+        }
+        let answer = answer.trim().to_string();
+        if answer.eq_ignore_ascii_case("cancel") {
+          return;
+        }
+        if answer.eq_ignore_ascii_case("clear") {
+          self.current_note_mut().travel = None;
+          return;
+        }
+        let valid = match answers.len() {
+          2 => parse_miles(&answer).map(|_| () ),
+          3 => answer.parse::<u32>().map(|_| () ).map_err(|_| format!("Invalid number of minutes: {}", answer) ),
+          _ => Ok(()),
+        };
+        match valid {
+          Ok(_) => {
+            answers.push(answer);
+            break;
+          },
+          Err(e) => println_err!("{}", e),
+        }
+      }
+    }
+    let location = |s: &String| if s.is_empty() { None } else { Some(s.clone()) };
+    self.current_note_mut().travel = Some(Travel {
+      start_location: location(&answers[0]),
+      end_location: location(&answers[1]),
+      miles: parse_miles(&answers[2]).unwrap(),
+      minutes: answers[3].parse().unwrap(),
+    });
+  }
   fn choose_edit_note(&mut self) {
     'choose_edit: loop {
       let mut blank_focus_id: Option<u32> = None;
@@ -11109,14 +11985,16 @@ impl NoteArchive {
         "CONTENT / C: Edit other content",
       );
       println_inst!(
-        "{} | {}",
+        "{} | {} | {} | {}",
         "TYPE / T: Edit note type/category",
+        "TIME / M: Enter time spent",
+        "TRAVEL / V: Enter mileage",
         "QUIT / Q: Quit menu",
       );
       println_inst!("Choose blank by ID to edit its content.");
       println_inst!("Changes are saved automatically.");
       let mut field_to_edit = String::new();
-      let input_attempt = io::stdin().read_line(&mut field_to_edit);
+      let input_attempt = read_input_line(&mut field_to_edit);
       match input_attempt {
         Ok(_) => (),
         Err(_) => {
@@ -11127,6 +12005,12 @@ impl NoteArchive {
       field_to_edit = field_to_edit.trim().to_string();
       match &field_to_edit.to_ascii_lowercase()[..] {
         "quit" | "q" => break,
+        "time" | "m" => {
+          self.choose_note_time();
+        },
+        "travel" | "v" => {
+          self.choose_note_travel();
+        },
         "type" | "t" | "category" | "cat" => {
           let structure = loop {
             self.display_structure_types();
@@ -11135,7 +12019,7 @@ impl NoteArchive {
             print_inst!("Enter 'CANCEL' at any time to cancel.");
             print!("\n");
             let mut structure_choice = String::new();
-            let structure_attempt = io::stdin().read_line(&mut structure_choice);
+            let structure_attempt = read_input_line(&mut structure_choice);
             match structure_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -11202,7 +12086,7 @@ impl NoteArchive {
                           let mut cancel_choice = String::new();
                           println_yel!("You must select a note category to fill in a custom note template.");
                           println_inst!("Cancel writing note? ( Y / N )");
-                          let cancel_choice_attempt = io::stdin().read_line(&mut cancel_choice);
+                          let cancel_choice_attempt = read_input_line(&mut cancel_choice);
                           let cancel_choice_content = match cancel_choice_attempt {
                             Ok(_) => cancel_choice.trim().to_ascii_lowercase(),
                             Err(e) => {
@@ -11272,7 +12156,7 @@ impl NoteArchive {
                           let mut cancel_choice = String::new();
                           println_yel!("You must select a note category to fill in a custom note template.");
                           println_inst!("Cancel writing note? ( Y / N )");
-                          let cancel_choice_attempt = io::stdin().read_line(&mut cancel_choice);
+                          let cancel_choice_attempt = read_input_line(&mut cancel_choice);
                           let cancel_choice_content = match cancel_choice_attempt {
                             Ok(_) => cancel_choice.trim().to_ascii_lowercase(),
                             Err(e) => {
@@ -11325,7 +12209,7 @@ impl NoteArchive {
             println_inst!("Choose content section by ID.");
             println_inst!("Enter 'QUIT / Q' at any time to return to the editing menu.");
             let mut content_choice = String::new();
-            let content_attempt = io::stdin().read_line(&mut content_choice);
+            let content_attempt = read_input_line(&mut content_choice);
             match content_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -11344,7 +12228,7 @@ impl NoteArchive {
                 println_inst!("Enter new content to append to the note, including spaces.");
                 let mut new_section = String::new();
                 let new_section_choice = loop {
-                  let section_result = io::stdin().read_line(&mut new_section);
+                  let section_result = read_input_line(&mut new_section);
                   break match section_result {
                     Ok(_) => String::from(&new_section[..new_section.len()-2]),
                     Err(e) => {
@@ -11377,7 +12261,7 @@ impl NoteArchive {
                 println_inst!("Enter exact content to replace the selected text, including any spaces on the left and right.");
                 let mut new_section = String::new();
                 let new_section_choice = loop {
-                  let section_result = io::stdin().read_line(&mut new_section);
+                  let section_result = read_input_line(&mut new_section);
                   break match section_result {
                     Ok(_) => String::from(&new_section[..new_section.len()-2]),
                     Err(e) => {
@@ -11406,7 +12290,7 @@ impl NoteArchive {
                 loop {
                   println_yel!("Are you sure you want to delete the currently selected section of content? (Y/N)");
                   let mut delete_choice = String::new();
-                  let delete_attempt = io::stdin().read_line(&mut delete_choice);
+                  let delete_attempt = read_input_line(&mut delete_choice);
                   match delete_attempt {
                     Ok(_) => (),
                     Err(e) => {
@@ -11452,7 +12336,7 @@ impl NoteArchive {
                   if chosen_text == String::new() {
                     println_inst!("Enter new content to insert, without spaces on the ends.");
                     let mut selected_text = String::new();
-                    let enter_result = io::stdin().read_line(&mut selected_text);
+                    let enter_result = read_input_line(&mut selected_text);
                     chosen_text = match enter_result {
                       Ok(_) => String::from(&selected_text[..selected_text.len()-2]),
                       Err(e) => {
@@ -11469,7 +12353,7 @@ impl NoteArchive {
 
                   let insert = loop {
                     let mut insert_choice = String::new();
-                    let insert_attempt = io::stdin().read_line(&mut insert_choice);
+                    let insert_attempt = read_input_line(&mut insert_choice);
                     match insert_attempt {
                       Ok(_) => (),
                       Err(e) => {
@@ -11546,7 +12430,7 @@ impl NoteArchive {
                         );
 
                         let mut insert_string = String::new();
-                        let insert_attempt = io::stdin().read_line(&mut insert_string);
+                        let insert_attempt = read_input_line(&mut insert_string);
                         match insert_attempt {
                           Ok(_) => (),
                           Err(e) => {
@@ -11578,7 +12462,7 @@ impl NoteArchive {
                               println_inst!("{}", &new_content_section);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -11636,7 +12520,7 @@ impl NoteArchive {
                               println_inst!("{}{}", &last_char, &new_content_section);
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -11712,7 +12596,7 @@ impl NoteArchive {
                               );
 
                               let mut confirm_insert = String::new();
-                              let confirm_attempt = io::stdin().read_line(&mut confirm_insert);
+                              let confirm_attempt = read_input_line(&mut confirm_insert);
                               let confirm = match confirm_attempt {
                                 Ok(_) => confirm_insert.trim(),
                                 Err(e) => {
@@ -11805,7 +12689,7 @@ impl NoteArchive {
                     println_inst!("Any other key to cancel.");
                     let mut new_section = String::new();
                     let new_section_choice = loop {
-                      let section_result = io::stdin().read_line(&mut new_section);
+                      let section_result = read_input_line(&mut new_section);
                       break match section_result {
                         Ok(_) => new_section.trim().to_ascii_lowercase(),
                         Err(e) => {
@@ -11897,7 +12781,7 @@ impl NoteArchive {
             println_inst!("Choose blank by blank ID.");
             println_inst!("Press ENTER to edit content of the selected blank.");
             let mut blank_choice = String::new();
-            let blank_attempt = io::stdin().read_line(&mut blank_choice);
+            let blank_attempt = read_input_line(&mut blank_choice);
             match blank_attempt {
               Ok(_) => (),
               Err(e) => {
@@ -11940,7 +12824,7 @@ impl NoteArchive {
                         self.display_client_collaterals(Some(collat_ids));
                         println_inst!("ALL: Select all");
                         let mut choice = String::new();
-                        let read_attempt = io::stdin().read_line(&mut choice);
+                        let read_attempt = read_input_line(&mut choice);
                         match read_attempt {
                           Ok(_) => break choice.trim().to_string(),
                           Err(e) => {
@@ -11968,7 +12852,7 @@ impl NoteArchive {
                               self.display_select_general_collaterals(Some(general_collat_ids.clone()));
                               println_inst!("ALL: Select all");
                               let mut choice = String::new();
-                              let read_attempt = io::stdin().read_line(&mut choice);
+                              let read_attempt = read_input_line(&mut choice);
                               match read_attempt {
                                 Ok(_) => break choice.trim().to_string(),
                                 Err(e) => {
@@ -12000,7 +12884,7 @@ impl NoteArchive {
                               "" => {
                                 break;
                               }
-                              _ => match general_input.parse() {
+                              _ => match general_input.parse::<u32>() {
                                 Ok(num) => {
                                   let collat = match self.general_collaterals.iter().find(|co| co.id == num) {
                                     Some(co) => co.clone(),
@@ -12110,521 +12994,21 @@ impl NoteArchive {
                     self.current_note_mut().foreign_keys.insert(String::from("collateral_ids"), new_ids);
                   },
                   ClientGoal => {
-                    let mut fill_ins: Vec<u32> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match self.select_client_goals(Some(fill_ins.clone())) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_strings = fill_ins.iter().map(|g_id| self.get_goal_by_id(*g_id).unwrap().goal.clone() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
+                    if let Some((final_blank_string, fill_ins)) = self.choose_client_goal_fill_in() {
                       self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, fill_ins));
                     }
                   },
-                  InternalDocument => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(InternalDocument, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| InternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  ExternalDocument => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(ExternalDocument, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ExternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  InternalMeeting => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(InternalMeeting, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| InternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  ExternalMeeting => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(ExternalMeeting, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ExternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  Appearance => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(Appearance, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| AppearanceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  SupportedParent => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(SupportedParent, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| SupportedParentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  ParentingSkill => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(ParentingSkill, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ParentingSkillFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  CarePlanningTopic => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(CarePlanningTopic, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| CarePlanningTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  YouthTopic => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(YouthTopic, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| YouthTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  ContactMethod => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(ContactMethod, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ContactMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  ContactPurpose => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(ContactPurpose, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  FulfilledContactPurpose => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(FulfilledContactPurpose, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| FulfilledContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  Service => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(Service, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| ServiceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  MeetingMethod => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(MeetingMethod, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| MeetingMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
-                  },
-                  SignatureMethod => {
-                    let mut fill_ins: Vec<usize> = vec![];
-                    let final_blank_string = loop {
-                      let blank_id = match Self::select_blank_fill_in(SignatureMethod, fill_ins.clone()) {
-                        Some(s) => s,
-                        None => {
-                          let fill_in_objects = fill_ins.iter().map(|fi| SignatureMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                          let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                          break if fill_in_strings.len() > 1 {
-                            format!(
-                              "{}{}{}",
-                              fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                              " and ",
-                              fill_in_strings[fill_in_strings.len()-1],
-                            )
-                          } else if fill_in_strings.len() > 0 {
-                            fill_in_strings[0].clone()
-                          } else {
-                            String::new()
-                          };
-                        }
-                      };
-                      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                        fill_ins.push(blank_id);
-                      } else {
-                        fill_ins.retain(|fi| fi != &blank_id )
-                      }
-                    };
-                    if fill_ins.len() > 0 {
-                      self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
-                    }
+                  InternalDocument | ExternalDocument | InternalMeeting | ExternalMeeting | Appearance | SupportedParent
+                    | ParentingSkill | CarePlanningTopic | YouthTopic | ContactMethod | ContactPurpose
+                    | FulfilledContactPurpose | Service | MeetingMethod | SignatureMethod => {
+                    let final_blank_string = Self::choose_blank_fill_ins(b.clone(), self.vocabularies_or_built_in().options(&b));
+                    self.current_note_mut().blanks.insert(i, (b.clone(), final_blank_string, vec![]));
                   },
                   CustomBlank => {
                     loop {
                       let mut custom_choice = String::new();
                       println_inst!("Enter new custom content for the current blank.");
-                      let custom_attempt = io::stdin().read_line(&mut custom_choice);
+                      let custom_attempt = read_input_line(&mut custom_choice);
                       let custom_content = match custom_attempt {
                         Ok(_) => custom_choice.trim(),
                         Err(e) => {
@@ -12651,7 +13035,7 @@ impl NoteArchive {
                 loop {
                   println_yel!("Are you sure you want to delete the currently selected blank? (Y/N)");
                   let mut delete_choice = String::new();
-                  let delete_attempt = io::stdin().read_line(&mut delete_choice);
+                  let delete_attempt = read_input_line(&mut delete_choice);
                   match delete_attempt {
                     Ok(_) => (),
                     Err(e) => {
@@ -12723,72 +13107,109 @@ impl NoteArchive {
       }
     }
   }
-  fn display_blank_fill_in(blank_type: Blank, selected: Option<Vec<usize>>) {
-    let display_category = format!("{}", blank_type.clone().display_to_user_empty());
-    let fill_ins: Vec<String> = match blank_type {
-      InternalDocument => {
-        InternalDocumentFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      ExternalDocument => {
-        ExternalDocumentFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      InternalMeeting => {
-        InternalMeetingFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      ExternalMeeting => {
-        ExternalMeetingFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      Appearance => {
-        AppearanceFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      SupportedParent => {
-        SupportedParentFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      ParentingSkill => {
-        ParentingSkillFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      CarePlanningTopic => {
-        CarePlanningTopicFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      YouthTopic => {
-        YouthTopicFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      ContactMethod => {
-        ContactMethodFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      ContactPurpose => {
-        ContactPurposeFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      FulfilledContactPurpose => {
-        FulfilledContactPurposeFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      Service => {
-        ServiceFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      MeetingMethod => {
-        MeetingMethodFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      SignatureMethod => {
-        SignatureMethodFillIn::iterator_of_blanks().map(|b| format!("{}", b) ).collect()
-      },
-      _ => panic!("Incompatible blank fill in string passed to fn 'display_blank_fill_in'")
-    };
+  /// the fill-in choices from the vocabulary file, or the built-in choices if no file is set
+  pub fn vocabularies(&self) -> Result<Vocabularies, String> {
+    match self.filepaths.get("vocabulary_filepath") {
+      None => Ok(Vocabularies::built_in()),
+      Some(fp) => Vocabularies::load(fp),
+    }
+  }
+  /// the agency's choices, or the built-in ones after showing why the vocabulary file couldn't be used
+  fn vocabularies_or_built_in(&self) -> Vocabularies {
+    self.vocabularies().unwrap_or_else(|e| {
+      println_err!("{} Using the built-in fill-in choices instead.", e);
+      thread::sleep(time::Duration::from_secs(2));
+      Vocabularies::built_in()
+    })
+  }
+  fn choose_blank_fill_ins(blank_type: Blank, options: &[FillInOption]) -> String {
+    let mut fill_ins: Vec<usize> = vec![];
+    loop {
+      let blank_id = match Self::select_blank_fill_in(blank_type, options, fill_ins.clone()) {
+        Some(s) => s,
+        None => {
+          let fill_in_strings = fill_ins.iter().map(|fi| Self::fill_in_option_display(&options[*fi]) ).collect::<Vec<String>>();
+          return if fill_in_strings.len() > 1 {
+            format!(
+              "{}{}{}",
+              fill_in_strings[..fill_in_strings.len()-1].join(", "),
+              " and ",
+              fill_in_strings[fill_in_strings.len()-1],
+            )
+          } else if fill_in_strings.len() > 0 {
+            fill_in_strings[0].clone()
+          } else {
+            String::new()
+          };
+        }
+      };
+      if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
+        fill_ins.push(blank_id);
+      } else {
+        fill_ins.retain(|fi| fi != &blank_id )
+      }
+    }
+  }
+  fn fill_in_option_display(option: &FillInOption) -> String {
+    if !option.custom {
+      return option.text.clone();
+    }
+    println_inst!("Enter text for the selected blank.");
+    'get_string: loop {
+      let mut input = String::new();
+      let input_result = read_input_line(&mut input);
+      match input_result {
+        Err(_) => {
+          println_err!("Invalid entry.");
+          thread::sleep(time::Duration::from_secs(2));
+          continue;
+        }
+        Ok(_) => {
+          break loop {
+            println_inst!("Confirm custom fill-in: '{}'? ( Y / N )", &input.trim());
+            let mut confirm_input = String::new();
+            let confirm_input_result = read_input_line(&mut confirm_input);
+            match confirm_input_result {
+              Err(_) => {
+                println_err!("Invalid entry.");
+                thread::sleep(time::Duration::from_secs(2));
+                continue;
+              }
+              Ok(_) => {
+                match &confirm_input.trim().to_ascii_lowercase()[..] {
+                  "yes" | "y" => break input.trim().to_string(),
+                  "n" | "no" => continue 'get_string,
+                  _ => {
+                    println_err!("Invalid entry.");
+                    thread::sleep(time::Duration::from_secs(2));
+                    continue;
+                  }
+                }
+              }
+            }
+          };
+        }
+      }
+    }
+  }
+  fn display_blank_fill_in(blank_type: Blank, options: &[FillInOption], selected: Option<Vec<usize>>) {
+    let display_category = format!("{}", blank_type.clone().display_to_user_empty());
     print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
     println_on_bg!("{:-^113}", "-");
     println_on_bg!("{:-^113}", format!(" Fill in blank with {} ", display_category));
     println_on_bg!("{:-^113}", "-");
     println_on_bg!("{:-^10} | {:-^100}", " ID ", " Content ");
-    for (i, fi) in fill_ins.iter().enumerate() {
+    for (i, fi) in options.iter().enumerate() {
       match selected.clone() {
         Some(ids) => {
           if ids.iter().any(|id| id == &i ) {
-            println_suc!("{: ^10} | {: <100}", i, fi);
+            println_suc!("{: ^10} | {: <100}", i, fi.text);
           } else {
-            println_on_bg!("{: ^10} | {: <100}", i, fi);
+            println_on_bg!("{: ^10} | {: <100}", i, fi.text);
           }
         },
         None => {
-          println_on_bg!("{: ^10} | {: <100}", i, fi);
+          println_on_bg!("{: ^10} | {: <100}", i, fi.text);
         }
       }
     }
@@ -12802,11 +13223,11 @@ impl NoteArchive {
       }
     }
   }
-  fn select_blank_fill_in(blank_type: Blank, selected: Vec<usize>) -> Option<usize> {
+  fn select_blank_fill_in(blank_type: Blank, options: &[FillInOption], selected: Vec<usize>) -> Option<usize> {
     loop {
-      NoteArchive::display_blank_fill_in(blank_type, Some(selected.clone()));
+      NoteArchive::display_blank_fill_in(blank_type, options, Some(selected.clone()));
       let mut fill_in_choice = String::new();
-      let fill_in_attempt = io::stdin().read_line(&mut fill_in_choice);
+      let fill_in_attempt = read_input_line(&mut fill_in_choice);
       let selected_option = match fill_in_attempt {
         Ok(_) => fill_in_choice.trim().to_ascii_lowercase(),
         Err(e) => {
@@ -12835,160 +13256,12 @@ impl NoteArchive {
           continue;
         }
       };
-      let selected_content: usize = match blank_type {
-        InternalMeeting => {
-          match InternalMeetingFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        ExternalMeeting => {
-          match ExternalMeetingFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        InternalDocument => {
-          match InternalDocumentFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        ExternalDocument => {
-          match ExternalDocumentFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        Appearance => {
-          match AppearanceFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        SupportedParent => {
-          match SupportedParentFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        ParentingSkill => {
-          match ParentingSkillFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        CarePlanningTopic => {
-          match CarePlanningTopicFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        YouthTopic => {
-          match YouthTopicFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        ContactMethod => {
-          match ContactMethodFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        ContactPurpose => {
-          match ContactPurposeFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        FulfilledContactPurpose => {
-          match FulfilledContactPurposeFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        Service => {
-          match ServiceFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        MeetingMethod => {
-          match MeetingMethodFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        SignatureMethod => {
-          match SignatureMethodFillIn::iterator_of_blanks().nth(chosen_id) {
-            Some(_) => chosen_id,
-            None => {
-              println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            }
-          }
-        },
-        _ => panic!("Incompatible fill in type passed to fn 'select_blank_flll_in'"),
-      };
-      return Some(selected_content)
+      if chosen_id >= options.len() {
+        println_err!("Index '{}' not found. Please select content from among the listed options.", chosen_id);
+        thread::sleep(time::Duration::from_secs(2));
+        continue;
+      }
+      return Some(chosen_id)
     }
   }
   fn display_icc_note_categories() {
@@ -13088,7 +13361,7 @@ impl NoteArchive {
       }
 
       let mut ncat_choice = String::new();
-      let ncat_attempt = io::stdin().read_line(&mut ncat_choice);
+      let ncat_attempt = read_input_line(&mut ncat_choice);
       let ncat_input = match ncat_attempt {
         Ok(_) => ncat_choice.trim().to_ascii_lowercase(),
         Err(e) => {
@@ -13161,7 +13434,7 @@ impl NoteArchive {
       }
 
       let mut ncat_choice = String::new();
-      let ncat_attempt = io::stdin().read_line(&mut ncat_choice);
+      let ncat_attempt = read_input_line(&mut ncat_choice);
       let ncat_input = match ncat_attempt {
         Ok(_) => ncat_choice.trim().to_ascii_lowercase(),
         Err(e) => {
@@ -13196,6 +13469,21 @@ impl NoteArchive {
       }
     }
   }
+  /// who is available to fill the automatic blanks for the current client, for rendering template directives
+  fn template_context(&self) -> TemplateContext {
+    let current_collaterals = self.get_owned_current_collaterals();
+    let partner_titles: [&str; 2] = match self.current_user().role {
+      Icc => ["fp", "family partner"],
+      Fp => ["icc", "intensive care coordinator"],
+    };
+    TemplateContext {
+      partners: current_collaterals.iter().filter(|co| partner_titles.contains(&&co.title.to_ascii_lowercase()[..]) ).count().min(1),
+      primary_contacts: current_collaterals.iter().filter(|co| co.primary_contact ).count(),
+      guardians: current_collaterals.iter().filter(|co| co.guardian ).count(),
+      care_plan_team: current_collaterals.iter().filter(|co| co.care_plan_team ).count(),
+      collaterals: current_collaterals.len(),
+    }
+  }
   fn autofill_note_blanks(&self, mut n: Note) -> Note {
     let current_client = self.current_client().clone();
     let current_collaterals = self.get_owned_current_collaterals();
@@ -13909,7 +14197,7 @@ impl NoteArchive {
                 let mut cancel_choice = String::new();
                 println_yel!("You must select a note template to build a note from a template.");
                 println_inst!("Cancel writing note? ( Y / N )");
-                let cancel_choice_attempt = io::stdin().read_line(&mut cancel_choice);
+                let cancel_choice_attempt = read_input_line(&mut cancel_choice);
                 break match cancel_choice_attempt {
                   Ok(_) => cancel_choice.trim().to_ascii_lowercase(),
                   Err(e) => {
@@ -13988,7 +14276,7 @@ impl NoteArchive {
                     let mut cancel_choice = String::new();
                     println_yel!("You must select a note category to fill in a custom note template.");
                     println_inst!("Cancel writing note? ( Y / N )");
-                    let cancel_choice_attempt = io::stdin().read_line(&mut cancel_choice);
+                    let cancel_choice_attempt = read_input_line(&mut cancel_choice);
                     let cancel_choice_content = match cancel_choice_attempt {
                       Ok(_) => cancel_choice.trim().to_ascii_lowercase(),
                       Err(e) => {
@@ -14057,7 +14345,7 @@ impl NoteArchive {
                     let mut cancel_choice = String::new();
                     println_yel!("You must select a note category to fill in a custom note template.");
                     println_inst!("Cancel writing note? ( Y / N )");
-                    let cancel_choice_attempt = io::stdin().read_line(&mut cancel_choice);
+                    let cancel_choice_attempt = read_input_line(&mut cancel_choice);
                     let cancel_choice_content = match cancel_choice_attempt {
                       Ok(_) => cancel_choice.trim().to_ascii_lowercase(),
                       Err(e) => {
@@ -14146,6 +14434,14 @@ impl NoteArchive {
     self.load_note_day(nd_id).unwrap();
 
     let date = self.current_note_day().date.clone();
+    let ncnt = match render_template(&ncnt, &self.template_context()) {
+      Ok(content) => content,
+      Err(e) => {
+        println_err!("{} The template is used as written.", e);
+        thread::sleep(time::Duration::from_secs(3));
+        ncnt
+      }
+    };
     let mut n = self.generate_note(date, ncat, nst, ncnt).unwrap();
 
     let mut focus_id_option: Option<u32> = None;
@@ -14196,7 +14492,7 @@ impl NoteArchive {
 
         // display options here
 
-        let note_attempt = io::stdin().read_line(&mut note_choice);
+        let note_attempt = read_input_line(&mut note_choice);
         match note_attempt {
           Ok(_) => break note_choice.trim().to_ascii_lowercase(),
           Err(e) => {
@@ -14229,7 +14525,7 @@ impl NoteArchive {
               loop {
                 let mut delete_choice = String::new();
                 println_yel!("Delete blank currently filled in with '{}'?", b_tup.1);
-                let delete_choice_attempt = io::stdin().read_line(&mut delete_choice);
+                let delete_choice_attempt = read_input_line(&mut delete_choice);
                 let delete_choice_content = match delete_choice_attempt {
                   Ok(_) => delete_choice.trim().to_ascii_lowercase(),
                   Err(e) => {
@@ -14300,7 +14596,7 @@ impl NoteArchive {
             loop {
               let mut clear_choice = String::new();
               println_yel!("Delete all blanks from the current template and start over?");
-              let clear_choice_attempt = io::stdin().read_line(&mut clear_choice);
+              let clear_choice_attempt = read_input_line(&mut clear_choice);
               let clear_choice_content = match clear_choice_attempt {
                 Ok(_) => clear_choice.trim().to_ascii_lowercase(),
                 Err(e) => {
@@ -14343,7 +14639,7 @@ impl NoteArchive {
                   }
                   println_inst!("Press ENTER to choose or edit collaterals on the next menu.");
                   let mut s = String::new();
-                  let input_attempt = io::stdin().read_line(&mut s);
+                  let input_attempt = read_input_line(&mut s);
                   match input_attempt {
                     _ => (),
                   }
@@ -14354,33 +14650,7 @@ impl NoteArchive {
               }
             },
             ClientGoal => {
-              let mut fill_ins: Vec<u32> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match self.select_client_goals(Some(fill_ins.clone())) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_strings = fill_ins.iter().map(|g_id| self.get_goal_by_id(*g_id).unwrap().goal.clone() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      String::new()
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              if fill_ins.len() > 0 {
+              if let Some((final_blank_string, fill_ins)) = self.choose_client_goal_fill_in() {
                 n.blanks.insert(i, (b.clone(), final_blank_string, fill_ins));
               }
             },
@@ -14426,7 +14696,7 @@ impl NoteArchive {
                   println_inst!("No primary contact found for current client.");
                   println_inst!("Press ENTER to choose or edit collaterals on the next menu.");
                   let mut s = String::new();
-                  let input_attempt = io::stdin().read_line(&mut s);
+                  let input_attempt = read_input_line(&mut s);
                   match input_attempt {
                     _ => (),
                   }
@@ -14443,7 +14713,7 @@ impl NoteArchive {
                   println_inst!("No guardian found for current client.");
                   println_inst!("Press ENTER to choose or edit collaterals on the next menu.");
                   let mut s = String::new();
-                  let input_attempt = io::stdin().read_line(&mut s);
+                  let input_attempt = read_input_line(&mut s);
                   match input_attempt {
                     _ => (),
                   }
@@ -14460,7 +14730,7 @@ impl NoteArchive {
                   println_inst!("No Care Plan Team members found for current client.");
                   println_inst!("Press ENTER to choose or edit collaterals on the next menu.");
                   let mut s = String::new();
-                  let input_attempt = io::stdin().read_line(&mut s);
+                  let input_attempt = read_input_line(&mut s);
                   match input_attempt {
                     _ => (),
                   }
@@ -14470,492 +14740,33 @@ impl NoteArchive {
                 }
               }
             }
-            InternalDocument => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(InternalDocument, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| InternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            ExternalDocument => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(ExternalDocument, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ExternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            InternalMeeting => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(InternalMeeting, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| InternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            ExternalMeeting => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(ExternalMeeting, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ExternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            Appearance => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(Appearance, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| AppearanceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            SupportedParent => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(SupportedParent, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| SupportedParentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
+            InternalDocument | ExternalDocument | InternalMeeting | ExternalMeeting | Appearance | SupportedParent
+              | ParentingSkill | CarePlanningTopic | YouthTopic | ContactMethod | ContactPurpose
+              | FulfilledContactPurpose | Service | MeetingMethod | SignatureMethod => {
+              let final_blank_string = Self::choose_blank_fill_ins(b.clone(), self.vocabularies_or_built_in().options(&b));
+              n.blanks.insert(i, (b.clone(), final_blank_string, vec![]));
             },
-            ParentingSkill => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(ParentingSkill, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ParentingSkillFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
+            CustomBlank => {
+              loop {
+                let mut custom_choice = String::new();
+                println_inst!("Enter custom content for the current blank. CANCEL to cancel.");
+                let custom_attempt = read_input_line(&mut custom_choice);
+                let custom_content = match custom_attempt {
+                  Ok(_) => custom_choice.trim(),
+                  Err(e) => {
+                    println_err!("Failed to read line: {}", e);
+                    thread::sleep(time::Duration::from_secs(2));
+                    continue;
                   }
                 };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
+                let blank_string = custom_content.to_string();
+                let id_vec = vec![];
+                if &blank_string.to_ascii_lowercase()[..] == "cancel" {
+                  break;
                 }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            CarePlanningTopic => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(CarePlanningTopic, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| CarePlanningTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            YouthTopic => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(YouthTopic, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| YouthTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            ContactMethod => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(ContactMethod, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ContactMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            ContactPurpose => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(ContactPurpose, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            FulfilledContactPurpose => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(FulfilledContactPurpose, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| FulfilledContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            Service => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(Service, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| ServiceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            MeetingMethod => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(MeetingMethod, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| MeetingMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            SignatureMethod => {
-              let mut fill_ins: Vec<usize> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match Self::select_blank_fill_in(SignatureMethod, fill_ins.clone()) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_objects = fill_ins.iter().map(|fi| SignatureMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                    let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      continue;
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              let id_vec = vec![];
-              n.blanks.insert(i, (b.clone(), final_blank_string, id_vec));
-            },
-            CustomBlank => {
-              loop {
-                let mut custom_choice = String::new();
-                println_inst!("Enter custom content for the current blank. CANCEL to cancel.");
-                let custom_attempt = io::stdin().read_line(&mut custom_choice);
-                let custom_content = match custom_attempt {
-                  Ok(_) => custom_choice.trim(),
-                  Err(e) => {
-                    println_err!("Failed to read line: {}", e);
-                    thread::sleep(time::Duration::from_secs(2));
-                    continue;
-                  }
-                };
-                let blank_string = custom_content.to_string();
-                let id_vec = vec![];
-                if &blank_string.to_ascii_lowercase()[..] == "cancel" {
-                  break;
-                }
-                n.blanks.insert(i, (b.clone(), blank_string, id_vec));
-                break;
-              }
+                n.blanks.insert(i, (b.clone(), blank_string, id_vec));
+                break;
+              }
             },
             _ => (),
           }
@@ -14974,7 +14785,7 @@ impl NoteArchive {
                     loop {
                       let mut fill_choice = String::new();
                       println_inst!("Fill custom blank with '{}'? ( Y / N )", choice);
-                      let fill_choice_attempt = io::stdin().read_line(&mut fill_choice);
+                      let fill_choice_attempt = read_input_line(&mut fill_choice);
                       let fill_choice_content = match fill_choice_attempt {
                         Ok(_) => fill_choice.trim().to_ascii_lowercase(),
                         Err(e) => {
@@ -15002,576 +14813,132 @@ impl NoteArchive {
                     loop {
                       let mut custom_choice = String::new();
                       println_inst!("Enter custom content for the current blank.");
-                      let custom_attempt = io::stdin().read_line(&mut custom_choice);
-                      let custom_content = match custom_attempt {
-                        Ok(_) => custom_choice.trim(),
-                        Err(e) => {
-                          println_err!("Failed to read line: {}", e);
-                          thread::sleep(time::Duration::from_secs(2));
-                          continue;
-                        }
-                      };
-                      let blank_string = custom_content.to_string();
-                      let id_vec = vec![];
-                      if &blank_string.to_ascii_lowercase()[..] == "cancel" {
-                        continue 'choice;
-                      }
-                      n.blanks.insert(i, (b.clone(), blank_string, id_vec));
-                      continue 'choice;
-                    }
-                  }
-                },
-                _ => {
-                  println_err!("Invalid command.");
-                  thread::sleep(time::Duration::from_secs(2));
-                  continue;
-                }
-              }
-            },
-          };
-          if blank_id > n.number_of_blanks() {
-            println_err!("Invalid blank ID.");
-            thread::sleep(time::Duration::from_secs(2));
-            continue;
-          }
-          focus_id_option = Some(blank_id as u32)
-        },
-      }
-    }
-
-    let note_id = n.id;
-    self.save_note(n);
-
-    self.write_to_files();
-    Some(note_id)
-    
-  }
-  fn display_blank_menus() {
-
-    let fillables = Blank::vec_of_fillables();
-
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    println_on_bg!("{:-^113}", "-");
-    println_on_bg!("{:-^113}", " All default fill-ins for blanks ");
-    println_on_bg!("{:-^113}", "-");
-    for (i, fillable) in fillables.iter().enumerate() {
-      println_on_bg!(
-        "{: ^10} | {: <100}",
-        i+1,
-        &fillable.display_to_user_empty(),
-      );
-    }
-    println_on_bg!("{:-^113}", "-");
-    
-    println_inst!("Select type of blank by ID.");
-    println_inst!(
-      "| {}",
-      "CANCEL / C: Cancel",
-    );
-  }
-  fn get_fillable_blank() -> Option<Blank> {
-    loop {
-      NoteArchive::display_blank_menus();
-      let mut buffer = String::new();
-      let idx_attempt = io::stdin().read_line(&mut buffer);
-      let idx = match idx_attempt {
-        Ok(_) => buffer.trim().to_ascii_lowercase(),
-        Err(e) => {
-          println_err!("Failed to read line: {}", e);
-          thread::sleep(time::Duration::from_secs(2));
-          continue;
-        }
-      };
-      match &idx[..] {
-        "cancel" | "c" => {
-          return None;
-        },
-        _ => {
-          match idx.parse::<usize>() {
-            Err(e) => {
-              println_err!("Invalid input: '{}' ({})", idx, e);
-              thread::sleep(time::Duration::from_secs(2));
-              continue;
-            },
-            Ok(num) => {
-              match Blank::vec_of_fillables().iter().nth(num-1) {
-                None => {
-                  println_err!("Please choose from among the displayed options.");
-                  thread::sleep(time::Duration::from_secs(2));
-                  continue;
-                },
-                Some(blank_ref) => return Some(blank_ref.to_owned()),
-              }
-            }
-          }
-        }
-      }
-    }
-  }
-  fn get_blank_from_menu() -> Option<(Blank, String)> {
-    loop {
-      let chosen_blank = NoteArchive::get_fillable_blank();
-      let b = match chosen_blank {
-        None => return None,
-        Some(blank) => blank,
-      };
-
-      match b.clone() {
-        InternalDocument => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(InternalDocument, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| InternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string))
-        },
-        ExternalDocument => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(ExternalDocument, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ExternalDocumentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        InternalMeeting => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(InternalMeeting, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| InternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        ExternalMeeting => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(ExternalMeeting, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ExternalMeetingFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        Appearance => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(Appearance, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| AppearanceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        SupportedParent => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(SupportedParent, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| SupportedParentFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        ParentingSkill => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(ParentingSkill, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ParentingSkillFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        CarePlanningTopic => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(CarePlanningTopic, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| CarePlanningTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        YouthTopic => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(YouthTopic, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| YouthTopicFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        ContactMethod => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(ContactMethod, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ContactMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        ContactPurpose => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(ContactPurpose, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        FulfilledContactPurpose => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(FulfilledContactPurpose, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| FulfilledContactPurposeFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
-        },
-        Service => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(Service, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| ServiceFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
+                      let custom_attempt = read_input_line(&mut custom_choice);
+                      let custom_content = match custom_attempt {
+                        Ok(_) => custom_choice.trim(),
+                        Err(e) => {
+                          println_err!("Failed to read line: {}", e);
+                          thread::sleep(time::Duration::from_secs(2));
+                          continue;
+                        }
+                      };
+                      let blank_string = custom_content.to_string();
+                      let id_vec = vec![];
+                      if &blank_string.to_ascii_lowercase()[..] == "cancel" {
+                        continue 'choice;
+                      }
+                      n.blanks.insert(i, (b.clone(), blank_string, id_vec));
+                      continue 'choice;
+                    }
+                  }
+                },
+                _ => {
+                  println_err!("Invalid command.");
+                  thread::sleep(time::Duration::from_secs(2));
+                  continue;
+                }
               }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
+            },
           };
-          return Some((b, final_blank_string));
+          if blank_id > n.number_of_blanks() {
+            println_err!("Invalid blank ID.");
+            thread::sleep(time::Duration::from_secs(2));
+            continue;
+          }
+          focus_id_option = Some(blank_id as u32)
         },
-        MeetingMethod => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(MeetingMethod, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| MeetingMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
-              }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
-            }
-          };
-          return Some((b, final_blank_string));
+      }
+    }
+
+    let note_id = n.id;
+    self.save_note(n);
+
+    self.write_to_files();
+    Some(note_id)
+    
+  }
+  fn display_blank_menus() {
+
+    let fillables = Blank::vec_of_fillables();
+
+    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+    println_on_bg!("{:-^113}", "-");
+    println_on_bg!("{:-^113}", " All default fill-ins for blanks ");
+    println_on_bg!("{:-^113}", "-");
+    for (i, fillable) in fillables.iter().enumerate() {
+      println_on_bg!(
+        "{: ^10} | {: <100}",
+        i+1,
+        &fillable.display_to_user_empty(),
+      );
+    }
+    println_on_bg!("{:-^113}", "-");
+    
+    println_inst!("Select type of blank by ID.");
+    println_inst!(
+      "| {}",
+      "CANCEL / C: Cancel",
+    );
+  }
+  fn get_fillable_blank() -> Option<Blank> {
+    loop {
+      NoteArchive::display_blank_menus();
+      let mut buffer = String::new();
+      let idx_attempt = read_input_line(&mut buffer);
+      let idx = match idx_attempt {
+        Ok(_) => buffer.trim().to_ascii_lowercase(),
+        Err(e) => {
+          println_err!("Failed to read line: {}", e);
+          thread::sleep(time::Duration::from_secs(2));
+          continue;
+        }
+      };
+      match &idx[..] {
+        "cancel" | "c" => {
+          return None;
         },
-        SignatureMethod => {
-          let mut fill_ins: Vec<usize> = vec![];
-          let final_blank_string = loop {
-            let blank_id = match Self::select_blank_fill_in(SignatureMethod, fill_ins.clone()) {
-              Some(s) => s,
-              None => {
-                let fill_in_objects = fill_ins.iter().map(|fi| SignatureMethodFillIn::iterator_of_blanks().nth(*fi).unwrap() );
-                let fill_in_strings = fill_in_objects.map(|fo| fo.selected_display() ).collect::<Vec<String>>();
-                break if fill_in_strings.len() > 1 {
-                  format!(
-                    "{}{}{}",
-                    fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                    " and ",
-                    fill_in_strings[fill_in_strings.len()-1],
-                  )
-                } else if fill_in_strings.len() > 0 {
-                  fill_in_strings[0].clone()
-                } else {
-                  return None;
-                };
+        _ => {
+          match idx.parse::<usize>() {
+            Err(e) => {
+              println_err!("Invalid input: '{}' ({})", idx, e);
+              thread::sleep(time::Duration::from_secs(2));
+              continue;
+            },
+            Ok(num) => {
+              match Blank::vec_of_fillables().iter().nth(num-1) {
+                None => {
+                  println_err!("Please choose from among the displayed options.");
+                  thread::sleep(time::Duration::from_secs(2));
+                  continue;
+                },
+                Some(blank_ref) => return Some(blank_ref.to_owned()),
               }
-            };
-            if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-              fill_ins.push(blank_id);
-            } else {
-              fill_ins.retain(|fi| fi != &blank_id )
             }
-          };
+          }
+        }
+      }
+    }
+  }
+  fn get_blank_from_menu(vocabularies: &Vocabularies) -> Option<(Blank, String)> {
+    loop {
+      let chosen_blank = NoteArchive::get_fillable_blank();
+      let b = match chosen_blank {
+        None => return None,
+        Some(blank) => blank,
+      };
+
+      match b.clone() {
+        InternalDocument | ExternalDocument | InternalMeeting | ExternalMeeting | Appearance | SupportedParent
+          | ParentingSkill | CarePlanningTopic | YouthTopic | ContactMethod | ContactPurpose
+          | FulfilledContactPurpose | Service | MeetingMethod | SignatureMethod => {
+          let final_blank_string = Self::choose_blank_fill_ins(b, vocabularies.options(&b));
           return Some((b, final_blank_string));
         },
         CustomBlank => {
           let final_string = loop {
             println!("Enter text exactly as you would like it to appear.");
             let mut input = String::new();
-            let input_attempt = io::stdin().read_line(&mut input);
+            let input_attempt = read_input_line(&mut input);
             match input_attempt {
               Ok(_) => break String::from(&input[..input.len()-2]),
               Err(e) => {
@@ -15595,7 +14962,7 @@ impl NoteArchive {
           let mut choice = String::new();
           println_yel!("In order to enter a note manually, you must select a category.");
           println_inst!("Do you wish to cancel writing this note?");
-          let choice_att = io::stdin().read_line(&mut choice);
+          let choice_att = read_input_line(&mut choice);
           match choice_att {
             Ok(_) => {
               match &choice.trim().to_ascii_lowercase()[..] {
@@ -15729,12 +15096,12 @@ impl NoteArchive {
       }
       
       let mut choice = String::new();
-      let choice_att = io::stdin().read_line(&mut choice);
+      let choice_att = read_input_line(&mut choice);
       match choice_att {
         Ok(_) => {
           match &choice.trim().to_ascii_lowercase()[..] {
             "" => {
-              let b_opt = NoteArchive::get_blank_from_menu();
+              let b_opt = NoteArchive::get_blank_from_menu(&self.vocabularies_or_built_in());
               let (blank, blank_fill) = match b_opt {
                 Some((b, bf)) => (b, bf),
                 None => continue,
@@ -15806,33 +15173,7 @@ impl NoteArchive {
               continue;
             },
             "goal" | "g" => {
-              let mut fill_ins: Vec<u32> = vec![];
-              let final_blank_string = loop {
-                let blank_id = match self.select_client_goals(Some(fill_ins.clone())) {
-                  Some(s) => s,
-                  None => {
-                    let fill_in_strings = fill_ins.iter().map(|g_id| self.get_goal_by_id(*g_id).unwrap().goal.clone() ).collect::<Vec<String>>();
-                    break if fill_in_strings.len() > 1 {
-                      format!(
-                        "{}{}{}",
-                        fill_in_strings[..fill_in_strings.len()-1].join(", "),
-                        " and ",
-                        fill_in_strings[fill_in_strings.len()-1],
-                      )
-                    } else if fill_in_strings.len() > 0 {
-                      fill_in_strings[0].clone()
-                    } else {
-                      String::new()
-                    };
-                  }
-                };
-                if !fill_ins.clone().iter().any(|fi| fi == &blank_id ) {
-                  fill_ins.push(blank_id);
-                } else {
-                  fill_ins.retain(|fi| fi != &blank_id )
-                }
-              };
-              if fill_ins.len() > 0 {
+              if let Some((final_blank_string, fill_ins)) = self.choose_client_goal_fill_in() {
                 n.add_blank(ClientGoal);
                 n.blanks.insert(current_blank, (ClientGoal, final_blank_string, fill_ins));
                 current_blank += 1;
@@ -15910,7 +15251,7 @@ impl NoteArchive {
                 println_inst!("No Care Plan Team members found for current client.");
                 println_inst!("Press ENTER to choose or edit collaterals on the next menu.");
                 let mut s = String::new();
-                let input_attempt = io::stdin().read_line(&mut s);
+                let input_attempt = read_input_line(&mut s);
                 match input_attempt {
                   _ => (),
                 }
@@ -15929,7 +15270,7 @@ impl NoteArchive {
                   self.display_select_general_collaterals(Some(general_collat_ids.clone()));
                   println_inst!("ALL: Select all");
                   let mut choice = String::new();
-                  let read_attempt = io::stdin().read_line(&mut choice);
+                  let read_attempt = read_input_line(&mut choice);
                   match read_attempt {
                     Ok(_) => break choice.trim().to_string(),
                     Err(e) => {
@@ -15961,7 +15302,7 @@ impl NoteArchive {
                   "" => {
                     break;
                   }
-                  _ => match general_input.parse() {
+                  _ => match general_input.parse::<u32>() {
                     Ok(num) => {
                       let collat = match self.general_collaterals.iter().find(|co| co.id == num) {
                         Some(co) => co.clone(),
@@ -16164,7 +15505,7 @@ impl NoteArchive {
         "CANCEL / C: cancel",
       );
       let mut input = String::new();
-      let input_attempt = io::stdin().read_line(&mut input);
+      let input_attempt = read_input_line(&mut input);
       let input_val = match input_attempt {
         Ok(_) => input.trim().to_ascii_lowercase(),
         Err(e) => {
@@ -16192,7 +15533,7 @@ impl NoteArchive {
     structure: StructureType,
     content: String,
   ) -> Result<Note, String> {
-    let id: u32 = self.notes.len() as u32 + 1;
+    let id: u32 = self.next_id("notes");
     let user_id = self.current_user().id;
     let client_id = match self.foreign_key.get("current_client_id") {
       None => self.select_client(),
@@ -16206,210 +15547,116 @@ impl NoteArchive {
 
     Ok(Note::new(id, date, category, structure, content, user_id, client_id, collateral_ids))
   }
-  pub fn read_notes(filepath: &str) -> Result<Vec<Note>, Error> {
-    let file = OpenOptions::new()
-      .read(true)
-      .write(true)
-      .create(true)
-      .open(filepath)
-      .unwrap();
-
-    let reader = BufReader::new(file);
-
-    let mut lines: Vec<std::io::Result<String>> = reader.lines().collect();
-
-    if lines.len() > 0 {
-      lines.remove(0)?;
-    }
-    if lines.len() > 0 {
-      lines.remove(lines.len() - 1)?;
-    }
-
-    let mut notes: Vec<Note> = vec![];
-
-    for line in lines {
-      let line_string = line?;
-
-      let values: Vec<String> = line_string
-        .split(" | ")
-        .map(|val| val.to_string())
-        .collect();
-
-      let id: u32 = values[0].parse().unwrap();
-
-      let date_vec: Vec<i32> = match &values[1][..] {
-        "" => vec![],
-        _ => values[1]
-        .split("-")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
-
-      let (year, month, day): (i32, u32, u32) = (date_vec[0], date_vec[1] as u32, date_vec[2] as u32);
-      let date = NaiveDate::from_ymd(year, month, day);
+  pub fn read_notes(filepath: &str) -> Result<(Vec<Note>, Vec<LoadError>), Error> {
+    let (mut notes, errors) = Self::read_text_records(filepath, NoteRecord, Self::parse_note_line)?;
+    notes.sort_by(|a, b| a.id.cmp(&b.id));
+    notes.sort_by(|a, b|
+      a.foreign_key["user_id"].cmp(&b.foreign_key["user_id"])
+    );
+    notes.sort_by(|a, b|
+      a.foreign_key["client_id"].cmp(&b.foreign_key["client_id"])
+    );
+    notes.sort_by(|a, b| a.date.cmp(&b.date) );
+    Ok((notes, errors))
+  }
+  pub fn parse_note_line(line: &str) -> Result<Note, FieldError> {
+    let values = LineFields::new(line);
+    let id = values.id(0, "id")?;
+    let date = values.date(1, "date")?;
 
-      let category_strings: Vec<String> = values[2].split(" - ").map(|s| s.to_string()).collect();
-      let (category_string, subcategory_string) = (category_strings[0].clone(), category_strings[1].clone());
-      
-      let category = match &category_string[..] {
-        "ICC Note" => {
-          let subcategory = match &subcategory_string[..] {
-            "Face to face contact with client" => FaceToFaceContactWithClient,
-            "Telephone contact with client" => TelephoneContactWithClient,
-            "Care coordination" => CareCoordination,
-            "Documentation" => Documentation,
-            "Care planning team" => CarePlanningTeam,
-            "Transport client" => TransportClient,
-            "Member outreach/no-show" => MemberOutreachNoShow,
-            _ => return Err(Error::new(
-              ErrorKind::Other,
-              "Unsupported ICC note subcategory saved to file.",
-            )),
-          };
-          ICCNote(subcategory)
-        },
-        "FP Note" => {
-          let subcategory = match &subcategory_string[..] {
-            "Functioning" => Functioning,
-            "Plan/additional information" => PlanAdditionalInformation,
-            "Description of 'face to face contact'" => DescriptionOfIntervention(Some(FaceToFaceContact)),
-            "Description of 'collateral contact'" => DescriptionOfIntervention(Some(CollateralContact)),
-            "Description of 'crisis support'" => DescriptionOfIntervention(Some(CrisisSupport)),
-            "Description of 'telephone support'" => DescriptionOfIntervention(Some(TelephoneSupport)),
-            "Description of 'direct time with providers'" => DescriptionOfIntervention(Some(DirectTimeWithProviders)),
-            "Description of 'educationg, coaching, modeling and guiding'" => DescriptionOfIntervention(Some(EducatingCoachingModelingAndGuiding)),
-            "Description of 'engage parent/caregiver in addressing goals'" => DescriptionOfIntervention(Some(EngageParentCaregiverInAddressingGoals)),
-            "Description of 'teach advocacy, guide linkage to resources'" => DescriptionOfIntervention(Some(TeachAdvocacyGuideLinkageToResources)),
-            "Description of 'teach networking in community and with providers'" => DescriptionOfIntervention(Some(TeachNetworkingInCommunityAndWithProviders)),
-            "Description of 'provider outreach to person'" => DescriptionOfIntervention(Some(ProviderOutreachToPerson)),
-            "Description of 'member transportation by staff'" => DescriptionOfIntervention(Some(MemberTransportationByStaff)),
-            "Description of 'no show/late cancellation'" => DescriptionOfIntervention(Some(NoShowLateCancellation)),
-            "Description of 'documentation'" => DescriptionOfIntervention(Some(FPInterventionDocumentation)),
-            "Description of 'other'" => DescriptionOfIntervention(Some(Other)),
-            "Response to 'face to face contact'" => ResponseToIntervention(Some(FaceToFaceContact)),
-            "Response to 'collateral contact'" => ResponseToIntervention(Some(CollateralContact)),
-            "Response to 'crisis support'" => ResponseToIntervention(Some(CrisisSupport)),
-            "Response to 'telephone support'" => ResponseToIntervention(Some(TelephoneSupport)),
-            "Response to 'direct time with providers'" => ResponseToIntervention(Some(DirectTimeWithProviders)),
-            "Response to 'educating, coaching, modeling and guiding'" => ResponseToIntervention(Some(EducatingCoachingModelingAndGuiding)),
-            "Response to 'engage parent/caregiver in addressing goals'" => ResponseToIntervention(Some(EngageParentCaregiverInAddressingGoals)),
-            "Response to 'teach advocacy, guide linkage to resources'" => ResponseToIntervention(Some(TeachAdvocacyGuideLinkageToResources)),
-            "Response to 'teach networking in community and with providers'" => ResponseToIntervention(Some(TeachNetworkingInCommunityAndWithProviders)),
-            "Response to 'provider outreach to person'" => ResponseToIntervention(Some(ProviderOutreachToPerson)),
-            "Response to 'member transportation by staff'" => ResponseToIntervention(Some(MemberTransportationByStaff)),
-            "Response to 'no show/late cancellation'" => ResponseToIntervention(Some(NoShowLateCancellation)),
-            "Response to 'documentation" => ResponseToIntervention(Some(FPInterventionDocumentation)),
-            "Response to 'other'" => ResponseToIntervention(Some(Other)),
-            _ => return Err(Error::new(
-              ErrorKind::Other,
-              "Unsupported FP note subcategory saved to file.",
-            )),
-          };
-          FPNote(subcategory)
-        },
-        _ => return Err(Error::new(
-          ErrorKind::Other,
-          "Unsupported note category saved to file.",
-        )),
-      };
+    let category_field = values.text(2, "category")?;
+    let (category_string, subcategory_string) = match category_field.find(" - ") {
+      Some(i) => (&category_field[..i], &category_field[i+3..]),
+      None => return Err(FieldError::new("category", format!("'{}' has no subcategory", category_field))),
+    };
+    let category = match category_string {
+      "ICC Note" => {
+        let subcategory = match subcategory_string {
+          "Face to face contact with client" => FaceToFaceContactWithClient,
+          "Telephone contact with client" => TelephoneContactWithClient,
+          "Care coordination" => CareCoordination,
+          "Documentation" => Documentation,
+          "Care planning team" => CarePlanningTeam,
+          "Transport client" => TransportClient,
+          "Member outreach/no-show" => MemberOutreachNoShow,
+          _ => return Err(FieldError::new("category", format!("unsupported ICC note subcategory '{}'", subcategory_string))),
+        };
+        ICCNote(subcategory)
+      },
+      "FP Note" => {
+        let subcategory = match subcategory_string {
+          "Functioning" => Functioning,
+          "Plan/additional information" => PlanAdditionalInformation,
+          "Description of 'face to face contact'" => DescriptionOfIntervention(Some(FaceToFaceContact)),
+          "Description of 'collateral contact'" => DescriptionOfIntervention(Some(CollateralContact)),
+          "Description of 'crisis support'" => DescriptionOfIntervention(Some(CrisisSupport)),
+          "Description of 'telephone support'" => DescriptionOfIntervention(Some(TelephoneSupport)),
+          "Description of 'direct time with providers'" => DescriptionOfIntervention(Some(DirectTimeWithProviders)),
+          "Description of 'educationg, coaching, modeling and guiding'" => DescriptionOfIntervention(Some(EducatingCoachingModelingAndGuiding)),
+          "Description of 'engage parent/caregiver in addressing goals'" => DescriptionOfIntervention(Some(EngageParentCaregiverInAddressingGoals)),
+          "Description of 'teach advocacy, guide linkage to resources'" => DescriptionOfIntervention(Some(TeachAdvocacyGuideLinkageToResources)),
+          "Description of 'teach networking in community and with providers'" => DescriptionOfIntervention(Some(TeachNetworkingInCommunityAndWithProviders)),
+          "Description of 'provider outreach to person'" => DescriptionOfIntervention(Some(ProviderOutreachToPerson)),
+          "Description of 'member transportation by staff'" => DescriptionOfIntervention(Some(MemberTransportationByStaff)),
+          "Description of 'no show/late cancellation'" => DescriptionOfIntervention(Some(NoShowLateCancellation)),
+          "Description of 'documentation'" => DescriptionOfIntervention(Some(FPInterventionDocumentation)),
+          "Description of 'other'" => DescriptionOfIntervention(Some(Other)),
+          "Response to 'face to face contact'" => ResponseToIntervention(Some(FaceToFaceContact)),
+          "Response to 'collateral contact'" => ResponseToIntervention(Some(CollateralContact)),
+          "Response to 'crisis support'" => ResponseToIntervention(Some(CrisisSupport)),
+          "Response to 'telephone support'" => ResponseToIntervention(Some(TelephoneSupport)),
+          "Response to 'direct time with providers'" => ResponseToIntervention(Some(DirectTimeWithProviders)),
+          "Response to 'educating, coaching, modeling and guiding'" => ResponseToIntervention(Some(EducatingCoachingModelingAndGuiding)),
+          "Response to 'engage parent/caregiver in addressing goals'" => ResponseToIntervention(Some(EngageParentCaregiverInAddressingGoals)),
+          "Response to 'teach advocacy, guide linkage to resources'" => ResponseToIntervention(Some(TeachAdvocacyGuideLinkageToResources)),
+          "Response to 'teach networking in community and with providers'" => ResponseToIntervention(Some(TeachNetworkingInCommunityAndWithProviders)),
+          "Response to 'provider outreach to person'" => ResponseToIntervention(Some(ProviderOutreachToPerson)),
+          "Response to 'member transportation by staff'" => ResponseToIntervention(Some(MemberTransportationByStaff)),
+          "Response to 'no show/late cancellation'" => ResponseToIntervention(Some(NoShowLateCancellation)),
+          "Response to 'documentation" => ResponseToIntervention(Some(FPInterventionDocumentation)),
+          "Response to 'other'" => ResponseToIntervention(Some(Other)),
+          _ => return Err(FieldError::new("category", format!("unsupported FP note subcategory '{}'", subcategory_string))),
+        };
+        FPNote(subcategory)
+      },
+      _ => return Err(FieldError::new("category", format!("unsupported note category '{}'", category_string))),
+    };
 
-      let structure = match &values[3][..] {
-        "Care Plan" => CarePlan,
-        "Intake" => Intake,
-        "Assessment" => Assessment,
-        "SNCD" => Sncd,
-        "Home Visit" => HomeVisit,
-        "Agenda Prep" => AgendaPrep,
-        "Debrief" => Debrief,
-        "Phone Call" => PhoneCall,
-        "Scheduling" => Scheduling,
-        "Sent Email" => SentEmail,
-        "Referral" => Referral,
-        "Custom Structure" => CustomStructure,
-        "Parent Support" => ParentSupport,
-        "Sent Cancellation" => SentCancellation,
-        "Parent Appearance" => ParentAppearance,
-        "Parent Skills" => ParentSkills,
-        "Failed Contact Attempt" => FailedContactAttempt,
-        "Categorized Emails" => CategorizedEmails,
-        "Authorization Requested" => AuthorizationRequested,
-        "Authorization Issued" => AuthorizationIssued,
-        "Collateral Outreach" => CollateralOutreach,
-        "Update From Collateral" => UpdateFromCollateral,
-        "Invited To Meeting" => InvitedToMeeting,
-        "Sent Document" => SentDocument,
-        "Updated Document" => UpdatedDocument,
-        "Discuss Communication" => DiscussCommunication,
-        "Received Verbal Consent" => ReceivedVerbalConsent,
-        "Received Written Consent" => ReceivedWrittenConsent,
-        "Documentation" => DocumentationStructure,
-        "Brainstorm Contribution" => BrainstormContribution,
-        _ => return Err(Error::new(
-          ErrorKind::Other,
-          "Unsupported StructureType saved to file.",
-        )),
-      };
+    let structure = Self::parse_structure_type(values.text(3, "structure")?)?;
 
-      let content = values[4].clone();
+    let content = String::from(values.text(4, "content")?);
 
-      let blanks_strings: Vec<String> = values[5].split("/#/").map(|s| s.to_string() ).collect();
-      let mut blanks: HashMap<u32, (Blank, String, Vec<u32>)> = HashMap::new();
+    let mut blanks: HashMap<u32, (Blank, String, Vec<u32>)> = HashMap::new();
 
-        // pub blanks: HashMap<u32, (Blank, String, Vec<u32>)> 
+    // pub blanks: HashMap<u32, (Blank, String, Vec<u32>)> 
 
-      for b_string in blanks_strings {
-        if b_string != String::from("") {
-          let blank_values: Vec<String> = b_string.split("/%/").map(|st| st.to_string() ).collect();
-  
-          let blank_position: u32 = blank_values[0].parse().unwrap();
-          let blank = Blank::get_blank_from_str(&blank_values[1]);
-          let blank_content = blank_values[2].clone();
-          let blank_foreign_keys: Vec<u32> = blank_values[3]
-            .split('-')
-            .map(|b_id_string| b_id_string.parse() )
-            .filter(|b_id_res| b_id_res.is_ok() )
-            .map(|b_id_res| b_id_res.unwrap() )
-            .collect();
-  
-          blanks.insert(blank_position, (blank, blank_content, blank_foreign_keys));
+    for b_string in values.text(5, "blanks")?.split("/#/") {
+      if !b_string.is_empty() {
+        let blank_values: Vec<&str> = b_string.split("/%/").collect();
+        if blank_values.len() < 4 {
+          return Err(FieldError::new("blanks", format!("'{}' does not have a position, type, content and IDs", b_string)));
         }
-      }
 
-      let note_user_id: u32 = values[6].parse().unwrap();
-      let note_client_id: u32 = values[7].parse().unwrap();
+        let blank_position: u32 = blank_values[0].parse()
+          .map_err(|_| FieldError::new("blanks", format!("'{}' is not a valid blank position", blank_values[0])) )?;
+        let blank = Blank::try_from_str(blank_values[1]).map_err(|e| FieldError::new("blanks", e) )?;
+        let blank_content = String::from(blank_values[2]);
+        let blank_foreign_keys: Vec<u32> = blank_values[3]
+          .split('-')
+          .filter_map(|b_id_string| b_id_string.parse().ok() )
+          .collect();
 
-      let collateral_ids: Vec<u32> = match &values[8][..] {
-        "" => vec![],
-        _ => values[8]
-          .split("#")
-          .map(|val| val.parse().unwrap())
-          .collect(),
-      };
+        blanks.insert(blank_position, (blank, blank_content, blank_foreign_keys));
+      }
+    }
 
-      let mut n = Note::new(id, date, category, structure, content, note_user_id, note_client_id, collateral_ids);
-      n.blanks = blanks;
+    let note_user_id = values.id(6, "user ID")?;
+    let note_client_id = values.id(7, "client ID")?;
+    let collateral_ids = values.ids(8, "collateral IDs")?;
 
-      notes.push(n);
-    }
-    notes.sort_by(|a, b| a.id.cmp(&b.id));
-    notes.sort_by(|a, b|
-      a.foreign_key["user_id"].cmp(&b.foreign_key["user_id"])
-    );
-    notes.sort_by(|a, b|
-      a.foreign_key["client_id"].cmp(&b.foreign_key["client_id"])
-    );
-    notes.sort_by(|a, b| a.date.cmp(&b.date) );
-    Ok(notes)
-  }
-  pub fn write_notes(&self) -> std::io::Result<()> {
-    let mut lines = String::from("##### notes #####\n");
-    for n in &self.notes {
-      lines.push_str(&n.to_string()[..]);
-    }
-    lines.push_str("##### notes #####");
-    let mut file = File::create(self.filepaths["note_filepath"].clone()).unwrap();
-    file.write_all(lines.as_bytes()).unwrap();
-    Ok(())
+    let mut n = Note::new(id, date, category, structure, content, note_user_id, note_client_id, collateral_ids);
+    n.blanks = blanks;
+    Ok(n)
   }
   fn save_note(&mut self, note: Note) {
 
@@ -16436,7 +15683,7 @@ impl NoteArchive {
       println_yel!("Are you sure you want to delete this note?");
       println_inst!("| {} | {}", "YES / Y: confirm", "Any other key to cancel");
       let mut confirm = String::new();
-      let input_attempt = io::stdin().read_line(&mut confirm);
+      let input_attempt = read_input_line(&mut confirm);
       let command = match input_attempt {
         Ok(_) => confirm.trim().to_string(),
         Err(e) => {
@@ -16448,10 +15695,6 @@ impl NoteArchive {
       match &command[..] {
         "YES" | "yes" | "Yes" | "Y" | "y" => {
           self.delete_current_note();
-          let new_note_id = self.reindex_notes(None);
-          match new_note_id {
-            _ => (),
-          }
           self.write_to_files();
           break;
         }
@@ -16482,37 +15725,7 @@ impl NoteArchive {
     self.current_note_day_mut().foreign_keys.insert(String::from("note_ids"), new_ids);
     self.foreign_key.remove("current_note_id");
   }
-  fn reindex_notes(&mut self, current_id: Option<u32>) -> Option<u32> {
-    let mut i: u32 = 1;
-    let mut new_current_id: Option<u32> = None;
-    let mut changes: HashMap<u32, u32> = HashMap::new();
-    for mut n in &mut self.notes {
-      match current_id {
-        Some(id) => {
-          if n.id == id {
-            new_current_id = Some(i);
-          }
-        },
-        None => (),
-      }
-      if n.id == i {
-        ()
-      } else {
-        changes.insert(n.id, i);
-        n.id = i;
-      }
-      i += 1;
-    }
-    for nd in &mut self.note_days {
-      let old_ids = nd.foreign_keys["note_ids"].clone();
-      let new_ids: Vec<u32> = old_ids.iter()
-        .map(|co_id| match changes.get(co_id) { Some(val) => *val, None => *co_id } )
-        .collect();
-      nd.foreign_keys.insert(String::from("note_ids"), new_ids);
-    }
-    new_current_id
-  }
-  fn get_note_option_by_id(&self, id: u32) -> Option<&Note> {
+  pub fn get_note_option_by_id(&self, id: u32) -> Option<&Note> {
     self.notes.iter().find(|n| n.id == id)
   }
   // fn get_note_option_by_id_mut(&mut self, id: u32) -> Option<&mut Note> {
@@ -16528,15 +15741,7 @@ mod tests {
   fn can_open_blank_files() {
     {
       let filepaths: HashMap<String, String> = [
-        (String::from("user_filepath"), String::from("some_random_blank_user_file_name.txt"),),
-        (String::from("client_filepath"), String::from("some_random_blank_client_file_name.txt"),),
-        (String::from("goal_filepath"), String::from("some_random_blank_goal_file_name.txt"),),
-        (String::from("collateral_filepath"), String::from("some_random_blank_collateral_file_name.txt"),),
-        (String::from("general_collateral_filepath"), String::from("some_random_blank_general_collateral_file_name.txt"),),
-        (String::from("pronouns_filepath"), String::from("some_random_blank_pronouns_file_name.txt"),),
-        (String::from("note_day_filepath"), String::from("some_random_blank_note_day_file_name.txt"),),
-        (String::from("note_template_filepath"), String::from("some_random_blank_note_template_file_name.txt"),),
-        (String::from("note_filepath"), String::from("some_random_blank_note_file_name.txt"),),
+        (String::from("archive_filepath"), String::from("some_random_blank_archive_file_name.json"),),
       ].iter().cloned().collect();
       let a = NoteArchive::new(filepaths);
       assert_eq!(a.users, vec![]);
@@ -16568,15 +15773,7 @@ mod tests {
         ]
       );
     }
-    fs::remove_file("some_random_blank_user_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_client_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_collateral_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_general_collateral_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_goal_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_pronouns_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_note_day_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_note_template_file_name.txt").unwrap();
-    fs::remove_file("some_random_blank_note_file_name.txt").unwrap();
+    assert!(!Path::new("some_random_blank_archive_file_name.json").exists());
   }
   #[test]
   fn can_load_from_files() {
@@ -16606,15 +15803,7 @@ mod tests {
         String::from("his"),
       );
         let filepaths: HashMap<String, String> = [
-          (String::from("user_filepath"), String::from("test_load_user.txt"),),
-          (String::from("client_filepath"), String::from("test_load_client.txt"),),
-          (String::from("goal_filepath"), String::from("test_load_goal.txt"),),
-          (String::from("collateral_filepath"), String::from("test_load_collateral.txt"),),
-          (String::from("general_collateral_filepath"), String::from("test_load_general_collateral.txt"),),
-          (String::from("pronouns_filepath"), String::from("test_load_pronouns.txt"),),
-          (String::from("note_day_filepath"), String::from("test_load_note_days.txt"),),
-          (String::from("note_template_filepath"), String::from("test_load_note_templates.txt"),),
-          (String::from("note_filepath"), String::from("test_load_note.txt"),),
+          (String::from("archive_filepath"), String::from("test_load_archive.json"),),
         ].iter().cloned().collect();
       let mut a1 = NoteArchive::new(filepaths);
 
@@ -16628,28 +15817,110 @@ mod tests {
       let some_id: &u32 = &1;
       assert_eq!(a1.foreign_key.get("current_user_id"), Some(some_id));
     }
-    fs::remove_file("test_load_user.txt").unwrap();
-    fs::remove_file("test_load_client.txt").unwrap();
-    fs::remove_file("test_load_collateral.txt").unwrap();
-    fs::remove_file("test_load_general_collateral.txt").unwrap();
-    fs::remove_file("test_load_goal.txt").unwrap();
-    fs::remove_file("test_load_pronouns.txt").unwrap();
-    fs::remove_file("test_load_note_days.txt").unwrap();
-    fs::remove_file("test_load_note_templates.txt").unwrap();
-    fs::remove_file("test_load_note.txt").unwrap();
+    fs::remove_file("test_load_archive.json").unwrap();
+  }
+  #[test]
+  fn imports_text_files_into_archive() {
+    let dir = std::env::temp_dir().join("wrap_notes_test_import_text_files");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let filepaths = crate::config::filepaths_for_dir(&dir);
+    let user = User::new(1, String::from("Bob"), String::from("Smith"), Icc, 3, vec![1], vec![]);
+    let client = Client::new(1, String::from("Ann"), String::from("Lee"), NaiveDate::from_ymd(2010, 5, 6), 2, vec![]);
+    fs::write(&filepaths["user_filepath"], format!("##### users #####\n{}##### users #####", user)).unwrap();
+    fs::write(&filepaths["client_filepath"], format!("##### clients #####\n{}##### clients #####", client)).unwrap();
+    {
+      let mut a = NoteArchive::open(filepaths.clone()).unwrap();
+      assert_eq!(a.users, vec![user.clone()]);
+      assert_eq!(a.clients, vec![client.clone()]);
+      assert_eq!(a.pronouns.len(), 3);
+      assert!(Path::new(&filepaths["archive_filepath"]).exists());
+      a.clients[0].first_name = String::from("Ann | Marie");
+      a.write_to_files();
+    }
+    // the text files are no longer read once the archive exists
+    fs::write(&filepaths["client_filepath"], "").unwrap();
+    let reloaded = NoteArchive::open(filepaths).unwrap();
+    assert_eq!(reloaded.clients[0].first_name, String::from("Ann | Marie"));
+    assert_eq!(reloaded.users, vec![user]);
+    fs::remove_dir_all(&dir).unwrap();
+  }
+  #[test]
+  fn collects_unreadable_records_and_repairs_them() {
+    let dir = std::env::temp_dir().join("wrap_notes_test_load_errors");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let filepaths = crate::config::filepaths_for_dir(&dir);
+    let user = User::new(1, String::from("Bob"), String::from("Smith"), Icc, 3, vec![1], vec![]);
+    let client = Client::new(1, String::from("Ann"), String::from("Lee"), NaiveDate::from_ymd(2010, 5, 6), 2, vec![]);
+    fs::write(
+      &filepaths["user_filepath"],
+      format!("##### users #####\n{}2 | Jo | Doe | XYZ | 1 |  | \n##### users #####", user),
+    ).unwrap();
+    fs::write(&filepaths["client_filepath"], format!("##### clients #####\n{}##### clients #####", client)).unwrap();
+
+    let mut a = NoteArchive::open(filepaths.clone()).unwrap();
+    assert_eq!(a.users, vec![user.clone()]);
+    assert_eq!(a.clients, vec![client.clone()]);
+    assert_eq!(a.load_errors.len(), 1);
+    assert_eq!(a.load_errors[0].line, 3);
+    assert_eq!(a.load_errors[0].field, String::from("role"));
+    assert!(!Path::new(&filepaths["archive_filepath"]).exists());
+
+    let error = a.load_errors[0].clone();
+    assert!(a.repair_record(&error, "1 | Jo | Doe | FP | 1 |  | ").is_err());
+    a.repair_record(&error, "2 | Jo | Doe | FP | 1 |  | ").unwrap();
+    assert_eq!(a.users[1].role, Fp);
+    a.load_errors.clear();
+    a.write_to_files();
+
+    let archive_filepath = &filepaths["archive_filepath"];
+    let mut value: serde_json::Value = serde_json::from_str(&fs::read_to_string(archive_filepath).unwrap()).unwrap();
+    value["clients"][0]["dob"] = serde_json::Value::from("not a date");
+    fs::write(archive_filepath, value.to_string()).unwrap();
+
+    let reloaded = NoteArchive::open(filepaths.clone()).unwrap();
+    assert_eq!(reloaded.users.len(), 2);
+    assert!(reloaded.clients.is_empty());
+    assert_eq!(reloaded.load_errors.len(), 1);
+    assert_eq!(reloaded.load_errors[0].kind, ClientRecord);
+    assert_eq!(reloaded.load_errors[0].format, JsonRecord);
+    assert_eq!(reloaded.load_errors[0].field, String::from("dob"));
+
+    let quarantine_filepath = reloaded.load_errors[0].quarantine().unwrap();
+    assert!(fs::read_to_string(quarantine_filepath).unwrap().contains("not a date"));
+    fs::remove_dir_all(&dir).unwrap();
+  }
+  #[test]
+  fn keeps_ids_when_sorting_and_deleting() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_stable_ids_archive.json"),),
+    ].iter().cloned().collect();
+    {
+      let mut a = NoteArchive::new_test(filepaths.clone());
+      a.note_days[0].date = NaiveDate::from_ymd(2021, 1, 4);
+      a.note_days[1].date = NaiveDate::from_ymd(2021, 2, 4);
+      let ids_by_date: Vec<(NaiveDate, u32)> = a.note_days.iter().map(|nd| (nd.date, nd.id) ).collect();
+      a.note_days.reverse();
+      a.sort_data_by_dates();
+      for (date, id) in ids_by_date {
+        assert_eq!(a.note_days.iter().find(|nd| nd.date == date ).unwrap().id, id);
+      }
+
+      assert_eq!(a.next_id("users"), 3);
+      a.users.retain(|u| u.id != 2 );
+      a.write_to_files();
+      assert_eq!(a.next_id("users"), 3);
+    }
+    let reloaded = NoteArchive::open(filepaths).unwrap();
+    assert_eq!(reloaded.next_id("users"), 3);
+    assert_eq!(reloaded.next_id("pronouns"), 4);
+    fs::remove_file("test_stable_ids_archive.json").unwrap();
   }
   #[test]
   fn creates_unique_new_instances() {
     let filepaths: HashMap<String, String> = [
-      (String::from("user_filepath"), String::from("test_user_new_instance.txt"),),
-      (String::from("client_filepath"), String::from("test_client_new_instance.txt"),),
-      (String::from("goal_filepath"), String::from("test_goal_new_instance.txt"),),
-      (String::from("collateral_filepath"), String::from("test_collateral_new_instance.txt"),),
-      (String::from("general_collateral_filepath"), String::from("test_general_collateral_new_instance.txt"),),
-      (String::from("pronouns_filepath"), String::from("test_pronouns_new_instance.txt"),),
-      (String::from("note_day_filepath"), String::from("test_note_days_new_instance.txt"),),
-      (String::from("note_template_filepath"), String::from("test_note_templates_new_instance.txt"),),
-      (String::from("note_filepath"), String::from("test_note_new_instance.txt"),),
+      (String::from("archive_filepath"), String::from("test_archive_new_instance.json"),),
     ].iter().cloned().collect();
 
     let mut notes = NoteArchive::new_test(filepaths.clone());
@@ -16661,6 +15932,7 @@ mod tests {
       String::from("Carlson"),
       NaiveDate::from_ymd(2008, 3, 4),
       1,
+      NaiveDate::from_ymd(2026, 9, 14),
     );
 
     let new_pronouns_attempt = notes.generate_unique_new_pronouns(
@@ -16712,6 +15984,7 @@ mod tests {
         vec![]
       )
     );
+    assert_eq!(new_client.current_episode().map(|e| e.enrollment_date ), Some(NaiveDate::from_ymd(2026, 9, 14)));
     assert_eq!(
       new_pronouns,
       Pronouns::new(
@@ -16732,15 +16005,7 @@ mod tests {
   #[test]
   fn gets_current_pronouns() {
     let filepaths: HashMap<String, String> = [
-      (String::from("user_filepath"), String::from("test_user_current_pronouns.txt"),),
-      (String::from("client_filepath"), String::from("test_client_current_pronouns.txt"),),
-      (String::from("goal_filepath"), String::from("test_goal_current_pronouns.txt"),),
-      (String::from("collateral_filepath"), String::from("test_collateral_current_pronouns.txt"),),
-      (String::from("general_collateral_filepath"), String::from("test_glonal_collateral_current_pronouns.txt"),),
-      (String::from("pronouns_filepath"), String::from("test_pronouns_current_pronouns.txt"),),
-      (String::from("note_day_filepath"), String::from("test_note_days_current_pronouns.txt"),),
-      (String::from("note_template_filepath"), String::from("test_note_templates_current_pronouns.txt"),),
-      (String::from("note_filepath"), String::from("test_note_current_pronouns.txt"),),
+      (String::from("archive_filepath"), String::from("test_archive_current_pronouns.json"),),
     ].iter().cloned().collect();
     let mut notes = NoteArchive::new_test(filepaths.clone());
 
@@ -16759,15 +16024,7 @@ mod tests {
   #[test]
   fn updates_current_pronouns() {
     let filepaths: HashMap<String, String> = [
-      (String::from("user_filepath"), String::from("test_user_updates_pronouns.txt"),),
-      (String::from("client_filepath"), String::from("test_client_updates_pronouns.txt"),),
-      (String::from("goal_filepath"), String::from("test_goal_updates_pronouns.txt"),),
-      (String::from("collateral_filepath"), String::from("test_collateral_updates_pronouns.txt"),),
-      (String::from("general_collateral_filepath"), String::from("test_general_collateral_updates_pronouns.txt"),),
-      (String::from("pronouns_filepath"), String::from("test_pronouns_updates_pronouns.txt"),),
-      (String::from("note_day_filepath"), String::from("test_note_days_updates_pronouns.txt"),),
-      (String::from("note_template_filepath"), String::from("test_note_templates_updates_pronouns.txt"),),
-      (String::from("note_filepath"), String::from("test_note_updates_pronouns.txt"),),
+      (String::from("archive_filepath"), String::from("test_archive_updates_pronouns.json"),),
     ].iter().cloned().collect();
     let mut notes = NoteArchive::new_test(filepaths.clone());
 
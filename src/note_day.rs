@@ -1,9 +1,10 @@
 use std::fmt;
 use chrono::{NaiveDate, Datelike, Weekday};
 use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoteDay {
   pub id: u32,
   pub foreign_key: HashMap<String, u32>,
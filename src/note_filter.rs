@@ -0,0 +1,160 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::billing::BillingCode;
+use crate::compliance::NoteMatch;
+use crate::note::*;
+use crate::note_archive::*;
+
+use Blank::{Collaterals, AllCollaterals, PrimaryContact, Guardian, CarePlanTeam};
+
+/// conditions a note must meet to be listed; conditions left as None match every note
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoteFilter {
+  pub user_id: Option<u32>,
+  pub client_id: Option<u32>,
+  /// a collateral named in one of the note's blanks
+  pub collateral_id: Option<u32>,
+  pub structure: Option<StructureType>,
+  /// the contact type, which groups FP notes by intervention (see BillingCode)
+  pub category: Option<BillingCode>,
+  pub since: Option<NaiveDate>,
+  pub until: Option<NaiveDate>,
+  pub has_unfilled_blanks: Option<bool>,
+}
+
+impl NoteFilter {
+  /// reads space separated conditions such as 'client:3 structure:CollateralOutreach category:CrisisSupport
+  /// collateral:4 since:2021-03-01 until:2021-03-31 last:30 unfilled:yes'; 'last:N' means since N days before `today`
+  pub fn parse(s: &str, today: NaiveDate) -> Result<NoteFilter, String> {
+    let mut filter = NoteFilter::default();
+    for condition in s.split_whitespace() {
+      let (key, value) = condition.split_once(':')
+        .ok_or(format!("'{}' is not a condition such as client:3 or since:YYYY-MM-DD.", condition))?;
+      let id = || value.parse::<u32>().map_err(|_| format!("Invalid ID for {}: {}", key, value) );
+      let date = || NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| format!("Invalid date (use YYYY-MM-DD): {}", value) );
+      match &key.to_ascii_lowercase()[..] {
+        "user" => filter.user_id = Some(id()?),
+        "client" => filter.client_id = Some(id()?),
+        "collateral" => filter.collateral_id = Some(id()?),
+        "structure" => match NoteMatch::parse(value)? {
+          NoteMatch::Structure(st) => filter.structure = Some(st),
+          NoteMatch::Contact(_) => return Err(format!("'{}' is a contact type; use category:{}.", value, value)),
+        },
+        "category" => match NoteMatch::parse(value)? {
+          NoteMatch::Contact(code) => filter.category = Some(code),
+          NoteMatch::Structure(_) => return Err(format!("'{}' is a note structure; use structure:{}.", value, value)),
+        },
+        "since" => filter.since = Some(date()?),
+        "until" => filter.until = Some(date()?),
+        "last" => match value.parse::<u32>() {
+          Ok(days) => filter.since = Some(today - Duration::days(days as i64)),
+          Err(_) => return Err(format!("Invalid number of days: {}", value)),
+        },
+        "unfilled" => match &value.to_ascii_lowercase()[..] {
+          "yes" | "y" | "true" => filter.has_unfilled_blanks = Some(true),
+          "no" | "n" | "false" => filter.has_unfilled_blanks = Some(false),
+          _ => return Err(format!("unfilled must be yes or no, not '{}'.", value)),
+        },
+        _ => return Err(format!("Unknown condition '{}'.", key)),
+      }
+    }
+    Ok(filter)
+  }
+  pub fn matches(&self, n: &Note) -> bool {
+    self.user_id.is_none_or(|id| n.foreign_key["user_id"] == id )
+      && self.client_id.is_none_or(|id| n.foreign_key["client_id"] == id )
+      && self.collateral_id.is_none_or(|id| note_mentions_collateral(n, id) )
+      && self.structure.is_none_or(|st| n.structure == st )
+      && self.category.is_none_or(|code| BillingCode::for_category(&n.category) == code )
+      && self.since.is_none_or(|d| n.date >= d )
+      && self.until.is_none_or(|d| n.date <= d )
+      && self.has_unfilled_blanks.is_none_or(|unfilled| n.has_unfilled_blanks() == unfilled )
+  }
+  /// the conditions in the same form parse reads, or 'all notes' if there are none
+  pub fn description(&self) -> String {
+    let mut conditions: Vec<String> = vec![];
+    if let Some(id) = self.user_id {
+      conditions.push(format!("user:{}", id));
+    }
+    if let Some(id) = self.client_id {
+      conditions.push(format!("client:{}", id));
+    }
+    if let Some(id) = self.collateral_id {
+      conditions.push(format!("collateral:{}", id));
+    }
+    if let Some(st) = self.structure {
+      conditions.push(format!("structure:{:?}", st));
+    }
+    if let Some(code) = self.category {
+      conditions.push(format!("category:{}", code.key()));
+    }
+    if let Some(d) = self.since {
+      conditions.push(format!("since:{}", d.format("%Y-%m-%d")));
+    }
+    if let Some(d) = self.until {
+      conditions.push(format!("until:{}", d.format("%Y-%m-%d")));
+    }
+    if let Some(unfilled) = self.has_unfilled_blanks {
+      conditions.push(format!("unfilled:{}", if unfilled { "yes" } else { "no" }));
+    }
+    if conditions.is_empty() {
+      String::from("all notes")
+    } else {
+      conditions.join(" ")
+    }
+  }
+}
+
+/// whether a collateral is filled into any of the note's collateral blanks
+pub fn note_mentions_collateral(n: &Note, collateral_id: u32) -> bool {
+  n.blanks.values().any(|(b, _, ids)| {
+    matches!(b, Collaterals | AllCollaterals | PrimaryContact | Guardian | CarePlanTeam) && ids.contains(&collateral_id)
+  })
+}
+
+impl NoteArchive {
+  /// every note matching the filter, oldest first
+  pub fn filter_notes(&self, filter: &NoteFilter) -> Vec<&Note> {
+    let mut notes: Vec<&Note> = self.notes.iter().filter(|n| filter.matches(n) ).collect();
+    notes.sort_by_key(|n| (n.date, n.id) );
+    notes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn filters_notes_by_each_condition() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_note_filter_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    a.notes.clear();
+    let d = |day| NaiveDate::from_ymd(2021, 3, day);
+    let coordination = NoteCategory::ICCNote(ICCNoteCategory::CareCoordination);
+    let crisis = NoteCategory::FPNote(FPNoteCategory::DescriptionOfIntervention(Some(FPIntervention::CrisisSupport)));
+    let mut with_collateral = Note::new(1, d(2), coordination, StructureType::CollateralOutreach, String::new(), 1, 1, vec![]);
+    with_collateral.blanks.insert(1, (Collaterals, String::from("Pat Doe (Teacher)"), vec![4]));
+    a.notes.push(with_collateral);
+    a.notes.push(Note::new(2, d(20), coordination, StructureType::CollateralOutreach, String::new(), 1, 1, vec![]));
+    a.notes.push(Note::new(3, d(25), crisis, StructureType::Intake, String::new(), 2, 2, vec![]));
+
+    let ids = |filter: &str| -> Vec<u32> {
+      a.filter_notes(&NoteFilter::parse(filter, d(31)).unwrap()).iter().map(|n| n.id ).collect()
+    };
+    assert_eq!(ids(""), vec![1, 2, 3]);
+    assert_eq!(ids("structure:CollateralOutreach collateral:4"), vec![1]);
+    assert_eq!(ids("structure:collateraloutreach last:14"), vec![2]);
+    assert_eq!(ids("category:CrisisSupport client:2"), vec![3]);
+    assert_eq!(ids("user:1 until:2021-03-10"), vec![1]);
+    let filter = NoteFilter::parse("client:1 unfilled:no category:CareCoordination", d(31)).unwrap();
+    assert_eq!(NoteFilter::parse(&filter.description(), d(31)).unwrap(), filter);
+    assert!(NoteFilter::parse("structure:CrisisSupport", d(31)).is_err());
+    assert!(NoteFilter::parse("client", d(31)).is_err());
+    assert!(NoteFilter::parse("mood:good", d(31)).is_err());
+  }
+}
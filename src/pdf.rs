@@ -0,0 +1,414 @@
+use chrono::{Datelike, NaiveDate};
+use std::io::Error;
+use std::path::Path;
+
+use crate::note_archive::*;
+use crate::note_day::*;
+use crate::client::*;
+use crate::export::*;
+use crate::utils::write_file_atomically;
+
+// US letter, in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 72.0;
+const BODY_SIZE: f32 = 11.0;
+const LINE_SPACING: f32 = 1.3;
+
+/// Helvetica glyph widths for ' ' through '~', in thousandths of the font size
+const HELVETICA_WIDTHS: [u16; 95] = [
+  278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+  556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+  1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+  667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+  333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+  556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// the two standard fonts every PDF reader has, so nothing needs to be embedded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfFont {
+  Regular,
+  Bold,
+}
+
+use PdfFont::{Regular, Bold};
+
+impl PdfFont {
+  fn resource_name(&self) -> &'static str {
+    match self {
+      Regular => "F1",
+      Bold => "F2",
+    }
+  }
+  /// width of the text in points; bold is measured a little wide so lines never overrun
+  pub fn text_width(&self, s: &str, size: f32) -> f32 {
+    let units: u32 = s.chars().map(|c| match c {
+      ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+      _ => 556,
+    }).sum();
+    let scale = match self {
+      Regular => 1.0,
+      Bold => 1.08,
+    };
+    units as f32 * size / 1000.0 * scale
+  }
+}
+
+/// a minimal PDF writer: lays out wrapped lines of text top to bottom, starting new pages as needed
+pub struct PdfWriter {
+  pages: Vec<String>,
+  current: String,
+  y: f32,
+}
+
+impl Default for PdfWriter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl PdfWriter {
+  pub fn new() -> PdfWriter {
+    PdfWriter { pages: vec![], current: String::new(), y: PAGE_HEIGHT - MARGIN }
+  }
+  fn content_width() -> f32 {
+    PAGE_WIDTH - 2.0 * MARGIN
+  }
+  pub fn page_break(&mut self) {
+    self.pages.push(std::mem::take(&mut self.current));
+    self.y = PAGE_HEIGHT - MARGIN;
+  }
+  /// moves down, starting a new page if the next `height` points do not fit
+  fn advance(&mut self, height: f32) {
+    if self.y - height < MARGIN {
+      self.page_break();
+    }
+    self.y -= height;
+  }
+  fn line(&mut self, text: &str, font: PdfFont, size: f32, x: f32) {
+    self.advance(size * LINE_SPACING);
+    self.current.push_str(&format!(
+      "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+      font.resource_name(),
+      size,
+      x,
+      self.y,
+      escape_pdf_text(text),
+    ));
+  }
+  /// wraps the text to the page width; blank lines in the text are kept
+  pub fn text(&mut self, text: &str, font: PdfFont, size: f32) {
+    self.text_indented(text, font, size, 0.0);
+  }
+  pub fn text_indented(&mut self, text: &str, font: PdfFont, size: f32, indent: f32) {
+    for line in wrap_to_width(text, font, size, Self::content_width() - indent) {
+      self.line(&line, font, size, MARGIN + indent);
+    }
+  }
+  pub fn heading(&mut self, text: &str) {
+    self.space(6.0);
+    self.text(text, Bold, 16.0);
+    self.space(4.0);
+  }
+  pub fn subheading(&mut self, text: &str) {
+    self.space(8.0);
+    self.text(text, Bold, 13.0);
+  }
+  pub fn paragraph(&mut self, text: &str) {
+    self.text(text, Regular, BODY_SIZE);
+    self.space(BODY_SIZE * 0.5);
+  }
+  pub fn label(&mut self, label: &str, value: &str) {
+    self.advance(BODY_SIZE * LINE_SPACING);
+    let label = format!("{}: ", label);
+    self.current.push_str(&format!(
+      "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj /{} {} Tf ({}) Tj ET\n",
+      Bold.resource_name(),
+      BODY_SIZE,
+      MARGIN,
+      self.y,
+      escape_pdf_text(&label),
+      Regular.resource_name(),
+      BODY_SIZE,
+      escape_pdf_text(value),
+    ));
+  }
+  pub fn space(&mut self, height: f32) {
+    if self.y - height > MARGIN {
+      self.y -= height;
+    }
+  }
+  /// a horizontal line across the page
+  pub fn rule(&mut self) {
+    self.advance(8.0);
+    self.current.push_str(&format!("0.5 w {:.2} {:.2} m {:.2} {:.2} l S\n", MARGIN, self.y, PAGE_WIDTH - MARGIN, self.y));
+  }
+  /// a line to sign on with a label and date line beneath it, kept together on one page
+  pub fn signature_line(&mut self, label: &str) {
+    if self.y - 60.0 < MARGIN {
+      self.page_break();
+    }
+    self.space(36.0);
+    self.current.push_str(&format!("0.5 w {:.2} {:.2} m {:.2} {:.2} l S\n", MARGIN, self.y, MARGIN + 250.0, self.y));
+    self.current.push_str(&format!("0.5 w {:.2} {:.2} m {:.2} {:.2} l S\n", MARGIN + 300.0, self.y, MARGIN + 420.0, self.y));
+    self.advance(BODY_SIZE * LINE_SPACING);
+    self.current.push_str(&format!(
+      "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET\nBT /{} {} Tf {:.2} {:.2} Td (Date) Tj ET\n",
+      Regular.resource_name(), BODY_SIZE - 1.0, MARGIN, self.y, escape_pdf_text(label),
+      Regular.resource_name(), BODY_SIZE - 1.0, MARGIN + 300.0, self.y,
+    ));
+  }
+  /// the finished file, with 'Page N of M' at the foot of each page
+  pub fn to_bytes(mut self) -> Vec<u8> {
+    if !self.current.is_empty() || self.pages.is_empty() {
+      self.pages.push(std::mem::take(&mut self.current));
+    }
+    let page_count = self.pages.len();
+    // objects: 1 catalog, 2 page tree, 3 and 4 fonts, then a page and its content stream for each page
+    let mut objects: Vec<String> = vec![
+      String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+      format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        (0..page_count).map(|i| format!("{} 0 R", 5 + 2 * i) ).collect::<Vec<String>>().join(" "),
+        page_count,
+      ),
+      String::from("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"),
+      String::from("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"),
+    ];
+    for (i, page) in self.pages.iter().enumerate() {
+      let footer = format!("Page {} of {}", i + 1, page_count);
+      let footer_x = (PAGE_WIDTH - Regular.text_width(&footer, 9.0)) / 2.0;
+      let content = format!("{}BT /F1 9 Tf {:.2} {:.2} Td ({}) Tj ET\n", page, footer_x, MARGIN / 2.0, footer);
+      objects.push(format!(
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+        PAGE_WIDTH,
+        PAGE_HEIGHT,
+        6 + 2 * i,
+      ));
+      objects.push(format!("<< /Length {} >>\nstream\n{}endstream", encode_pdf_text(&content).len(), content));
+    }
+    let mut output: Vec<u8> = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets: Vec<usize> = vec![];
+    for (i, object) in objects.iter().enumerate() {
+      offsets.push(output.len());
+      output.extend(format!("{} 0 obj\n", i + 1).as_bytes());
+      output.extend(encode_pdf_text(object));
+      output.extend(b"\nendobj\n");
+    }
+    let xref_offset = output.len();
+    output.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+      output.extend(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    output.extend(format!(
+      "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+      objects.len() + 1,
+      xref_offset,
+    ).as_bytes());
+    output
+  }
+}
+
+/// splits text into lines that fit in `width` points, breaking at spaces
+pub fn wrap_to_width(text: &str, font: PdfFont, size: f32, width: f32) -> Vec<String> {
+  let mut lines: Vec<String> = vec![];
+  for paragraph in text.lines() {
+    let mut line = String::new();
+    for word in paragraph.split_whitespace() {
+      let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+      if !line.is_empty() && font.text_width(&candidate, size) > width {
+        lines.push(std::mem::replace(&mut line, word.to_string()));
+      } else {
+        line = candidate;
+      }
+    }
+    lines.push(line);
+  }
+  lines
+}
+
+/// escapes the characters that end or break a PDF string
+fn escape_pdf_text(s: &str) -> String {
+  let mut escaped = String::with_capacity(s.len());
+  for c in s.chars() {
+    match c {
+      '(' | ')' | '\\' => {
+        escaped.push('\\');
+        escaped.push(c);
+      },
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// converts to the WinAnsi bytes the standard fonts use; characters they cannot show become '?'
+fn encode_pdf_text(s: &str) -> Vec<u8> {
+  s.chars().map(|c| match c {
+    '\u{2018}' => 0x91,
+    '\u{2019}' => 0x92,
+    '\u{201C}' => 0x93,
+    '\u{201D}' => 0x94,
+    '\u{2022}' => 0x95,
+    '\u{2013}' => 0x96,
+    '\u{2014}' => 0x97,
+    c if (c as u32) < 0x80 || ((c as u32) >= 0xA0 && (c as u32) <= 0xFF) => c as u32 as u8,
+    _ => b'?',
+  }).collect()
+}
+
+fn word_count(s: &str) -> usize {
+  s.split_whitespace().count()
+}
+
+/// a printable record of note days for one client: a header block, each day's notes grouped by
+/// category with word counts, and a signature line
+pub fn note_days_pdf(a: &NoteArchive, title: &str, note_days: &[&NoteDay]) -> Vec<u8> {
+  let mut pdf = PdfWriter::new();
+  let mut days: Vec<&NoteDay> = note_days.to_vec();
+  days.sort_by_key(|nd| nd.date );
+
+  pdf.heading(title);
+  if let Some(nd) = days.first() {
+    if let Some(c) = a.get_client_by_id(nd.foreign_key["client_id"]) {
+      pdf.label("Client", &c.full_name());
+      pdf.label("Date of birth", &c.fmt_date_of_birth());
+    }
+    if let Some(u) = a.users.iter().find(|u| u.id == nd.foreign_key["user_id"] ) {
+      pdf.label("User", &u.full_name());
+      pdf.label("Role", &u.role.to_string());
+    }
+  }
+  pdf.rule();
+
+  let mut total_notes = 0;
+  let mut total_words = 0;
+  for nd in &days {
+    pdf.subheading(&nd.fmt_date_long());
+    let mut day_words = 0;
+    for (category, notes) in &a.get_note_day_notes_by_category((*nd).clone()) {
+      if notes.is_empty() {
+        continue;
+      }
+      pdf.space(4.0);
+      pdf.text(&category.to_string(), Bold, BODY_SIZE);
+      for n in notes {
+        let (content, _) = n.generate_display_content_string_with_blanks(None, None, None, None, None);
+        let words = word_count(&content);
+        day_words += words;
+        total_notes += 1;
+        pdf.paragraph(&content);
+        pdf.text_indented(&format!("{} words", words), Regular, BODY_SIZE - 2.0, 0.0);
+        pdf.space(4.0);
+      }
+    }
+    total_words += day_words;
+    pdf.text(&format!("Total for {}: {} words", nd.heading_date(), day_words), Bold, BODY_SIZE - 1.0);
+  }
+  pdf.rule();
+  pdf.text(
+    &format!("{} note days, {} notes, {} words", days.len(), total_notes, total_words),
+    Regular,
+    BODY_SIZE,
+  );
+  let signer = days.first()
+    .and_then(|nd| a.users.iter().find(|u| u.id == nd.foreign_key["user_id"] ) )
+    .map(|u| u.name_and_title())
+    .unwrap_or_else(|| String::from("Signature"));
+  pdf.signature_line(&signer);
+  pdf.to_bytes()
+}
+
+/// the first and last day of a month, or None if the month is not valid
+pub fn month_bounds(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+  let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+  let next = if month == 12 {
+    NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+  } else {
+    NaiveDate::from_ymd_opt(year, month + 1, 1)?
+  };
+  Some((first, next.pred_opt()?))
+}
+
+/// reads a month written as 'YYYY-MM'
+pub fn parse_month(s: &str) -> Result<(NaiveDate, NaiveDate), String> {
+  let invalid = || format!("Invalid month (expected YYYY-MM): {}", s);
+  let (year, month) = s.split_once('-').ok_or_else(invalid)?;
+  let year: i32 = year.parse().map_err(|_| invalid() )?;
+  let month: u32 = month.parse().map_err(|_| invalid() )?;
+  month_bounds(year, month).ok_or_else(invalid)
+}
+
+/// a PDF of a single note day, saved as e.g. 'lee_ann_2021-03-04.pdf'
+pub fn note_day_pdf(a: &NoteArchive, nd: &NoteDay) -> (String, Vec<u8>) {
+  let doc = ExportDocument::new(a, &[nd]);
+  (format!("{}.pdf", doc.file_stem()), note_days_pdf(a, &doc.title(), &[nd]))
+}
+
+/// a PDF of every note day for a client in the month starting on `first`, saved as e.g. 'lee_ann_2021-03.pdf';
+/// None if there are no note days that month
+pub fn client_month_pdf(a: &NoteArchive, c: &Client, first: NaiveDate, last: NaiveDate) -> Option<(String, Vec<u8>)> {
+  let note_days: Vec<&NoteDay> = a.note_days.iter()
+    .filter(|nd| nd.foreign_key["client_id"] == c.id && nd.date >= first && nd.date <= last )
+    .collect();
+  if note_days.is_empty() {
+    return None;
+  }
+  let title = format!("Notes for {}, {} {}", c.full_name(), first.format("%B"), first.year());
+  let file_name = format!("{}_{}.pdf", client_file_name(&c.full_name()), first.format("%Y-%m"));
+  Some((file_name, note_days_pdf(a, &title, &note_days)))
+}
+
+pub fn write_pdf(fp: &Path, bytes: &[u8]) -> Result<(), Error> {
+  if let Some(dir) = fp.parent() {
+    if !dir.as_os_str().is_empty() {
+      std::fs::create_dir_all(dir)?;
+    }
+  }
+  write_file_atomically(fp, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn writes_a_well_formed_pdf() {
+    let mut pdf = PdfWriter::new();
+    pdf.heading("Notes (draft)");
+    for _ in 0..80 {
+      pdf.paragraph("A long line of note text that should wrap across the width of the page more than once, with a backslash \\ too.");
+    }
+    pdf.signature_line("Bob Smith, ICC");
+    let bytes = pdf.to_bytes();
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    assert!(text.starts_with("%PDF-1.4"));
+    assert!(text.ends_with("%%EOF\n"));
+    assert!(text.contains("(Notes \\(draft\\)) Tj"));
+    let page_count = text.matches("/Type /Page ").count();
+    assert!(page_count > 1);
+    assert!(text.contains(&format!("/Count {}", page_count)));
+    assert!(text.contains(&format!("(Page {} of {})", page_count, page_count)));
+
+    // each xref entry points at the start of its object
+    let xref = text.find("xref\n").unwrap();
+    let entries: Vec<&str> = text[xref..].lines().skip(3).take(3).collect();
+    for (i, entry) in entries.iter().enumerate() {
+      let offset: usize = entry[..10].parse().unwrap();
+      assert!(bytes[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+    }
+  }
+
+  #[test]
+  fn wraps_lines_to_the_page() {
+    let lines = wrap_to_width("one two three four five six", Regular, 10.0, 60.0);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|l| Regular.text_width(l, 10.0) <= 60.0 ));
+    assert_eq!(month_bounds(2024, 2), Some((NaiveDate::from_ymd(2024, 2, 1), NaiveDate::from_ymd(2024, 2, 29))));
+    assert_eq!(month_bounds(2021, 12).unwrap().1, NaiveDate::from_ymd(2021, 12, 31));
+    assert_eq!(parse_month("2021-03").unwrap().1, NaiveDate::from_ymd(2021, 3, 31));
+    assert!(parse_month("2021-13").is_err());
+    assert_eq!(encode_pdf_text("it\u{2019}s é"), vec![b'i', b't', 0x92, b's', b' ', 0xE9]);
+  }
+}
@@ -1,6 +1,7 @@
 use std::fmt;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pronouns {
   pub id: u32,
   pub subject: String,
@@ -34,6 +35,14 @@ impl Pronouns {
       possessive,
     }
   }
+  /// the pronouns every archive starts with
+  pub fn defaults() -> Vec<Pronouns> {
+    vec![
+      Pronouns::new(1, String::from("he"), String::from("him"), String::from("his"), String::from("his")),
+      Pronouns::new(2, String::from("she"), String::from("her"), String::from("her"), String::from("hers")),
+      Pronouns::new(3, String::from("they"), String::from("them"), String::from("their"), String::from("theirs")),
+    ]
+  }
   pub fn short_string(&self) -> String {
     String::from(format!("({}/{}/{}/{})", self.subject, self.object, self.possessive_determiner, self.possessive))
   }
@@ -0,0 +1,402 @@
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::billing::BillingCode;
+use crate::compliance::NoteMatch;
+use crate::note::*;
+use crate::note_archive::*;
+
+/// characters shown on each side of the first match in a snippet
+pub const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchRecord {
+  Note(u32),
+  NoteTemplate(u32),
+  Collateral(u32),
+}
+
+impl fmt::Display for SearchRecord {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SearchRecord::Note(id) => write!(f, "note {}", id),
+      SearchRecord::NoteTemplate(id) => write!(f, "template {}", id),
+      SearchRecord::Collateral(id) => write!(f, "collateral {}", id),
+    }
+  }
+}
+
+/// the searchable text of one record; each field is searched separately, so phrases never run from one into the next
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDocument {
+  pub record: SearchRecord,
+  pub title: String,
+  pub fields: Vec<String>,
+  pub client_ids: Vec<u32>,
+  pub date: Option<NaiveDate>,
+  pub structure: Option<StructureType>,
+  pub category: Option<NoteCategory>,
+}
+
+/// where one word appears: the document and field, the word's position in the field, and its byte span
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Posting {
+  doc: usize,
+  field: usize,
+  position: usize,
+  start: usize,
+  end: usize,
+}
+
+/// lowercase words with the byte span of each in `s`
+pub fn tokenize(s: &str) -> Vec<(String, usize, usize)> {
+  let mut tokens = vec![];
+  let mut start: Option<usize> = None;
+  for (i, c) in s.char_indices().chain(std::iter::once((s.len(), ' '))) {
+    match (c.is_alphanumeric(), start) {
+      (true, None) => start = Some(i),
+      (false, Some(st)) => {
+        tokens.push((s[st..i].to_lowercase(), st, i));
+        start = None;
+      },
+      _ => (),
+    }
+  }
+  tokens
+}
+
+/// limits on which records a search returns; a record without the filtered field (such as a template
+/// when searching one client's records) is left out
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SearchFilter {
+  pub client_id: Option<u32>,
+  pub since: Option<NaiveDate>,
+  pub until: Option<NaiveDate>,
+  pub category: Option<NoteMatch>,
+}
+
+impl SearchFilter {
+  pub fn allows(&self, d: &SearchDocument) -> bool {
+    let client_ok = self.client_id.is_none_or(|id| d.client_ids.contains(&id) );
+    let since_ok = self.since.is_none_or(|since| d.date.is_some_and(|date| date >= since ) );
+    let until_ok = self.until.is_none_or(|until| d.date.is_some_and(|date| date <= until ) );
+    let category_ok = match self.category {
+      None => true,
+      Some(NoteMatch::Structure(st)) => d.structure == Some(st),
+      Some(NoteMatch::Contact(code)) => d.category.is_some_and(|c| BillingCode::for_category(&c) == code ),
+    };
+    client_ok && since_ok && until_ok && category_ok
+  }
+}
+
+/// words and quoted phrases that must all appear, plus any 'client:', 'since:', 'until:' and 'category:' filters
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SearchQuery {
+  pub terms: Vec<Vec<String>>,
+  pub filter: SearchFilter,
+}
+
+impl SearchQuery {
+  pub fn parse(s: &str) -> Result<SearchQuery, String> {
+    let mut query = SearchQuery::default();
+    for (i, part) in s.split('"').enumerate() {
+      if i % 2 == 1 {
+        let phrase: Vec<String> = tokenize(part).into_iter().map(|t| t.0 ).collect();
+        if !phrase.is_empty() {
+          query.terms.push(phrase);
+        }
+        continue;
+      }
+      for word in part.split_whitespace() {
+        match word.split_once(':') {
+          Some(("client", id)) => query.filter.client_id = Some(
+            id.parse().map_err(|_| format!("Invalid client ID: {}", id) )?
+          ),
+          Some(("since", date)) => query.filter.since = Some(parse_search_date(date)?),
+          Some(("until", date)) => query.filter.until = Some(parse_search_date(date)?),
+          Some(("category", name)) => query.filter.category = Some(NoteMatch::parse(name)?),
+          _ => query.terms.extend(tokenize(word).into_iter().map(|t| vec![t.0] )),
+        }
+      }
+    }
+    if query.terms.is_empty() {
+      return Err(String::from("Enter at least one word to search for."));
+    }
+    Ok(query)
+  }
+}
+
+fn parse_search_date(s: &str) -> Result<NaiveDate, String> {
+  NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| format!("Invalid date (use YYYY-MM-DD): {}", s) )
+}
+
+/// part of a field around the first match, with the byte spans of every match inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+  pub text: String,
+  pub highlights: Vec<(usize, usize)>,
+}
+
+impl Snippet {
+  /// the snippet with matches in square brackets, for plain text output
+  pub fn to_marked_string(&self) -> String {
+    let mut output = String::new();
+    let mut prev_end = 0;
+    for (start, end) in &self.highlights {
+      output.push_str(&self.text[prev_end..*start]);
+      output.push_str(&format!("[{}]", &self.text[*start..*end]));
+      prev_end = *end;
+    }
+    output.push_str(&self.text[prev_end..]);
+    output
+  }
+  /// alternating (text, is_match) pieces, for printing matches highlighted
+  pub fn pieces(&self) -> Vec<(&str, bool)> {
+    let mut pieces = vec![];
+    let mut prev_end = 0;
+    for (start, end) in &self.highlights {
+      pieces.push((&self.text[prev_end..*start], false));
+      pieces.push((&self.text[*start..*end], true));
+      prev_end = *end;
+    }
+    pieces.push((&self.text[prev_end..], false));
+    pieces
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult<'a> {
+  pub document: &'a SearchDocument,
+  /// how many times the terms matched, used to rank results
+  pub hits: usize,
+  pub snippet: Snippet,
+}
+
+/// an inverted index from each word to everywhere it appears in the archive
+pub struct SearchIndex {
+  pub documents: Vec<SearchDocument>,
+  postings: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+  /// indexes a user's notes, templates and collaterals, or everything in the archive if `user_id` is None
+  pub fn new(a: &NoteArchive, user_id: Option<u32>) -> SearchIndex {
+    let user = user_id.and_then(|id| a.users.iter().find(|u| u.id == id ) );
+    let mut documents: Vec<SearchDocument> = vec![];
+    for n in a.notes.iter().filter(|n| user_id.is_none_or(|id| n.foreign_key["user_id"] == id ) ) {
+      let client_name = a.get_client_by_id(n.foreign_key["client_id"]).map(|c| c.full_name() ).unwrap_or_default();
+      let mut fields = vec![n.generate_display_content_string_with_blanks(None, None, None, None, None).0];
+      let mut positions: Vec<&u32> = n.blanks.keys().collect();
+      positions.sort();
+      fields.extend(positions.into_iter().map(|p| n.blanks[p].1.clone() ));
+      documents.push(SearchDocument {
+        record: SearchRecord::Note(n.id),
+        title: format!("{} {} - {}", n.fmt_date(), client_name, n.category),
+        fields,
+        client_ids: vec![n.foreign_key["client_id"]],
+        date: Some(n.date),
+        structure: Some(n.structure),
+        category: Some(n.category),
+      });
+    }
+    for nt in &a.note_templates {
+      let in_scope = match user_id {
+        None => true,
+        Some(id) => !nt.custom || nt.foreign_keys.get("user_ids").is_some_and(|ids| ids.contains(&id) ),
+      };
+      if in_scope {
+        documents.push(SearchDocument {
+          record: SearchRecord::NoteTemplate(nt.id),
+          title: format!("{} template", nt.structure),
+          fields: vec![nt.generate_display_content_string_with_blanks(None, None, None, None, None).0],
+          client_ids: vec![],
+          date: None,
+          structure: Some(nt.structure),
+          category: None,
+        });
+      }
+    }
+    for co in &a.collaterals {
+      if user.is_some_and(|u| !u.foreign_keys["collateral_ids"].contains(&co.id) ) {
+        continue;
+      }
+      let mut fields = vec![co.full_name(), co.title.clone()];
+      fields.extend(co.institution.clone());
+      documents.push(SearchDocument {
+        record: SearchRecord::Collateral(co.id),
+        title: co.full_name_and_title(),
+        fields,
+        client_ids: a.clients.iter().filter(|c| c.foreign_keys["collateral_ids"].contains(&co.id) ).map(|c| c.id ).collect(),
+        date: None,
+        structure: None,
+        category: None,
+      });
+    }
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+    for (doc, d) in documents.iter().enumerate() {
+      for (field, text) in d.fields.iter().enumerate() {
+        for (position, (word, start, end)) in tokenize(text).into_iter().enumerate() {
+          postings.entry(word).or_default().push(Posting { doc, field, position, start, end });
+        }
+      }
+    }
+    SearchIndex { documents, postings }
+  }
+  /// every place a word or phrase appears, as the posting of each phrase's first and last words
+  fn term_matches(&self, term: &[String]) -> Vec<(Posting, Posting)> {
+    let firsts = match self.postings.get(&term[0]) {
+      Some(p) => p,
+      None => return vec![],
+    };
+    let mut later_words: Vec<HashMap<(usize, usize, usize), Posting>> = vec![];
+    for word in &term[1..] {
+      match self.postings.get(word) {
+        Some(p) => later_words.push(p.iter().map(|p| ((p.doc, p.field, p.position), *p) ).collect()),
+        None => return vec![],
+      }
+    }
+    firsts.iter().filter_map(|first| {
+      let mut last = *first;
+      for (i, word_postings) in later_words.iter().enumerate() {
+        last = *word_postings.get(&(first.doc, first.field, first.position + i + 1))?;
+      }
+      Some((*first, last))
+    }).collect()
+  }
+  /// records containing every term and passing the filter, most matches first, then newest first
+  pub fn search(&self, query: &SearchQuery) -> Vec<SearchResult<'_>> {
+    let mut matches_by_doc: HashMap<usize, Vec<(usize, usize, usize)>> = HashMap::new();
+    let mut docs: Option<HashSet<usize>> = None;
+    for term in &query.terms {
+      let matches = self.term_matches(term);
+      let term_docs: HashSet<usize> = matches.iter().map(|(first, _)| first.doc ).collect();
+      docs = Some(match docs {
+        Some(d) => d.intersection(&term_docs).copied().collect(),
+        None => term_docs,
+      });
+      for (first, last) in matches {
+        matches_by_doc.entry(first.doc).or_default().push((first.field, first.start, last.end));
+      }
+    }
+    let mut results: Vec<SearchResult> = docs.unwrap_or_default().into_iter()
+      .filter(|doc| query.filter.allows(&self.documents[*doc]) )
+      .map(|doc| {
+        let mut spans = matches_by_doc.remove(&doc).unwrap_or_default();
+        spans.sort();
+        SearchResult {
+          document: &self.documents[doc],
+          hits: spans.len(),
+          snippet: snippet(&self.documents[doc].fields[spans[0].0], &spans),
+        }
+      })
+      .collect();
+    results.sort_by(|x, y| {
+      y.hits.cmp(&x.hits)
+        .then_with(|| y.document.date.cmp(&x.document.date) )
+        .then_with(|| x.document.title.cmp(&y.document.title) )
+    });
+    results
+  }
+}
+
+/// the text around the first match in its field; `spans` are (field, start, end), sorted
+fn snippet(text: &str, spans: &[(usize, usize, usize)]) -> Snippet {
+  let (field, first_start, _) = spans[0];
+  let mut start = first_start;
+  for (count, (i, _)) in text[..first_start].char_indices().rev().enumerate() {
+    start = i;
+    if count + 1 >= SNIPPET_CONTEXT_CHARS {
+      break;
+    }
+  }
+  let end = text[first_start..].char_indices()
+    .nth(SNIPPET_CONTEXT_CHARS * 2)
+    .map(|(i, _)| first_start + i )
+    .unwrap_or(text.len());
+  let mut highlights: Vec<(usize, usize)> = vec![];
+  for (_, s, e) in spans.iter().filter(|(f, s, e)| *f == field && *s >= start && *e <= end ) {
+    let (s, e) = (s - start, e - start);
+    match highlights.last_mut() {
+      Some(last) if s <= last.1 => last.1 = last.1.max(e),
+      _ => highlights.push((s, e)),
+    }
+  }
+  let prefix = if start > 0 { "..." } else { "" };
+  let suffix = if end < text.len() { "..." } else { "" };
+  Snippet {
+    text: format!("{}{}{}", prefix, &text[start..end], suffix),
+    highlights: highlights.into_iter().map(|(s, e)| (s + prefix.len(), e + prefix.len()) ).collect(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn searches_words_and_phrases_with_filters() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_search_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    a.notes.clear();
+    let category = NoteCategory::ICCNote(ICCNoteCategory::FaceToFaceContactWithClient);
+    for (id, client_id, day, text) in [
+      (1, 1, 3, "Met with youth at school to review the safety plan."),
+      (2, 2, 4, "Reviewed the plan for safety with the family."),
+      (3, 1, 5, "Discussed school attendance and the safety plan again."),
+    ] {
+      let date = NaiveDate::from_ymd(2021, 3, day);
+      a.notes.push(Note::new(id, date, category, StructureType::HomeVisit, String::from(text), 1, client_id, vec![]));
+    }
+    let index = SearchIndex::new(&a, Some(1));
+    let ids = |query: &str| -> Vec<SearchRecord> {
+      index.search(&SearchQuery::parse(query).unwrap()).iter()
+        .filter(|r| matches!(r.document.record, SearchRecord::Note(_)) )
+        .map(|r| r.document.record )
+        .collect()
+    };
+
+    assert_eq!(ids("\"safety plan\""), vec![SearchRecord::Note(3), SearchRecord::Note(1)]);
+    assert_eq!(ids("safety plan family"), vec![SearchRecord::Note(2)]);
+    assert_eq!(ids("\"safety plan\" client:1 since:2021-03-04"), vec![SearchRecord::Note(3)]);
+    assert_eq!(ids("school category:CareCoordination"), vec![]);
+    assert!(SearchQuery::parse("since:March").is_err());
+    assert!(SearchQuery::parse("\"\"").is_err());
+
+    let results = index.search(&SearchQuery::parse("\"SAFETY plan\" client:2").unwrap());
+    assert!(results.is_empty());
+    let results = index.search(&SearchQuery::parse("\"safety plan\" until:2021-03-03").unwrap());
+    assert_eq!(results[0].snippet.to_marked_string(), "Met with youth at school to review the [safety plan].");
+  }
+
+  #[test]
+  fn merges_overlapping_highlights_far_into_a_note() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_search_snippet_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    a.notes.clear();
+    let category = NoteCategory::ICCNote(ICCNoteCategory::FaceToFaceContactWithClient);
+    let text = "Met with the youth and family at home to talk about the school week and then reviewed the safety plan together.";
+    a.notes.push(Note::new(1, NaiveDate::from_ymd(2021, 3, 3), category, StructureType::HomeVisit, String::from(text), 1, 1, vec![]));
+    let index = SearchIndex::new(&a, Some(1));
+    let results = index.search(&SearchQuery::parse("safety \"safety plan\"").unwrap());
+    let snippet = &results[0].snippet;
+    assert_eq!(snippet.highlights.len(), 1);
+    assert!(snippet.to_marked_string().ends_with("reviewed the [safety plan] together."));
+    assert_eq!(snippet.pieces().iter().filter(|(_, is_match)| *is_match ).count(), 1);
+  }
+
+  #[test]
+  fn tokenizes_with_byte_spans() {
+    assert_eq!(tokenize("Café, y'all!"), vec![
+      (String::from("café"), 0, 5),
+      (String::from("y"), 7, 8),
+      (String::from("all"), 9, 12),
+    ]);
+  }
+}
@@ -0,0 +1,264 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::note::*;
+use crate::note_archive::*;
+use crate::template_lint::*;
+use crate::utils::write_file_atomically;
+
+pub const TEMPLATE_LIBRARY_VERSION: u32 = 1;
+
+/// one template in a library file; IDs and users belong to the archive it is imported into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryTemplate {
+  pub structure: StructureType,
+  pub content: String,
+}
+
+/// a portable set of note templates, such as a supervisor's vetted templates for the whole team
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLibrary {
+  pub version: u32,
+  pub templates: Vec<LibraryTemplate>,
+}
+
+impl TemplateLibrary {
+  pub fn new(templates: &[&NoteTemplate]) -> TemplateLibrary {
+    TemplateLibrary {
+      version: TEMPLATE_LIBRARY_VERSION,
+      templates: templates.iter().map(|nt| LibraryTemplate { structure: nt.structure, content: nt.content.clone() } ).collect(),
+    }
+  }
+  pub fn from_json(s: &str) -> Result<TemplateLibrary, String> {
+    let library: TemplateLibrary = serde_json::from_str(s).map_err(|e| format!("Not a template library file: {}", e) )?;
+    if library.version > TEMPLATE_LIBRARY_VERSION {
+      return Err(format!(
+        "The template library was written by a newer version of the program (format {}, this version reads {}).",
+        library.version,
+        TEMPLATE_LIBRARY_VERSION,
+      ));
+    }
+    Ok(library)
+  }
+  pub fn load(filepath: &str) -> Result<TemplateLibrary, String> {
+    let s = fs::read_to_string(filepath).map_err(|e| format!("Failed to read {}: {}", filepath, e) )?;
+    Self::from_json(&s)
+  }
+  pub fn save(&self, filepath: &Path) -> Result<(), String> {
+    let s = serde_json::to_string_pretty(self).map_err(|e| e.to_string() )?;
+    if let Some(dir) = filepath.parent().filter(|dir| !dir.as_os_str().is_empty() ) {
+      fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e) )?;
+    }
+    write_file_atomically(filepath, s.as_bytes()).map_err(|e| format!("Failed to write {}: {}", filepath.display(), e) )
+  }
+}
+
+/// one sentence of a comparison between an existing template and an imported one
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+  Same(String),
+  Removed(String),
+  Added(String),
+}
+
+impl DiffLine {
+  pub fn to_marked_string(&self) -> String {
+    match self {
+      DiffLine::Same(s) => format!("  {}", s),
+      DiffLine::Removed(s) => format!("- {}", s),
+      DiffLine::Added(s) => format!("+ {}", s),
+    }
+  }
+}
+
+fn sentences(content: &str) -> Vec<&str> {
+  content.split(". ").map(|s| s.trim() ).filter(|s| !s.is_empty() ).collect()
+}
+
+/// the sentences removed from and added to `old` to make `new`, in order
+pub fn sentence_diff(old: &str, new: &str) -> Vec<DiffLine> {
+  let (old, new) = (sentences(old), sentences(new));
+  // lengths of the longest common subsequences of the remaining sentences
+  let mut lcs = vec![vec![0; new.len()+1]; old.len()+1];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i][j] = if old[i] == new[j] { lcs[i+1][j+1] + 1 } else { lcs[i+1][j].max(lcs[i][j+1]) };
+    }
+  }
+  let (mut i, mut j) = (0, 0);
+  let mut lines: Vec<DiffLine> = vec![];
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      lines.push(DiffLine::Same(old[i].to_string()));
+      i += 1;
+      j += 1;
+    } else if i < old.len() && (j == new.len() || lcs[i+1][j] >= lcs[i][j+1]) {
+      lines.push(DiffLine::Removed(old[i].to_string()));
+      i += 1;
+    } else {
+      lines.push(DiffLine::Added(new[j].to_string()));
+      j += 1;
+    }
+  }
+  lines
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportStatus {
+  /// nothing like it is saved for the user
+  New,
+  /// a different version of a saved template of the same structure, which is kept alongside it
+  Revision(u32),
+  /// the user already has this exact template
+  Duplicate(u32),
+  /// the template has errors and can't be saved
+  Invalid(Vec<TemplateIssue>),
+}
+
+/// what importing one template would do, shown before anything is saved
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportEntry {
+  pub template: LibraryTemplate,
+  pub status: ImportStatus,
+  /// changes from the saved template for a revision, otherwise empty
+  pub diff: Vec<DiffLine>,
+}
+
+impl ImportEntry {
+  pub fn will_import(&self) -> bool {
+    matches!(self.status, ImportStatus::New | ImportStatus::Revision(_))
+  }
+  pub fn description(&self) -> String {
+    match &self.status {
+      ImportStatus::New => format!("New {} template", self.template.structure),
+      ImportStatus::Revision(id) => format!("Revision of {} template {}", self.template.structure, id),
+      ImportStatus::Duplicate(id) => format!("Skipped: same as {} template {}", self.template.structure, id),
+      ImportStatus::Invalid(issues) => {
+        let errors = issues.iter().filter(|i| i.is_error() ).count();
+        format!("Skipped: {} template has {} error{}", self.template.structure, errors, if errors == 1 { "" } else { "s" })
+      },
+    }
+  }
+  /// the description followed by the diff for a revision or the content for a new template
+  pub fn preview(&self) -> String {
+    let mut lines = vec![self.description()];
+    match &self.status {
+      ImportStatus::Revision(_) => lines.extend(self.diff.iter().map(|l| format!("    {}", l.to_marked_string()) )),
+      ImportStatus::New => lines.extend(sentences(&self.template.content).iter().map(|s| format!("    + {}", s) )),
+      ImportStatus::Invalid(issues) => lines.extend(issues.iter().filter(|i| i.is_error() ).map(|i| format!("    {}", i) )),
+      ImportStatus::Duplicate(_) => (),
+    }
+    lines.join("\n")
+  }
+}
+
+impl NoteArchive {
+  pub fn user_note_templates(&self, user_id: u32) -> Vec<&NoteTemplate> {
+    self.note_templates.iter()
+      .filter(|nt| nt.foreign_keys.get("user_ids").is_some_and(|ids| ids.contains(&user_id) ) )
+      .collect()
+  }
+  /// compares each template in the library with the user's saved templates; exact copies and templates with
+  /// errors are skipped, and a template sharing sentences with a saved one is shown as a diff against it
+  pub fn plan_template_import(&self, library: &TemplateLibrary, user_id: u32) -> Vec<ImportEntry> {
+    let saved = self.user_note_templates(user_id);
+    library.templates.iter().map(|lt| {
+      let issues = lint_template(&lt.content);
+      let same_structure: Vec<&&NoteTemplate> = saved.iter().filter(|nt| nt.structure == lt.structure ).collect();
+      let (status, diff) = if let Some(nt) = same_structure.iter().find(|nt| nt.content == lt.content ) {
+        (ImportStatus::Duplicate(nt.id), vec![])
+      } else if issues.iter().any(|i| i.is_error() ) {
+        (ImportStatus::Invalid(issues), vec![])
+      } else {
+        let closest = same_structure.iter()
+          .map(|nt| (nt, sentence_diff(&nt.content, &lt.content)) )
+          .map(|(nt, diff)| (diff.iter().filter(|l| matches!(l, DiffLine::Same(_)) ).count(), nt.id, diff) )
+          .filter(|(shared, _, _)| *shared > 0 )
+          .max_by_key(|(shared, id, _)| (*shared, std::cmp::Reverse(*id)) );
+        match closest {
+          Some((_, id, diff)) => (ImportStatus::Revision(id), diff),
+          None => (ImportStatus::New, vec![]),
+        }
+      };
+      ImportEntry { template: lt.clone(), status, diff }
+    }).collect()
+  }
+  /// saves the new templates and revisions in the plan for the user, returning their IDs
+  pub fn import_templates(&mut self, plan: &[ImportEntry], user_id: u32) -> Vec<u32> {
+    let mut ids: Vec<u32> = vec![];
+    for entry in plan.iter().filter(|e| e.will_import() ) {
+      let id = self.next_id("note_templates");
+      let nt = NoteTemplate::new(id, entry.template.structure, true, entry.template.content.clone(), vec![user_id]);
+      if self.save_note_template(nt).is_err() {
+        continue;
+      }
+      // a library can hold the same template twice, so keep only the first copy saved for the user
+      let saved_for_user = self.note_template_dup_already_saved(id).is_some_and(|dup_ids| dup_ids.iter().any(|dup_id|
+        self.user_note_templates(user_id).iter().any(|nt| nt.id == *dup_id )
+      ));
+      if saved_for_user {
+        self.note_templates.retain(|nt| nt.id != id );
+        self.write_to_files();
+      } else {
+        ids.push(id);
+      }
+    }
+    ids
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn previews_and_imports_a_library() {
+    let old = "Met with (---g---). Reviewed the plan. Scheduled a follow up.";
+    let new = "Met with (---g---). Reviewed the safety plan. Scheduled a follow up.";
+    assert_eq!(sentence_diff(old, new), vec![
+      DiffLine::Same(String::from("Met with (---g---)")),
+      DiffLine::Removed(String::from("Reviewed the plan")),
+      DiffLine::Added(String::from("Reviewed the safety plan")),
+      DiffLine::Same(String::from("Scheduled a follow up.")),
+    ]);
+
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_template_library_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    a.note_templates.clear();
+    a.note_templates.push(NoteTemplate::new(1, StructureType::HomeVisit, true, String::from(old), vec![1]));
+    a.note_templates.push(NoteTemplate::new(2, StructureType::Intake, true, String::from("Completed intake with (---c---)."), vec![1]));
+
+    let exported = TemplateLibrary::new(&a.user_note_templates(1));
+    let library = TemplateLibrary::from_json(&serde_json::to_string(&TemplateLibrary {
+      version: TEMPLATE_LIBRARY_VERSION,
+      templates: vec![
+        exported.templates[1].clone(),
+        LibraryTemplate { structure: StructureType::HomeVisit, content: String::from(new) },
+        LibraryTemplate { structure: StructureType::Sncd, content: String::from("Discussed (---yt---).") },
+        LibraryTemplate { structure: StructureType::Sncd, content: String::from("Discussed (---yt---).") },
+        LibraryTemplate { structure: StructureType::Sncd, content: String::from("Met with (---gg---).") },
+      ],
+    }).unwrap()).unwrap();
+
+    let plan = a.plan_template_import(&library, 1);
+    assert_eq!(plan[0].status, ImportStatus::Duplicate(2));
+    assert_eq!(plan[1].status, ImportStatus::Revision(1));
+    assert!(plan[1].preview().contains("    + Reviewed the safety plan"));
+    assert_eq!(plan[2].status, ImportStatus::New);
+    assert_eq!(plan[3].status, ImportStatus::New);
+    assert!(matches!(plan[4].status, ImportStatus::Invalid(_)));
+    assert_eq!(plan[4].description(), "Skipped: SNCD template has 1 error");
+
+    let new_ids = a.import_templates(&plan, 1);
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    assert_eq!(new_ids.len(), 2);
+    assert_eq!(a.user_note_templates(1).len(), 4);
+    assert!(a.plan_template_import(&library, 1).iter().all(|e| !e.will_import() ));
+    assert!(TemplateLibrary::from_json("{\"version\": 99, \"templates\": []}").is_err());
+  }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use crate::note::*;
+use crate::note::Blank::*;
+use crate::template_syntax::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+  /// the template can't be saved until it is fixed
+  Error,
+  /// the template works but will read badly in notes
+  Warning,
+}
+
+/// a problem found in a template's content; positions are the sentence ID shown on the template display
+/// and the 1-based column within that sentence
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateIssue {
+  pub severity: IssueSeverity,
+  pub position: Option<(usize, usize)>,
+  pub message: String,
+}
+
+impl TemplateIssue {
+  fn at(severity: IssueSeverity, content: &str, offset: usize, message: String) -> TemplateIssue {
+    TemplateIssue { severity, position: Some(sentence_position(content, offset)), message }
+  }
+  pub fn is_error(&self) -> bool {
+    self.severity == IssueSeverity::Error
+  }
+}
+
+impl fmt::Display for TemplateIssue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let severity = match self.severity {
+      IssueSeverity::Error => "Error",
+      IssueSeverity::Warning => "Warning",
+    };
+    match self.position {
+      Some((sentence, column)) => write!(f, "{} at sentence {}, column {}: {}", severity, sentence, column, self.message),
+      None => write!(f, "{}: {}", severity, self.message),
+    }
+  }
+}
+
+/// the sentence ID and column of a byte offset, counting sentences the way the template display splits them
+pub fn sentence_position(content: &str, offset: usize) -> (usize, usize) {
+  let before = &content[..offset];
+  let sentence_start = before.rfind(". ").map(|i| i + 2 ).unwrap_or(0);
+  (before.matches(". ").count() + 1, before[sentence_start..].chars().count() + 1)
+}
+
+fn pronoun_target(b: &Option<Blank>) -> Option<usize> {
+  match b {
+    Some(Pronoun1ForBlank(Some(id))) | Some(Pronoun2ForBlank(Some(id)))
+      | Some(Pronoun3ForBlank(Some(id))) | Some(Pronoun4ForBlank(Some(id))) => Some(*id as usize),
+    _ => None,
+  }
+}
+
+/// every problem in a template's content: unknown or unclosed blank markers, pronoun blanks pointing at
+/// a missing blank, one without pronouns or one in an '{if}' section the pronouns are outside of,
+/// invalid directives, empty sentences and double spaces
+pub fn lint_template(content: &str) -> Vec<TemplateIssue> {
+  use IssueSeverity::*;
+  let mut issues: Vec<TemplateIssue> = vec![];
+  // every closed marker, with its blank if the abbreviation is known
+  let mut markers: Vec<(usize, usize, Option<Blank>)> = vec![];
+  let mut search_from = 0;
+  while let Some(i) = content[search_from..].find("(---").map(|i| i + search_from ) {
+    let close = content[i+4..].find("---)").map(|j| i + 4 + j + 4 );
+    let next_open = content[i+4..].find("(---").map(|j| i + 4 + j );
+    match close {
+      Some(end) if next_open.is_none_or(|open| open > end ) => {
+        let marker = &content[i..end];
+        let blank = Blank::try_from_str(marker).ok();
+        if blank.is_none() {
+          issues.push(TemplateIssue::at(Error, content, i, format!("Unknown blank '{}'.", marker)));
+        }
+        markers.push((i, end, blank));
+        search_from = end;
+      },
+      _ => {
+        issues.push(TemplateIssue::at(Error, content, i, String::from("Blank marker '(---' is never closed with '---)'.")));
+        search_from = i + 4;
+      },
+    }
+  }
+  for (i, _) in content.match_indices("---)") {
+    if !markers.iter().any(|(start, end, _)| *start < i && i < *end ) {
+      issues.push(TemplateIssue::at(Error, content, i, String::from("'---)' closes a blank marker that was never opened.")));
+    }
+  }
+  for (n, (i, _, b)) in markers.iter().enumerate() {
+    let target = match pronoun_target(b) {
+      Some(target) => target,
+      None => continue,
+    };
+    let message = if target == 0 || target > markers.len() {
+      format!("Pronouns refer to blank #{}, but the template has {} blanks.", target, markers.len())
+    } else if target == n + 1 {
+      format!("Pronouns in blank #{} refer to themselves.", target)
+    } else {
+      match markers[target-1].2 {
+        Some(referenced) if !referenced.has_pronouns() => {
+          format!("Pronouns refer to blank #{} ({}), which has no pronouns.", target, referenced.display_to_user_empty())
+        },
+        _ => continue,
+      }
+    };
+    issues.push(TemplateIssue::at(Error, content, *i, message));
+  }
+  if issues.is_empty() {
+    match parse_template(content) {
+      Err(e) => issues.push(TemplateIssue { severity: Error, position: None, message: e }),
+      // a note leaves out the sections that don't apply to its client, so pronouns can only
+      // refer to a blank that is kept whenever they are
+      Ok(pieces) => {
+        let sections = blank_sections(&pieces);
+        for (n, (i, _, b)) in markers.iter().enumerate() {
+          let target = match pronoun_target(b) {
+            Some(target) => target,
+            None => continue,
+          };
+          // markers written inside a directive aren't blanks, so they have no section
+          let left_out = match (sections.get(n), sections.get(target-1)) {
+            (Some(pronoun_section), Some(target_section)) => !pronoun_section.starts_with(target_section),
+            _ => false,
+          };
+          if left_out {
+            issues.push(TemplateIssue::at(Error, content, *i, format!(
+              "Pronouns refer to blank #{}, which is in an '{{if}}' section that can be left out when they are not.",
+              target,
+            )));
+          }
+        }
+      },
+    }
+  }
+  let mut offset = 0;
+  let sentences: Vec<&str> = content.split(". ").collect();
+  for (n, sentence) in sentences.iter().enumerate() {
+    let is_last = n == sentences.len() - 1;
+    if (sentence.trim().is_empty() && !is_last) || sentence.trim() == "." {
+      issues.push(TemplateIssue::at(Warning, content, offset, String::from("Empty sentence.")));
+    }
+    offset += sentence.len() + 2;
+  }
+  for (i, _) in content.match_indices("  ") {
+    if i == 0 || &content[i-1..i] != " " {
+      issues.push(TemplateIssue::at(Warning, content, i, String::from("Double space.")));
+    }
+  }
+  issues.sort_by_key(|issue| (issue.position, issue.severity) );
+  issues
+}
+
+impl NoteTemplate {
+  pub fn lint(&self) -> Vec<TemplateIssue> {
+    lint_template(&self.content)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reports_template_problems_with_positions() {
+    assert!(lint_template("(---u---) met with (---g---). (---pb1@2@---) signed the (---id---).").is_empty());
+
+    let issues = lint_template("(---u---) met with (---gg---). (---pb1@4@---) signed (---id---. Then  left. . Done.");
+    let described: Vec<String> = issues.iter().map(|issue| issue.to_string() ).collect();
+    assert_eq!(described, vec![
+      "Error at sentence 1, column 20: Unknown blank '(---gg---)'.",
+      "Error at sentence 2, column 1: Pronouns refer to blank #4, but the template has 3 blanks.",
+      "Error at sentence 2, column 23: Blank marker '(---' is never closed with '---)'.",
+      "Warning at sentence 3, column 5: Double space.",
+      "Warning at sentence 4, column 1: Empty sentence.",
+    ]);
+    assert!(issues.iter().any(|issue| issue.is_error() ));
+
+    let issues = lint_template("(---id---) was filed. (---pb2@1@---) was read. Met ---) later.");
+    assert_eq!(issues[0].message, "Pronouns refer to blank #1 (Internal document), which has no pronouns.");
+    assert_eq!(issues[1].message, "'---)' closes a blank marker that was never opened.");
+    assert_eq!(lint_template("(---g---) met {if:p}with (---p---).")[0].position, None);
+
+    assert!(lint_template("{if:p}(---p---) came. {end}(---g---) met. (---pb1@2@---) signed.").is_empty());
+    assert!(lint_template("(---g---){as:x} {pb1:x} met (---c---). (---pb2@2@---) agreed.").is_empty());
+    assert!(lint_template("(---g---){as:x} {verb:x:(---c---)|(---c---)} met. (---pb2@2@---) agreed.").iter().all(|issue| !issue.message.contains("section") ));
+    assert!(lint_template("{if:p}(---p---) came and (---pb1@1@---) stayed. {end}Done.").is_empty());
+    assert_eq!(
+      lint_template("{if:p}(---p---) came. {end}(---pb1@1@---) signed.")[0].to_string(),
+      "Error at sentence 2, column 6: Pronouns refer to blank #1, which is in an '{if}' section that can be left out when they are not.",
+    );
+  }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+
+use crate::note::*;
+use crate::note::Blank::*;
+
+/// one part of a template's content; directives are written between '{' and '}' around the usual blank markers
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePiece {
+  Text(String),
+  /// '{as:parent}' names the blank just before it so other directives can refer to it
+  Name(String),
+  /// '{pb1:parent}' to '{pb4:parent}', the subject, object, possessive determiner or possessive pronoun
+  /// of whoever fills the named blank
+  Pronoun(u32, String),
+  /// '{verb:parent:signs|sign}', the singular form when one person fills the named blank, otherwise the plural
+  Verb { name: String, singular: String, plural: String },
+  /// '{if:p} ... {else} ... {end}', kept only when the client has someone to fill that blank; '{if:!p}' for the opposite
+  If { condition: TemplateCondition, then: Vec<TemplatePiece>, otherwise: Vec<TemplatePiece> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemplateCondition {
+  pub blank: Blank,
+  pub negated: bool,
+}
+
+/// how many people the current client has for each blank that is filled automatically when a note is written
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TemplateContext {
+  pub partners: usize,
+  pub primary_contacts: usize,
+  pub guardians: usize,
+  pub care_plan_team: usize,
+  pub collaterals: usize,
+}
+
+impl TemplateContext {
+  /// None for blanks filled by the user, whose number of people isn't known until the note is written
+  pub fn count(&self, b: Blank) -> Option<usize> {
+    match b {
+      CurrentUser | CurrentClientName => Some(1),
+      PartnerICCOrFP => Some(self.partners),
+      PrimaryContact => Some(self.primary_contacts),
+      Guardian => Some(self.guardians),
+      CarePlanTeam => Some(self.care_plan_team),
+      AllCollaterals => Some(self.collaterals),
+      _ => None,
+    }
+  }
+}
+
+/// the blanks a condition or verb can depend on, since they are filled before the user sees the note
+fn counted_blank(b: Blank) -> bool {
+  TemplateContext::default().count(b).is_some()
+}
+
+lazy_static! {
+  static ref RE_BLANK: Regex = Regex::new("[(]---[a-zA-Z0-9_]*@?[0-9]*@?---[)]").unwrap();
+  static ref RE_NUMBERED_PRONOUN: Regex = Regex::new("[(]---(pb[1-4])@([0-9]+)@---[)]").unwrap();
+}
+
+fn valid_name(name: &str) -> bool {
+  !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' )
+}
+
+fn last_blank(text: &str) -> Option<Blank> {
+  RE_BLANK.find_iter(text).last()
+    .filter(|m| m.end() == text.len() )
+    .and_then(|m| Blank::try_from_str(m.as_str()).ok() )
+}
+
+fn parse_directive(directive: &str) -> Result<TemplatePiece, String> {
+  let parts: Vec<&str> = directive.splitn(3, ':').collect();
+  match &parts[..] {
+    ["as", name] if valid_name(name) => Ok(TemplatePiece::Name(name.to_string())),
+    [pronoun, name] if valid_name(name) && pronoun.len() == 3 && pronoun.starts_with("pb") => match &pronoun[2..] {
+      "1" | "2" | "3" | "4" => Ok(TemplatePiece::Pronoun(pronoun[2..].parse().unwrap(), name.to_string())),
+      _ => Err(format!("'{{{}}}' is not a pronoun; use pb1, pb2, pb3 or pb4.", directive)),
+    },
+    ["verb", name, forms] if valid_name(name) => match forms.split_once('|') {
+      Some((singular, plural)) if !forms[singular.len()+1..].contains('|') => Ok(TemplatePiece::Verb {
+        name: name.to_string(),
+        singular: singular.to_string(),
+        plural: plural.to_string(),
+      }),
+      _ => Err(format!("'{{{}}}' needs a singular and a plural form, such as {{verb:{}:signs|sign}}.", directive, name)),
+    },
+    ["if", condition] => {
+      let (negated, abbreviation) = match condition.strip_prefix('!') {
+        Some(a) => (true, a),
+        None => (false, *condition),
+      };
+      match Blank::try_from_str(&format!("(---{}---)", abbreviation)) {
+        Ok(blank) if counted_blank(blank) => Ok(TemplatePiece::If { condition: TemplateCondition { blank, negated }, then: vec![], otherwise: vec![] }),
+        _ => Err(format!("'{{{}}}' can only depend on u, p, c, allco, pc, g or cpt.", directive)),
+      }
+    },
+    _ => Err(format!("Unknown template directive '{{{}}}'.", directive)),
+  }
+}
+
+/// an '{if}' still being read, with the pieces before '{else}' and whether '{else}' has been reached
+type OpenIf = (TemplateCondition, Vec<TemplatePiece>, bool);
+
+/// reads the directives in a template's content, checking that every '{if}' is closed
+/// and every name is given once to a blank and refers to the right kind of blank
+pub fn parse_template(content: &str) -> Result<Vec<TemplatePiece>, String> {
+  let mut stack: Vec<(Vec<TemplatePiece>, Option<OpenIf>)> = vec![(vec![], None)];
+  let mut names: HashMap<String, Blank> = HashMap::new();
+  let mut references: Vec<TemplatePiece> = vec![];
+  let mut rest = content;
+  while !rest.is_empty() {
+    let (text, directive) = match rest.find(['{', '}']) {
+      None => (rest, None),
+      Some(i) if &rest[i..i+1] == "}" => return Err(format!("'}}' without an opening '{{' after '{}'.", &rest[..i])),
+      Some(i) => match rest[i+1..].find(['{', '}']) {
+        Some(j) if &rest[i+1+j..i+2+j] == "}" => (&rest[..i], Some((&rest[i+1..i+1+j], i+2+j))),
+        _ => return Err(format!("'{{' without a closing '}}' in '{}'.", &rest[i..])),
+      },
+    };
+    let pieces = &mut stack.last_mut().unwrap().0;
+    if !text.is_empty() {
+      pieces.push(TemplatePiece::Text(text.to_string()));
+    }
+    let (directive, end) = match directive {
+      Some(d) => d,
+      None => break,
+    };
+    rest = &rest[end..];
+    match directive.trim() {
+      "else" => match stack.last_mut().unwrap() {
+        (pieces, Some((_, then, in_else))) if !*in_else => {
+          *in_else = true;
+          *then = std::mem::take(pieces);
+        },
+        _ => return Err(String::from("'{else}' without an open '{if}'.")),
+      },
+      "end" => match stack.pop() {
+        Some((pieces, Some((condition, then, in_else)))) => {
+          let piece = if in_else {
+            TemplatePiece::If { condition, then, otherwise: pieces }
+          } else {
+            TemplatePiece::If { condition, then: pieces, otherwise: vec![] }
+          };
+          stack.last_mut().unwrap().0.push(piece);
+        },
+        _ => return Err(String::from("'{end}' without an open '{if}'.")),
+      },
+      d => match parse_directive(d)? {
+        TemplatePiece::If { condition, .. } => stack.push((vec![], Some((condition, vec![], false)))),
+        TemplatePiece::Name(name) => {
+          let blank = match pieces.last() {
+            Some(TemplatePiece::Text(t)) => last_blank(t),
+            _ => None,
+          };
+          match blank {
+            None => return Err(format!("'{{as:{}}}' must come right after a blank.", name)),
+            Some(_) if names.contains_key(&name) => return Err(format!("The name '{}' is given to more than one blank.", name)),
+            Some(b) => {
+              names.insert(name.clone(), b);
+              pieces.push(TemplatePiece::Name(name));
+            },
+          }
+        },
+        piece => {
+          references.push(piece.clone());
+          pieces.push(piece);
+        },
+      },
+    }
+  }
+  if stack.len() > 1 {
+    return Err(String::from("'{if}' without a closing '{end}'."));
+  }
+  for reference in references {
+    match reference {
+      TemplatePiece::Pronoun(_, name) => match names.get(&name) {
+        None => return Err(format!("No blank is named '{}'.", name)),
+        Some(b) if !b.has_pronouns() => return Err(format!("The blank named '{}' ({}) has no pronouns.", name, b.display_to_user_empty())),
+        _ => (),
+      },
+      TemplatePiece::Verb { name, .. } => match names.get(&name) {
+        None => return Err(format!("No blank is named '{}'.", name)),
+        Some(b) if !counted_blank(*b) => return Err(format!(
+          "The verb for '{}' can't agree with {}, which is chosen while writing the note; name a u, p, c, allco, pc, g or cpt blank.",
+          name,
+          b.display_to_user_empty(),
+        )),
+        _ => (),
+      },
+      _ => (),
+    }
+  }
+  Ok(stack.pop().unwrap().0)
+}
+
+fn raw_blank_count(pieces: &[TemplatePiece]) -> usize {
+  pieces.iter().map(|piece| match piece {
+    TemplatePiece::Text(t) => RE_BLANK.find_iter(t).count(),
+    TemplatePiece::If { then, otherwise, .. } => raw_blank_count(then) + raw_blank_count(otherwise),
+    _ => 0,
+  }).sum()
+}
+
+/// the '{if}' and '{else}' branches around each blank marker in the content, outermost first,
+/// with every branch numbered in the order it appears
+pub fn blank_sections(pieces: &[TemplatePiece]) -> Vec<Vec<usize>> {
+  fn walk(pieces: &[TemplatePiece], path: &mut Vec<usize>, next_branch: &mut usize, output: &mut Vec<Vec<usize>>) {
+    for piece in pieces {
+      match piece {
+        TemplatePiece::Text(t) => output.extend(RE_BLANK.find_iter(t).map(|_| path.clone() )),
+        TemplatePiece::If { then, otherwise, .. } => for branch in [then, otherwise] {
+          path.push(*next_branch);
+          *next_branch += 1;
+          walk(branch, path, next_branch, output);
+          path.pop();
+        },
+        _ => (),
+      }
+    }
+  }
+  let mut output: Vec<Vec<usize>> = vec![];
+  walk(pieces, &mut vec![], &mut 0, &mut output);
+  output
+}
+
+/// keeps the sections that apply to the client, pairing each piece with the number of blank markers
+/// before it in the content, counting those in sections left out
+fn flatten(pieces: Vec<TemplatePiece>, context: &TemplateContext, raw_blanks: &mut usize, output: &mut Vec<(TemplatePiece, usize)>) {
+  for piece in pieces {
+    match piece {
+      TemplatePiece::If { condition, then, otherwise } => {
+        let present = context.count(condition.blank).unwrap_or(0) > 0;
+        if present != condition.negated {
+          flatten(then, context, raw_blanks, output);
+          *raw_blanks += raw_blank_count(&otherwise);
+        } else {
+          *raw_blanks += raw_blank_count(&then);
+          flatten(otherwise, context, raw_blanks, output);
+        }
+      },
+      TemplatePiece::Text(t) => {
+        let before = *raw_blanks;
+        *raw_blanks += RE_BLANK.find_iter(&t).count();
+        output.push((TemplatePiece::Text(t), before));
+      },
+      p => output.push((p, *raw_blanks)),
+    }
+  }
+}
+
+/// the content of a note written from the template: sections are kept or left out for the current client,
+/// verbs agree with the number of people in their blank, named pronouns become '(---pb1@N@---)' blanks
+/// and existing '(---pb1@N@---)' blanks are renumbered to point at the same blank in the note
+pub fn render_template(content: &str, context: &TemplateContext) -> Result<String, String> {
+  let mut pieces: Vec<(TemplatePiece, usize)> = vec![];
+  let mut raw_blanks = 0;
+  flatten(parse_template(content)?, context, &mut raw_blanks, &mut pieces);
+  let mut positions: HashMap<String, (usize, Blank)> = HashMap::new();
+  // the position in the note of each blank kept from the content, by its position in the content
+  let mut renumbered: HashMap<usize, usize> = HashMap::new();
+  let mut num_blanks = 0;
+  for (i, (piece, raw_before)) in pieces.iter().enumerate() {
+    match piece {
+      TemplatePiece::Text(t) => {
+        let count = RE_BLANK.find_iter(t).count();
+        for k in 1..=count {
+          renumbered.insert(raw_before + k, num_blanks + k);
+        }
+        num_blanks += count;
+      },
+      TemplatePiece::Pronoun(_, _) => num_blanks += 1,
+      TemplatePiece::Name(name) => if let Some((TemplatePiece::Text(t), _)) = pieces[..i].last() {
+        if let Some(b) = last_blank(t) {
+          positions.insert(name.clone(), (num_blanks, b));
+        }
+      },
+      _ => (),
+    }
+  }
+  let mut output = String::new();
+  for (piece, _) in &pieces {
+    match piece {
+      TemplatePiece::Text(t) => {
+        let mut copied_to = 0;
+        for caps in RE_NUMBERED_PRONOUN.captures_iter(t) {
+          let marker = caps.get(0).unwrap();
+          output.push_str(&t[copied_to..marker.start()]);
+          let target: usize = caps[2].parse().unwrap_or(0);
+          match renumbered.get(&target) {
+            Some(position) => output.push_str(&format!("(---{}@{}@---)", &caps[1], position)),
+            None if target >= 1 && target <= raw_blanks => return Err(format!(
+              "The pronouns in '{}' refer to a blank in a section left out of this note.",
+              marker.as_str(),
+            )),
+            None => output.push_str(marker.as_str()),
+          }
+          copied_to = marker.end();
+        }
+        output.push_str(&t[copied_to..]);
+      },
+      TemplatePiece::Name(_) => (),
+      TemplatePiece::Pronoun(n, name) => match positions.get(name) {
+        Some((position, _)) => output.push_str(&format!("(---pb{}@{}@---)", n, position)),
+        None => return Err(format!("The blank named '{}' is in a section left out of this note.", name)),
+      },
+      TemplatePiece::Verb { name, singular, plural } => match positions.get(name) {
+        Some((_, b)) => output.push_str(if context.count(*b) == Some(1) { singular } else { plural }),
+        None => return Err(format!("The blank named '{}' is in a section left out of this note.", name)),
+      },
+      TemplatePiece::If { .. } => (),
+    }
+  }
+  Ok(output)
+}
+
+impl NoteTemplate {
+  pub fn parse_syntax(&self) -> Result<Vec<TemplatePiece>, String> {
+    parse_template(&self.content)
+  }
+  pub fn render(&self, context: &TemplateContext) -> Result<String, String> {
+    render_template(&self.content, context)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn renders_conditionals_agreement_and_named_pronouns() {
+    let content = "\
+      (---u---) met with (---g---){as:parent}. \
+      {if:p}(---p---) joined the meeting. {end}\
+      (---g---){as:guardians} {verb:guardians:signs|sign} the (---id---) and {pb1:parent} {verb:parent:takes|take} a copy.\
+    ";
+    let one = TemplateContext { guardians: 1, ..TemplateContext::default() };
+    assert_eq!(render_template(content, &one).unwrap(), "\
+      (---u---) met with (---g---). \
+      (---g---) signs the (---id---) and (---pb1@2@---) takes a copy.\
+    ");
+    let two = TemplateContext { guardians: 2, partners: 1, ..TemplateContext::default() };
+    assert_eq!(render_template(content, &two).unwrap(), "\
+      (---u---) met with (---g---). \
+      (---p---) joined the meeting. \
+      (---g---) sign the (---id---) and (---pb1@2@---) take a copy.\
+    ");
+    assert_eq!(render_template("{if:!pc}No primary contact.{else}(---pc---) called.{end}", &one).unwrap(), "No primary contact.");
+    assert_eq!(render_template("Met with (---c---).", &one).unwrap(), "Met with (---c---).");
+
+    assert_eq!(
+      render_template("{if:p}(---p---) came. {end}(---g---) met. (---pb1@2@---) signed.", &one).unwrap(),
+      "(---g---) met. (---pb1@1@---) signed.",
+    );
+    assert_eq!(
+      render_template("{if:p}(---p---) came. {end}(---g---) met. (---pb1@2@---) signed.", &two).unwrap(),
+      "(---p---) came. (---g---) met. (---pb1@2@---) signed.",
+    );
+    assert_eq!(
+      render_template("(---g---){as:x} {pb1:x} met (---c---). (---pb2@2@---) agreed.", &one).unwrap(),
+      "(---g---) (---pb1@1@---) met (---c---). (---pb2@3@---) agreed.",
+    );
+    assert!(render_template("{if:p}(---p---) came. {end}(---pb1@1@---) signed.", &one).is_err());
+    assert_eq!(
+      blank_sections(&parse_template("(---u---) {if:p}(---p---){else}(---g---) {if:pc}(---pc---){end}{end}").unwrap()),
+      vec![vec![], vec![0], vec![1], vec![1, 2]],
+    );
+
+    assert!(parse_template("{if:p}(---p---) came.").is_err());
+    assert!(parse_template("(---g---) came.{end}").is_err());
+    assert!(parse_template("(---g---) came {as:parent}.").is_err());
+    assert!(parse_template("(---co---){as:them} {verb:them:is|are} here.").is_err());
+    assert!(parse_template("(---id---){as:form} {pb1:form} signed.").is_err());
+    assert!(parse_template("{pb5:parent}").is_err());
+    assert!(parse_template("{if:id}{end}").is_err());
+    assert!(parse_template("Met {unclosed").is_err());
+    assert!(parse_template("(---g---){as:a} and (---pc---){as:a}").is_err());
+  }
+}
@@ -0,0 +1,325 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Serialize, Deserialize};
+
+use crate::billing::csv_field;
+use crate::note::*;
+use crate::note_archive::*;
+use crate::pdf::month_bounds;
+
+/// driving done for a contact, such as transporting a client or going to a home visit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Travel {
+  pub start_location: Option<String>,
+  pub end_location: Option<String>,
+  pub miles: f64,
+  pub minutes: u32,
+}
+
+impl Travel {
+  /// such as 'Office to 12 Elm St, 8.4 miles, 20 minutes'
+  pub fn fmt_travel(&self) -> String {
+    let route = match (&self.start_location, &self.end_location) {
+      (Some(from), Some(to)) => format!("{} to {}, ", from, to),
+      (Some(from), None) => format!("from {}, ", from),
+      (None, Some(to)) => format!("to {}, ", to),
+      (None, None) => String::new(),
+    };
+    format!("{}{:.1} miles, {} minutes", route, self.miles, self.minutes)
+  }
+}
+
+pub fn parse_miles(s: &str) -> Result<f64, String> {
+  match s.trim().parse::<f64>() {
+    Ok(miles) if miles >= 0.0 && miles.is_finite() => Ok(miles),
+    _ => Err(format!("Invalid number of miles: {}", s.trim())),
+  }
+}
+
+/// how pay periods are counted: the 1st to 15th and 16th to the end of each month,
+/// or a fixed number of days starting from a known first day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PayPeriod {
+  SemiMonthly,
+  FixedDays(NaiveDate, u32),
+}
+
+impl PayPeriod {
+  /// the first and last day of the pay period that includes `date`
+  pub fn containing(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match self {
+      PayPeriod::SemiMonthly => {
+        let (first, last) = month_bounds(date.year(), date.month()).unwrap();
+        if date.day() <= 15 {
+          (first, first + Duration::days(14))
+        } else {
+          (first + Duration::days(15), last)
+        }
+      },
+      PayPeriod::FixedDays(start, days) => {
+        let days = (*days).max(1) as i64;
+        let offset = (date - *start).num_days().div_euclid(days);
+        let first = *start + Duration::days(offset * days);
+        (first, first + Duration::days(days - 1))
+      },
+    }
+  }
+}
+
+/// two notes by the same user on the same day, for different clients, whose times overlap
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeOverlap {
+  pub date: NaiveDate,
+  pub note_ids: (u32, u32),
+  pub client_names: (String, String),
+}
+
+impl TimeOverlap {
+  pub fn description(&self) -> String {
+    format!(
+      "{}: note {} for {} overlaps note {} for {}.",
+      self.date.format("%Y-%m-%d"),
+      self.note_ids.0,
+      self.client_names.0,
+      self.note_ids.1,
+      self.client_names.1,
+    )
+  }
+}
+
+/// the contact's start and end on the note's date, so a late contact can run past midnight into the next day
+fn time_range(date: NaiveDate, start: NaiveTime, minutes: u32) -> (NaiveDateTime, NaiveDateTime) {
+  let start = date.and_time(start);
+  (start, start + Duration::minutes(minutes as i64))
+}
+
+fn ranges_overlap(x: (NaiveDateTime, NaiveDateTime), y: (NaiveDateTime, NaiveDateTime)) -> bool {
+  x.0 < y.1 && y.0 < x.1
+}
+
+/// the first note that would overlap `n` if it started at `start` and took `minutes`
+pub fn find_overlapping_note<'a>(a: &'a NoteArchive, n: &Note, start: NaiveTime, minutes: u32) -> Option<&'a Note> {
+  let range = time_range(n.date, start, minutes);
+  a.notes.iter().find(|other| {
+    other.id != n.id
+      && other.foreign_key["user_id"] == n.foreign_key["user_id"]
+      && other.foreign_key["client_id"] != n.foreign_key["client_id"]
+      && match (other.start_time, other.duration_minutes) {
+        (Some(s), Some(m)) => ranges_overlap(range, time_range(other.date, s, m)),
+        _ => false,
+      }
+  })
+}
+
+/// every pair of a user's notes from `first` to `last` whose times overlap across clients
+pub fn find_time_overlaps(a: &NoteArchive, user_id: u32, first: NaiveDate, last: NaiveDate) -> Vec<TimeOverlap> {
+  let mut timed: Vec<&Note> = a.notes.iter()
+    .filter(|n| n.foreign_key["user_id"] == user_id && n.date >= first && n.date <= last )
+    .filter(|n| n.start_time.is_some() && n.duration_minutes.is_some() )
+    .collect();
+  timed.sort_by_key(|n| (n.date, n.start_time, n.id) );
+  let client_name = |n: &Note| match a.get_client_by_id(n.foreign_key["client_id"]) {
+    Some(c) => c.full_name(),
+    None => format!("client {}", n.foreign_key["client_id"]),
+  };
+  let mut overlaps: Vec<TimeOverlap> = vec![];
+  for (i, x) in timed.iter().enumerate() {
+    // notes are in start order, so only a contact running past midnight can reach the next day's notes
+    for y in timed[i + 1..].iter().take_while(|y| y.date <= x.date.succ() ) {
+      if x.foreign_key["client_id"] == y.foreign_key["client_id"] {
+        continue;
+      }
+      let x_range = time_range(x.date, x.start_time.unwrap(), x.duration_minutes.unwrap());
+      let y_range = time_range(y.date, y.start_time.unwrap(), y.duration_minutes.unwrap());
+      if ranges_overlap(x_range, y_range) {
+        overlaps.push(TimeOverlap {
+          date: x.date,
+          note_ids: (x.id, y.id),
+          client_names: (client_name(x), client_name(y)),
+        });
+      }
+    }
+  }
+  overlaps
+}
+
+/// one line of the mileage log
+#[derive(Debug, Clone, PartialEq)]
+pub struct MileageEntry {
+  pub note_id: u32,
+  pub date: NaiveDate,
+  pub client_name: String,
+  pub category: String,
+  pub travel: Travel,
+}
+
+/// a user's driving for a pay period, with any overlapping note times found along the way
+#[derive(Debug, Clone, PartialEq)]
+pub struct MileageLog {
+  pub user_name: String,
+  pub first: NaiveDate,
+  pub last: NaiveDate,
+  pub entries: Vec<MileageEntry>,
+  pub overlaps: Vec<TimeOverlap>,
+}
+
+impl MileageLog {
+  pub fn new(a: &NoteArchive, user_id: u32, first: NaiveDate, last: NaiveDate) -> MileageLog {
+    let user_name = match a.users.iter().find(|u| u.id == user_id ) {
+      Some(u) => u.name_and_title(),
+      None => format!("User {}", user_id),
+    };
+    let mut entries: Vec<MileageEntry> = a.notes.iter()
+      .filter(|n| n.foreign_key["user_id"] == user_id && n.date >= first && n.date <= last )
+      .filter_map(|n| n.travel.as_ref().map(|t| MileageEntry {
+        note_id: n.id,
+        date: n.date,
+        client_name: match a.get_client_by_id(n.foreign_key["client_id"]) {
+          Some(c) => c.full_name(),
+          None => String::new(),
+        },
+        category: n.category.to_string(),
+        travel: t.clone(),
+      }))
+      .collect();
+    entries.sort_by_key(|e| (e.date, e.note_id) );
+    MileageLog { user_name, first, last, entries, overlaps: find_time_overlaps(a, user_id, first, last) }
+  }
+  pub fn total_miles(&self) -> f64 {
+    self.entries.iter().map(|e| e.travel.miles ).sum()
+  }
+  pub fn total_minutes(&self) -> u32 {
+    self.entries.iter().map(|e| e.travel.minutes ).sum()
+  }
+  pub fn to_display_string(&self) -> String {
+    let mut output = format!(
+      "Mileage for {} from {} to {}\n\n",
+      self.user_name,
+      self.first.format("%Y-%m-%d"),
+      self.last.format("%Y-%m-%d"),
+    );
+    if self.entries.is_empty() {
+      output.push_str("No travel entered in this period.\n");
+    }
+    for e in &self.entries {
+      output.push_str(&format!(
+        "{}  {: <20} {}\n",
+        e.date.format("%Y-%m-%d"),
+        e.client_name,
+        e.travel.fmt_travel(),
+      ));
+    }
+    output.push_str(&format!("\nTotal: {:.1} miles, {} minutes of travel\n", self.total_miles(), self.total_minutes()));
+    for overlap in &self.overlaps {
+      output.push_str(&format!("Overlapping times: {}\n", overlap.description()));
+    }
+    output
+  }
+  pub fn to_csv(&self) -> String {
+    let mut output = String::from("date,note_id,client_name,category,start_location,end_location,miles,travel_minutes\n");
+    for e in &self.entries {
+      let fields = [
+        e.date.format("%Y-%m-%d").to_string(),
+        e.note_id.to_string(),
+        e.client_name.clone(),
+        e.category.clone(),
+        e.travel.start_location.clone().unwrap_or_default(),
+        e.travel.end_location.clone().unwrap_or_default(),
+        format!("{:.1}", e.travel.miles),
+        e.travel.minutes.to_string(),
+      ];
+      output.push_str(&fields.iter().map(|f| csv_field(f) ).collect::<Vec<String>>().join(","));
+      output.push('\n');
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::collections::HashMap;
+  use crate::billing::parse_note_time;
+
+  #[test]
+  fn finds_pay_periods() {
+    let d = |m, day| NaiveDate::from_ymd(2021, m, day);
+    assert_eq!(PayPeriod::SemiMonthly.containing(d(2, 15)), (d(2, 1), d(2, 15)));
+    assert_eq!(PayPeriod::SemiMonthly.containing(d(2, 16)), (d(2, 16), d(2, 28)));
+    let biweekly = PayPeriod::FixedDays(d(1, 4), 14);
+    assert_eq!(biweekly.containing(d(1, 17)), (d(1, 4), d(1, 17)));
+    assert_eq!(biweekly.containing(d(1, 18)), (d(1, 18), d(1, 31)));
+    assert_eq!(biweekly.containing(d(1, 1)), (NaiveDate::from_ymd(2020, 12, 21), d(1, 3)));
+  }
+
+  #[test]
+  fn logs_mileage_and_finds_overlaps() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_travel_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    let day = NaiveDate::from_ymd(2021, 3, 4);
+    let category = NoteCategory::ICCNote(ICCNoteCategory::TransportClient);
+    for (id, client_id, start) in [(1, 1, (9, 0)), (2, 2, (9, 30)), (3, 1, (10, 0))] {
+      let mut n = Note::new(id, day, category, StructureType::HomeVisit, String::new(), 1, client_id, vec![]);
+      n.set_time(Some(NaiveTime::from_hms(start.0, start.1, 0)), Some(45));
+      a.notes.push(n);
+    }
+    a.notes[0].travel = Some(Travel {
+      start_location: Some(String::from("Office")),
+      end_location: Some(String::from("12 Elm St, Apt 2")),
+      miles: 8.4,
+      minutes: 20,
+    });
+
+    let overlaps = find_time_overlaps(&a, 1, day, day);
+    assert_eq!(overlaps.iter().map(|o| o.note_ids ).collect::<Vec<(u32, u32)>>(), vec![(1, 2), (2, 3)]);
+    assert_eq!(find_overlapping_note(&a, &a.notes[2], NaiveTime::from_hms(10, 15, 0), 30).map(|n| n.id ), None);
+    assert_eq!(find_overlapping_note(&a, &a.notes[2], NaiveTime::from_hms(9, 15, 0), 30).map(|n| n.id ), Some(2));
+
+    // a contact from 23:30 lasting an hour runs into the next day
+    let late = |id, client_id, date: NaiveDate, start: (u32, u32), minutes| {
+      let mut n = Note::new(id, date, category, StructureType::HomeVisit, String::new(), 1, client_id, vec![]);
+      n.set_time(Some(NaiveTime::from_hms(start.0, start.1, 0)), Some(minutes));
+      n
+    };
+    a.notes.push(late(4, 1, day, (23, 30), 60));
+    a.notes.push(late(5, 2, day, (23, 45), 10));
+    a.notes.push(late(6, 2, day.succ(), (0, 15), 30));
+    a.notes.push(late(7, 2, day, (0, 0), 20));
+    let overlaps = find_time_overlaps(&a, 1, day, day.succ());
+    assert_eq!(overlaps.iter().map(|o| o.note_ids ).collect::<Vec<(u32, u32)>>(), vec![(1, 2), (2, 3), (4, 5), (4, 6)]);
+    assert_eq!(find_overlapping_note(&a, &a.notes[3], NaiveTime::from_hms(23, 30, 0), 60).map(|n| n.id ), Some(5));
+    assert_eq!(find_overlapping_note(&a, &late(8, 1, day, (0, 5), 10), NaiveTime::from_hms(0, 5, 0), 10).map(|n| n.id ), Some(7));
+    assert_eq!(find_overlapping_note(&a, &late(8, 1, day, (12, 0), 10), NaiveTime::from_hms(12, 0, 0), 10).map(|n| n.id ), None);
+    a.notes.truncate(3);
+
+    let log = MileageLog::new(&a, 1, day, day);
+    assert_eq!(log.entries.len(), 1);
+    assert_eq!(log.total_miles(), 8.4);
+    assert!(log.to_csv().contains(",Office,\"12 Elm St, Apt 2\",8.4,20\n"));
+    assert!(parse_miles("-1").is_err());
+  }
+
+  #[test]
+  fn finds_overlaps_for_a_time_entered_past_midnight() {
+    let filepaths: HashMap<String, String> = [
+      (String::from("archive_filepath"), String::from("test_travel_midnight_archive.json"),),
+    ].iter().cloned().collect();
+    let mut a = NoteArchive::new_test(filepaths.clone());
+    std::fs::remove_file(&filepaths["archive_filepath"]).unwrap();
+    let day = NaiveDate::from_ymd(2021, 3, 4);
+    let category = NoteCategory::ICCNote(ICCNoteCategory::CareCoordination);
+    a.notes.clear();
+    a.notes.push(Note::new(1, day, category, StructureType::PhoneCall, String::new(), 1, 1, vec![]));
+    let mut next_day = Note::new(2, day.succ(), category, StructureType::PhoneCall, String::new(), 1, 2, vec![]);
+    next_day.set_time(Some(NaiveTime::from_hms(0, 15, 0)), Some(30));
+    a.notes.push(next_day);
+
+    let (start, minutes) = parse_note_time("23:30-0:30").unwrap();
+    assert_eq!(find_overlapping_note(&a, &a.notes[0], start.unwrap(), minutes).map(|n| n.id ), Some(2));
+    a.notes[0].set_time(start, Some(minutes));
+    let overlaps = find_time_overlaps(&a, 1, day, day.succ());
+    assert_eq!(overlaps.iter().map(|o| o.note_ids ).collect::<Vec<(u32, u32)>>(), vec![(1, 2)]);
+  }
+}
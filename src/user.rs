@@ -1,7 +1,8 @@
 use std::fmt;
 use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
   pub id: u32,
   pub first_name: String,
@@ -20,7 +21,7 @@ impl PartialEq for User {
   }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EmployeeRole {
   Fp,
   Icc,
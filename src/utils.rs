@@ -1,11 +1,14 @@
-use std::fs::OpenOptions;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
-use std::io::{BufRead, BufReader};
+use std::fs;
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
+use std::convert::TryInto;
 use aes::cipher::generic_array::GenericArray;
 use aes::cipher::{BlockCipher, NewBlockCipher};
 use aes::Aes256;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::aead::{Aead, NewAead, Payload};
+use hmac::Hmac;
+use sha2::Sha256;
 
 pub fn make_ascii_titlecase(s: String) -> String {
   let mut s = s.clone();
@@ -16,106 +19,190 @@ pub fn make_ascii_titlecase(s: String) -> String {
   s
 }
 
-pub fn decrypt_file(data_fp: &str, output_fp: &str, pw: &str) -> Result<(), Error> {
+// encrypted files are stored in a versioned container:
+// magic | version | KDF iterations (u32, big endian) | salt | key check | nonce | AES-256-GCM ciphertext and tag
+// the header is authenticated along with the data, and the key check (a second block of KDF output) lets
+// a wrong password be told apart from a file that was damaged or modified after encryption.
+// files written before the container existed have no header and are read with the legacy ECB scheme.
 
-  let mut key = password_to_bytes(pw.to_string());
+pub const ENCRYPTION_MAGIC: &[u8; 5] = b"WNENC";
+pub const ENCRYPTION_VERSION: u8 = 2;
+pub const KDF_ITERATIONS: u32 = 200_000;
+/// iteration counts above this are taken as a damaged header rather than spending minutes deriving a key
+pub const MAX_KDF_ITERATIONS: u32 = 10_000_000;
+const SALT_LEN: usize = 16;
+const KEY_CHECK_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = 5 + 1 + 4 + SALT_LEN + KEY_CHECK_LEN + NONCE_LEN;
 
-  let cipher_key = GenericArray::from_mut_slice(&mut key);
-  let cipher = Aes256::new(&cipher_key);
+pub fn wrong_password_error() -> Error {
+  Error::new(ErrorKind::PermissionDenied, "Incorrect password.")
+}
 
-  let mut file = File::open(data_fp)?;
-  let mut buffer = Vec::new();
-  file.read_to_end(&mut buffer)?;
+pub fn corrupt_file_error(reason: &str) -> Error {
+  Error::new(ErrorKind::InvalidData, format!("Encrypted file is corrupt or has been modified ({}).", reason))
+}
 
-  let mut all_blocks = vec![];
+pub fn is_encrypted_container(data: &[u8]) -> bool {
+  data.len() >= ENCRYPTION_MAGIC.len() && &data[..ENCRYPTION_MAGIC.len()] == ENCRYPTION_MAGIC
+}
 
-  while buffer.len() > 0 {
-    let mut block = vec![];
-    for byte in &buffer[0..16] {
-      block.push(*byte);
-    }
-    buffer.drain(0..16);
-    let mut cipher_block = GenericArray::from_mut_slice(&mut block);
-    cipher.decrypt_block(&mut cipher_block);
-
-    let mut decrypted_block: Vec<u8> = vec![];
-
-    if buffer.is_empty() {
-      if cipher_block.iter().any(|byte| byte > &0 && byte < &16) {
-        let mut idx = 0;
-        for byte in cipher_block.clone() {
-          if byte > 0 && byte < 16 {
-            if cipher_block[idx+1..].to_vec().iter().all(|b| *b == 0) {
-              decrypted_block.append(&mut cipher_block[..idx].to_vec());
-            }
-          }
-          idx += 1;
-        }
-      }
-    } else {
-      decrypted_block.append(&mut cipher_block.to_vec());
-    }
+fn random_bytes(len: usize) -> Result<Vec<u8>, Error> {
+  let mut bytes = vec![0; len];
+  getrandom::getrandom(&mut bytes).map_err(|e| Error::other(format!("Failed to generate random bytes: {}", e)) )?;
+  Ok(bytes)
+}
 
-    all_blocks.append(&mut decrypted_block.to_vec());
-  }
+/// derives the 32 byte cipher key and the key check value from the password with PBKDF2-HMAC-SHA256
+fn derive_key(pw: &str, salt: &[u8], iterations: u32) -> (Vec<u8>, Vec<u8>) {
+  let mut output = [0u8; 64];
+  pbkdf2::pbkdf2::<Hmac<Sha256>>(pw.as_bytes(), salt, iterations, &mut output);
+  (output[..32].to_vec(), output[32..32+KEY_CHECK_LEN].to_vec())
+}
 
-  if !all_blocks.is_empty() {
-    let mut file = File::create(output_fp)?;
-  
-    file.write_all(&all_blocks)?;
+pub fn encrypt_bytes(data: &[u8], pw: &str) -> Result<Vec<u8>, Error> {
+  encrypt_bytes_with_iterations(data, pw, KDF_ITERATIONS)
+}
+
+pub fn encrypt_bytes_with_iterations(data: &[u8], pw: &str, iterations: u32) -> Result<Vec<u8>, Error> {
+  if iterations == 0 || iterations > MAX_KDF_ITERATIONS {
+    return Err(Error::new(ErrorKind::InvalidInput, format!("Key derivation needs 1 to {} iterations, not {}.", MAX_KDF_ITERATIONS, iterations)));
   }
+  let salt = random_bytes(SALT_LEN)?;
+  let nonce = random_bytes(NONCE_LEN)?;
+  let (key, key_check) = derive_key(pw, &salt, iterations);
 
-  Ok(())
+  let mut header: Vec<u8> = Vec::with_capacity(HEADER_LEN);
+  header.extend_from_slice(ENCRYPTION_MAGIC);
+  header.push(ENCRYPTION_VERSION);
+  header.extend_from_slice(&iterations.to_be_bytes());
+  header.extend_from_slice(&salt);
+  header.extend_from_slice(&key_check);
+  header.extend_from_slice(&nonce);
 
+  let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
+  let ciphertext = cipher
+    .encrypt(GenericArray::from_slice(&nonce), Payload { msg: data, aad: &header })
+    .map_err(|_| Error::other("Failed to encrypt data."))?;
+
+  let mut output = header;
+  output.extend_from_slice(&ciphertext);
+  Ok(output)
 }
 
-pub fn encrypt_file(data_fp: &str, pw: &str) -> Result<(), Error> {
+/// decrypts a container, returning a PermissionDenied error for a wrong password and InvalidData for a damaged file
+pub fn decrypt_bytes(data: &[u8], pw: &str) -> Result<Vec<u8>, Error> {
+  if !is_encrypted_container(data) {
+    return Err(corrupt_file_error("missing header"));
+  }
+  if data.len() < HEADER_LEN {
+    return Err(corrupt_file_error("header is truncated"));
+  }
+  let version = data[5];
+  if version != ENCRYPTION_VERSION {
+    return Err(corrupt_file_error(&format!("unsupported version {}", version)));
+  }
+  let iterations = u32::from_be_bytes(data[6..10].try_into().unwrap());
+  if iterations == 0 || iterations > MAX_KDF_ITERATIONS {
+    return Err(corrupt_file_error("invalid iteration count"));
+  }
+  let salt = &data[10..10+SALT_LEN];
+  let saved_key_check = &data[10+SALT_LEN..10+SALT_LEN+KEY_CHECK_LEN];
+  let nonce = &data[HEADER_LEN-NONCE_LEN..HEADER_LEN];
 
-  let mut key = password_to_bytes(pw.to_string());
+  let (key, key_check) = derive_key(pw, salt, iterations);
+  if key_check != saved_key_check {
+    return Err(wrong_password_error());
+  }
 
-  let file = OpenOptions::new()
-    .read(true)
-    .write(true)
-    .create(true)
-    .open(data_fp).unwrap();
+  let cipher = Aes256Gcm::new(GenericArray::from_slice(&key));
+  cipher
+    .decrypt(GenericArray::from_slice(nonce), Payload { msg: &data[HEADER_LEN..], aad: &data[..HEADER_LEN] })
+    .map_err(|_| corrupt_file_error("authentication failed"))
+}
 
-  let reader = BufReader::new(file);
-  let lines: Vec<std::io::Result<String>> = reader.lines().collect();
-  let mut data = lines.iter().filter(|l| l.is_ok() ).map(|l| l.as_ref().unwrap().clone() ).collect::<Vec<String>>().join("\n").as_bytes().to_vec();
+/// removes the legacy padding from the final decrypted block.
+///
+/// the legacy writer ended a file whose last chunk had L < 16 bytes with a marker byte equal to 15 - L,
+/// followed by that many zero bytes, and wrote no padding at all when the length was a multiple of 16.
+/// the marker is the first byte whose value matches its distance from the end of the block and is
+/// followed only by zeros; if there is none, the block is all data. text never contains zero bytes,
+/// so the two cases cannot be confused for the data files this was used for.
+pub fn strip_legacy_padding(block: &[u8]) -> &[u8] {
+  for (idx, byte) in block.iter().enumerate() {
+    if *byte as usize == block.len() - 1 - idx && block[idx+1..].iter().all(|b| *b == 0) {
+      return &block[..idx];
+    }
+  }
+  block
+}
+
+/// decrypts a file written before the versioned container, which has no way to detect a wrong password
+pub fn decrypt_legacy_bytes(data: &[u8], pw: &str) -> Vec<u8> {
+  let mut key = password_to_bytes(pw.to_string());
 
   let cipher_key = GenericArray::from_mut_slice(&mut key);
   let cipher = Aes256::new(&cipher_key);
 
+  let num_blocks = data.len() / 16;
   let mut all_blocks = vec![];
-  
-  while data.len() > 0 {
-    let mut block = vec![];
-    if data.len() > 15 {
-      for byte in &data[0..16] {
-        block.push(*byte);
-      }
-      data.drain(0..16);
+
+  for (i, chunk) in data.chunks_exact(16).enumerate() {
+    let mut block = GenericArray::clone_from_slice(chunk);
+    cipher.decrypt_block(&mut block);
+    if i == num_blocks - 1 {
+      all_blocks.extend_from_slice(strip_legacy_padding(&block));
     } else {
-      for byte in &data[0..] {
-        block.push(*byte);
-      }
-      let num_zeros = 15-data.len();
-      block.push(num_zeros as u8);
-      for _n in 0..num_zeros {
-        block.push(0 as u8);
-      }
-      data.clear();
+      all_blocks.extend_from_slice(&block);
     }
-    let mut cipher_block = GenericArray::from_mut_slice(&mut block);
-    cipher.encrypt_block(&mut cipher_block);
-    all_blocks.append(&mut cipher_block.to_vec());
   }
 
-  let mut file = File::create(data_fp)?;
+  all_blocks
+}
+
+/// writes to '<filepath>.tmp' and renames it over the file, so a crash mid-write leaves either the old or the new file
+pub fn write_file_atomically<P: AsRef<Path>>(filepath: P, data: &[u8]) -> Result<(), Error> {
+  let filepath = filepath.as_ref();
+  let mut tmp_name = filepath.as_os_str().to_owned();
+  tmp_name.push(".tmp");
+  let tmp_filepath = Path::new(&tmp_name);
+  {
+    let mut file = fs::File::create(tmp_filepath)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+  }
+  fs::rename(tmp_filepath, filepath)
+}
+
+/// decrypts data_fp into output_fp; files without the container header are read with the legacy scheme
+pub fn decrypt_file(data_fp: &str, output_fp: &str, pw: &str) -> Result<(), Error> {
+  let buffer = fs::read(data_fp)?;
+
+  let plaintext = if is_encrypted_container(&buffer) {
+    decrypt_bytes(&buffer, pw)?
+  } else {
+    decrypt_legacy_bytes(&buffer, pw)
+  };
+
+  write_file_atomically(output_fp, &plaintext)
+}
+
+/// encrypts the file in place. AES-GCM ciphertext is the same length as the data, so no padding is
+/// needed and every byte, including trailing newlines and empty files, round-trips exactly.
+pub fn encrypt_file(data_fp: &str, pw: &str) -> Result<(), Error> {
+  encrypt_file_with_iterations(data_fp, pw, KDF_ITERATIONS)
+}
+
+pub fn encrypt_file_with_iterations(data_fp: &str, pw: &str, iterations: u32) -> Result<(), Error> {
+  let data = match fs::read(data_fp) {
+    Ok(data) => data,
+    Err(e) if e.kind() == ErrorKind::NotFound => vec![],
+    Err(e) => return Err(e),
+  };
 
-  file.write_all(&all_blocks)?;
+  let encrypted = encrypt_bytes_with_iterations(&data, pw, iterations)?;
 
-  Ok(())
+  write_file_atomically(data_fp, &encrypted)
 }
 
 fn password_to_bytes(password: String) -> Vec<u8> {
@@ -138,4 +225,112 @@ fn password_to_bytes(password: String) -> Vec<u8> {
 
   xord_bytes
 
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn container_detects_wrong_password_and_tampering() {
+    let data = b"1 | Bob | Smith | ICC | 1 | 1#2 | \n".to_vec();
+    let encrypted = encrypt_bytes_with_iterations(&data, "correct horse", 10).unwrap();
+    assert!(is_encrypted_container(&encrypted));
+    assert_eq!(decrypt_bytes(&encrypted, "correct horse").unwrap(), data);
+
+    let wrong = decrypt_bytes(&encrypted, "wrong horse").unwrap_err();
+    assert_eq!(wrong.kind(), ErrorKind::PermissionDenied);
+
+    let mut tampered = encrypted.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert_eq!(decrypt_bytes(&tampered, "correct horse").unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let mut tampered_header = encrypted.clone();
+    tampered_header[HEADER_LEN - 1] ^= 1;
+    assert_eq!(decrypt_bytes(&tampered_header, "correct horse").unwrap_err().kind(), ErrorKind::InvalidData);
+
+    let again = encrypt_bytes_with_iterations(&data, "correct horse", 10).unwrap();
+    assert_ne!(again, encrypted);
+
+    for iterations in [0, MAX_KDF_ITERATIONS + 1, u32::MAX] {
+      let mut bad_count = encrypted.clone();
+      bad_count[6..10].copy_from_slice(&iterations.to_be_bytes());
+      let e = decrypt_bytes(&bad_count, "correct horse").unwrap_err();
+      assert_eq!(e.kind(), ErrorKind::InvalidData);
+      assert!(e.to_string().contains("invalid iteration count"));
+      assert!(encrypt_bytes_with_iterations(&data, "correct horse", iterations).is_err());
+    }
+  }
+
+  // deterministic pseudo-random bytes, biased toward newlines, zeros and small values that look like padding
+  fn sample_bytes(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    (0..len).map(|_| {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      match state % 8 {
+        0 => b'\n',
+        1 => 0,
+        2 => (state % 16) as u8,
+        _ => (state >> 8) as u8,
+      }
+    }).collect()
+  }
+
+  // the writer used before the container, kept here to check that old files still decode
+  fn encrypt_legacy_bytes(data: &[u8], pw: &str) -> Vec<u8> {
+    let mut key = password_to_bytes(pw.to_string());
+    let cipher = Aes256::new(GenericArray::from_mut_slice(&mut key));
+    let mut output = vec![];
+    for chunk in data.chunks(16) {
+      let mut block = chunk.to_vec();
+      if block.len() < 16 {
+        let num_zeros = 15 - block.len();
+        block.push(num_zeros as u8);
+        block.extend(vec![0; num_zeros]);
+      }
+      let mut cipher_block = GenericArray::clone_from_slice(&block);
+      cipher.encrypt_block(&mut cipher_block);
+      output.extend_from_slice(&cipher_block);
+    }
+    output
+  }
+
+  #[test]
+  fn container_round_trips_any_length() {
+    for len in 0..70 {
+      for seed in 0..3 {
+        let data = sample_bytes(len, seed);
+        let encrypted = encrypt_bytes_with_iterations(&data, "pw", 1).unwrap();
+        assert_eq!(decrypt_bytes(&encrypted, "pw").unwrap(), data, "length {}, seed {}", len, seed);
+      }
+    }
+  }
+
+  #[test]
+  fn legacy_text_round_trips_any_length() {
+    let text = "##### users #####\n1 | Bob | Smith | ICC | 1 | 1#2 | 3\n##### users #####\n";
+    for len in 0..text.len() {
+      let data = text[..len].as_bytes();
+      let encrypted = encrypt_legacy_bytes(data, "legacy password");
+      assert_eq!(decrypt_legacy_bytes(&encrypted, "legacy password"), data, "length {}", len);
+    }
+  }
+
+  #[test]
+  fn files_keep_trailing_newlines_and_empty_contents() {
+    for (i, contents) in ["", "\n", "one line\n", "exactly sixteen!", "two\nlines\n\n"].iter().enumerate() {
+      let fp = format!("test_utils_round_trip_{}.txt", i);
+      let out_fp = format!("test_utils_round_trip_{}_out.txt", i);
+      fs::write(&fp, contents).unwrap();
+      encrypt_file_with_iterations(&fp, "file password", 1).unwrap();
+      assert_ne!(fs::read(&fp).unwrap(), contents.as_bytes());
+      decrypt_file(&fp, &out_fp, "file password").unwrap();
+      assert_eq!(fs::read(&out_fp).unwrap(), contents.as_bytes());
+      fs::remove_file(fp).unwrap();
+      fs::remove_file(out_fp).unwrap();
+    }
+  }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::blank_enums::*;
+use crate::note::Blank;
+use crate::note::Blank::*;
+
+/// one choice offered for a fill-in blank; a custom choice asks for the text when it is picked
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillInOption {
+  pub text: String,
+  pub custom: bool,
+}
+
+impl FillInOption {
+  pub fn new(text: &str, custom: bool) -> FillInOption {
+    FillInOption { text: text.to_string(), custom }
+  }
+}
+
+/// the blanks filled from a list of choices, in menu order
+pub fn fill_in_blanks() -> Vec<Blank> {
+  vec![
+    InternalDocument,
+    ExternalDocument,
+    InternalMeeting,
+    ExternalMeeting,
+    Appearance,
+    SupportedParent,
+    ParentingSkill,
+    CarePlanningTopic,
+    YouthTopic,
+    ContactMethod,
+    ContactPurpose,
+    FulfilledContactPurpose,
+    Service,
+    MeetingMethod,
+    SignatureMethod,
+  ]
+}
+
+/// the name of a fill-in blank in the vocabulary file, such as 'InternalDocument'
+pub fn fill_in_blank_key(b: &Blank) -> String {
+  format!("{:?}", b)
+}
+
+fn options_from<T: ToString>(iter: impl Iterator<Item = T>, is_custom: impl Fn(&T) -> bool) -> Vec<FillInOption> {
+  iter.map(|fi| FillInOption { text: fi.to_string(), custom: is_custom(&fi) } ).collect()
+}
+
+/// the choices shipped with the program, from the fill-in enums
+fn built_in_options(b: &Blank) -> Vec<FillInOption> {
+  match b {
+    InternalDocument => options_from(InternalDocumentFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    ExternalDocument => options_from(ExternalDocumentFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    InternalMeeting => options_from(InternalMeetingFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    ExternalMeeting => options_from(ExternalMeetingFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    Appearance => options_from(AppearanceFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    SupportedParent => options_from(SupportedParentFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    ParentingSkill => options_from(ParentingSkillFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    CarePlanningTopic => options_from(CarePlanningTopicFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    YouthTopic => options_from(YouthTopicFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    ContactMethod => options_from(ContactMethodFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    ContactPurpose => options_from(ContactPurposeFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    FulfilledContactPurpose => options_from(FulfilledContactPurposeFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    Service => options_from(ServiceFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    MeetingMethod => options_from(MeetingMethodFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    SignatureMethod => options_from(SignatureMethodFillIn::iterator_of_blanks(), |fi| fi.is_custom() ),
+    _ => vec![],
+  }
+}
+
+/// a choice's text as written to the file, with a '\' before a leading character parse would read as markup
+fn escape_option(text: &str) -> String {
+  if text.starts_with(['*', '#', '[', '\\']) {
+    format!("\\{}", text)
+  } else {
+    text.to_string()
+  }
+}
+
+fn unescape_option(text: &str) -> &str {
+  text.strip_prefix('\\').unwrap_or(text)
+}
+
+/// the choices for each fill-in blank; lists missing from the vocabulary file keep the built-in choices.
+/// Notes save the chosen text itself, so changing a list never changes a note that was already written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabularies {
+  lists: BTreeMap<String, Vec<FillInOption>>,
+}
+
+impl Default for Vocabularies {
+  fn default() -> Self {
+    Vocabularies::built_in()
+  }
+}
+
+impl Vocabularies {
+  pub fn built_in() -> Vocabularies {
+    Vocabularies {
+      lists: fill_in_blanks().iter().map(|b| (fill_in_blank_key(b), built_in_options(b)) ).collect(),
+    }
+  }
+  /// reads a file of '[InternalDocument]' style headings, each followed by one choice per line;
+  /// a choice starting with '*' asks for custom text, lines starting with '#' are comments,
+  /// and a '\' before a choice keeps a leading '*', '#' or '[' as part of its text
+  pub fn parse(s: &str) -> Result<Vocabularies, String> {
+    let mut vocabularies = Vocabularies::built_in();
+    // the heading's key, its choices and its 1-based line number
+    let mut current: Option<(String, Vec<FillInOption>, usize)> = None;
+    for (i, line) in s.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      if let Some(heading) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']') ) {
+        let key = heading.trim();
+        if !vocabularies.lists.contains_key(key) {
+          return Err(format!("Line {} of vocabulary file: '{}' is not a fill-in blank.", i+1, key));
+        }
+        if let Some((k, options, line_number)) = current.take() {
+          vocabularies.set_list(k, options, line_number)?;
+        }
+        current = Some((key.to_string(), vec![], i+1));
+        continue;
+      }
+      let option = match line.strip_prefix('*') {
+        Some(text) => FillInOption::new(unescape_option(text.trim()), true),
+        None => FillInOption::new(unescape_option(line), false),
+      };
+      match current.as_mut() {
+        Some((_, options, _)) => options.push(option),
+        None => return Err(format!("Line {} of vocabulary file comes before any [Blank] heading.", i+1)),
+      }
+    }
+    if let Some((k, options, line_number)) = current.take() {
+      vocabularies.set_list(k, options, line_number)?;
+    }
+    Ok(vocabularies)
+  }
+  fn set_list(&mut self, key: String, options: Vec<FillInOption>, line_number: usize) -> Result<(), String> {
+    if options.is_empty() {
+      return Err(format!("Line {} of vocabulary file: the list for [{}] has no choices.", line_number, key));
+    }
+    self.lists.insert(key, options);
+    Ok(())
+  }
+  /// the built-in choices if there is no vocabulary file
+  pub fn load(filepath: &str) -> Result<Vocabularies, String> {
+    if !Path::new(filepath).exists() {
+      return Ok(Vocabularies::built_in());
+    }
+    let s = fs::read_to_string(filepath).map_err(|e| format!("Failed to read {}: {}", filepath, e) )?;
+    Vocabularies::parse(&s)
+  }
+  pub fn options(&self, b: &Blank) -> &[FillInOption] {
+    match self.lists.get(&fill_in_blank_key(b)) {
+      Some(options) => options,
+      None => &[],
+    }
+  }
+  /// every list in the format parse reads, as a starting point for an agency's own file
+  pub fn to_file_string(&self) -> String {
+    let mut output = String::from("# wrap_notes fill-in choices; a choice starting with '*' asks for custom text\n");
+    for b in fill_in_blanks() {
+      output.push_str(&format!("\n[{}]\n", fill_in_blank_key(&b)));
+      for option in self.options(&b) {
+        if option.custom {
+          output.push_str(&format!("* {}\n", escape_option(&option.text)));
+        } else {
+          output.push_str(&format!("{}\n", escape_option(&option.text)));
+        }
+      }
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_agency_lists_over_built_in_ones() {
+    let vocabularies = Vocabularies::parse("\
+      # our forms\n\
+      [InternalDocument]\n\
+      Intake Packet\n\
+      Youth Safety Plan\n\
+      * other form\n\
+    ").unwrap();
+    assert_eq!(vocabularies.options(&InternalDocument), &[
+      FillInOption::new("Intake Packet", false),
+      FillInOption::new("Youth Safety Plan", false),
+      FillInOption::new("other form", true),
+    ]);
+    assert_eq!(vocabularies.options(&Service), Vocabularies::built_in().options(&Service));
+    assert!(Vocabularies::built_in().options(&InternalDocument).iter().any(|o| o.text == "CBHI Paperwork" ));
+    assert_eq!(Vocabularies::parse(&vocabularies.to_file_string()).unwrap(), vocabularies);
+
+    assert!(Vocabularies::parse("[Lunch]\nSandwich").is_err());
+    assert!(Vocabularies::parse("Sandwich\n[Service]").is_err());
+    assert_eq!(
+      Vocabularies::parse("# forms\n[Service]\n[MeetingMethod]\nvia Zoom"),
+      Err(String::from("Line 2 of vocabulary file: the list for [Service] has no choices.")),
+    );
+    assert!(Vocabularies::parse("[Service]\nCase management\n[MeetingMethod]").unwrap_err().starts_with("Line 3 "));
+    assert!(Vocabularies::built_in().options(&CustomBlank).is_empty());
+
+    let marked = Vocabularies::parse("[Service]\n\\*starred service\n\\# of visits\n\\[bracketed]\n\\\\backslash\n* \\*custom").unwrap();
+    assert_eq!(marked.options(&Service), &[
+      FillInOption::new("*starred service", false),
+      FillInOption::new("# of visits", false),
+      FillInOption::new("[bracketed]", false),
+      FillInOption::new("\\backslash", false),
+      FillInOption::new("*custom", true),
+    ]);
+    assert_eq!(Vocabularies::parse(&marked.to_file_string()).unwrap(), marked);
+  }
+}